@@ -0,0 +1,298 @@
+//! API authentication.
+//!
+//! Requests authenticate with a static API key (`X-API-Key` header) or a
+//! signed bearer token (`Authorization: Bearer <token>`). Both carry
+//! [`Scope`]s, and every route requires one scope (see [`required_scope`]).
+//!
+//! Tokens are `<payload>.<signature>`: the base64url-encoded JSON
+//! [`TokenClaims`] and their HMAC-SHA256 signature with the server's secret.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// Header carrying a static API key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// A permission granted to an API key or token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// Stateless conversion: export, validate, import and merge.
+    Export,
+    /// Saved levels, their revisions, share links and collaborative editing.
+    Store,
+    /// Everything, including minting tokens.
+    Admin,
+}
+
+impl Scope {
+    /// Looks up a scope by name (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "export" => Some(Scope::Export),
+            "store" => Some(Scope::Store),
+            "admin" => Some(Scope::Admin),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scope::Export => "export",
+            Scope::Store => "store",
+            Scope::Admin => "admin",
+        }
+    }
+}
+
+/// Authentication error variants with associated context data.
+#[derive(Debug, Clone)]
+pub enum AuthError {
+    /// No API key or bearer token was sent.
+    MissingCredentials,
+    /// The API key is unknown, or the token is malformed or wrongly signed.
+    InvalidCredentials,
+    TokenExpired,
+    /// The credentials are valid but lack the route's scope.
+    InsufficientScope { required: Scope },
+}
+
+impl AuthError {
+    /// Returns a machine-readable error code for API responses.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            AuthError::MissingCredentials => "UNAUTHORIZED",
+            AuthError::InvalidCredentials => "INVALID_CREDENTIALS",
+            AuthError::TokenExpired => "TOKEN_EXPIRED",
+            AuthError::InsufficientScope { .. } => "FORBIDDEN",
+        }
+    }
+
+    /// Returns a human-readable error message for display to users.
+    pub fn message(&self) -> String {
+        match self {
+            AuthError::MissingCredentials => {
+                "Authentication required. Send an X-API-Key header or an Authorization: Bearer token.".to_string()
+            }
+            AuthError::InvalidCredentials => "The API key or token is not valid.".to_string(),
+            AuthError::TokenExpired => "The token has expired.".to_string(),
+            AuthError::InsufficientScope { required } => {
+                format!("This route requires the '{}' scope.", required.name())
+            }
+        }
+    }
+
+    /// Returns `true` for errors that should be answered with `403 Forbidden`
+    /// rather than `401 Unauthorized`.
+    pub fn is_forbidden(&self) -> bool {
+        matches!(self, AuthError::InsufficientScope { .. })
+    }
+}
+
+/// Contents of a signed bearer token.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenClaims {
+    /// Who the token was issued to, for logs.
+    pub subject: String,
+    pub scopes: Vec<Scope>,
+    /// Unix timestamp in milliseconds after which the token is rejected.
+    pub expires_at: u64,
+    /// Name of the validation limits tier granted to the holder.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+}
+
+/// The authenticated caller of a request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Principal {
+    /// The API key's name or the token's subject.
+    pub subject: String,
+    pub scopes: Vec<Scope>,
+    /// Validation limits tier; the server's default limits apply when absent.
+    pub tier: Option<String>,
+}
+
+impl Principal {
+    /// Returns `true` if the caller has `scope`; [`Scope::Admin`] grants every scope.
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.iter().any(|&granted| granted == scope || granted == Scope::Admin)
+    }
+}
+
+/// A static API key and the scopes it grants.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKey {
+    /// Name used in logs and as the principal's subject, never the key itself.
+    pub name: String,
+    pub key: String,
+    pub scopes: Vec<Scope>,
+    /// Validation limits tier, e.g. `pro` for larger levels.
+    #[serde(default)]
+    pub tier: Option<String>,
+}
+
+/// API keys and token secret used to authenticate requests.
+#[derive(Debug, Clone, Default)]
+pub struct AuthConfig {
+    pub api_keys: Vec<ApiKey>,
+    /// HMAC secret for bearer tokens. Tokens are rejected when absent.
+    pub token_secret: Option<Vec<u8>>,
+}
+
+impl AuthConfig {
+    /// Returns `true` if any credentials are configured. Without any, the
+    /// server runs unauthenticated.
+    pub fn is_enabled(&self) -> bool {
+        !self.api_keys.is_empty() || self.token_secret.is_some()
+    }
+
+    /// Authenticates a request from its `X-API-Key` and `Authorization` header
+    /// values at time `now` (Unix milliseconds).
+    pub fn authenticate(
+        &self,
+        api_key: Option<&str>,
+        authorization: Option<&str>,
+        now: u64,
+    ) -> Result<Principal, AuthError> {
+        if let Some(key) = api_key {
+            return self.authenticate_key(key);
+        }
+        let Some(authorization) = authorization else {
+            return Err(AuthError::MissingCredentials);
+        };
+        let token = authorization
+            .strip_prefix("Bearer ")
+            .ok_or(AuthError::InvalidCredentials)?;
+        let secret = self.token_secret.as_deref().ok_or(AuthError::InvalidCredentials)?;
+        let claims = verify_token(secret, token.trim())?;
+        if now >= claims.expires_at {
+            return Err(AuthError::TokenExpired);
+        }
+        Ok(Principal {
+            subject: claims.subject,
+            scopes: claims.scopes,
+            tier: claims.tier,
+        })
+    }
+
+    fn authenticate_key(&self, key: &str) -> Result<Principal, AuthError> {
+        // Compare against every key so timing does not reveal which one matched
+        let mut matched = None;
+        for api_key in &self.api_keys {
+            if constant_time_eq(api_key.key.as_bytes(), key.as_bytes()) {
+                matched = Some(api_key);
+            }
+        }
+        let api_key = matched.ok_or(AuthError::InvalidCredentials)?;
+        Ok(Principal {
+            subject: api_key.name.clone(),
+            scopes: api_key.scopes.clone(),
+            tier: api_key.tier.clone(),
+        })
+    }
+}
+
+/// Parses API keys from `name:key:scope,scope[:tier];name:key:scope` (e.g.
+/// `ci:s3cret:export;partner:p4rtner:export:pro`). Names and keys cannot
+/// contain `:` or `;`.
+pub fn parse_api_keys(spec: &str) -> Result<Vec<ApiKey>, String> {
+    let mut keys = Vec::new();
+    for entry in spec.split(';').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let mut parts = entry.splitn(4, ':');
+        let (Some(name), Some(key), Some(scopes)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(format!("API key entry '{}' must be name:key:scopes", name_of(entry)));
+        };
+        if key.is_empty() {
+            return Err(format!("API key '{}' is empty", name));
+        }
+        let scopes = parse_scopes(scopes).map_err(|e| format!("API key '{}': {}", name, e))?;
+        let tier = parts.next().map(str::trim);
+        if tier == Some("") {
+            return Err(format!("API key '{}' has an empty tier", name));
+        }
+        keys.push(ApiKey {
+            name: name.to_string(),
+            key: key.to_string(),
+            scopes,
+            tier: tier.map(str::to_string),
+        });
+    }
+    Ok(keys)
+}
+
+/// Parses a comma-separated scope list such as `export,store`.
+pub fn parse_scopes(spec: &str) -> Result<Vec<Scope>, String> {
+    let scopes = spec
+        .split(',')
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| Scope::from_name(name).ok_or_else(|| format!("unknown scope '{}'", name.trim())))
+        .collect::<Result<Vec<_>, _>>()?;
+    if scopes.is_empty() {
+        return Err("at least one scope is required".to_string());
+    }
+    Ok(scopes)
+}
+
+/// Returns the part of an API key entry before the key, so errors never echo keys.
+fn name_of(entry: &str) -> &str {
+    entry.split(':').next().unwrap_or_default()
+}
+
+/// Returns the scope a request needs, or `None` for public routes.
+///
+/// Public routes are the health checks and share links (whose unguessable
+/// token is the credential), plus CORS preflight requests.
+pub fn required_scope(method: &str, path: &str) -> Option<Scope> {
+    if method.eq_ignore_ascii_case("OPTIONS") {
+        return None;
+    }
+    match path {
+        "/health" | "/api/health" => None,
+        "/api/export" | "/api/validate" | "/api/playability" | "/api/import" | "/api/merge" => Some(Scope::Export),
+        "/api/tokens" => Some(Scope::Admin),
+        _ if path.starts_with("/api/shared/") => None,
+        _ if path == "/api/levels" || path.starts_with("/api/levels/") => Some(Scope::Store),
+        _ => Some(Scope::Admin),
+    }
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Signs claims into a bearer token.
+pub fn issue_token(secret: &[u8], claims: &TokenClaims) -> String {
+    let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims).unwrap_or_default());
+    let signature = URL_SAFE_NO_PAD.encode(sign(secret, payload.as_bytes()));
+    format!("{}.{}", payload, signature)
+}
+
+/// Checks a token's signature and decodes its claims. Expiry is not checked.
+pub fn verify_token(secret: &[u8], token: &str) -> Result<TokenClaims, AuthError> {
+    let (payload, signature) = token.split_once('.').ok_or(AuthError::InvalidCredentials)?;
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| AuthError::InvalidCredentials)?;
+    let mut mac = HmacSha256::new_from_slice(secret).map_err(|_| AuthError::InvalidCredentials)?;
+    mac.update(payload.as_bytes());
+    mac.verify_slice(&signature).map_err(|_| AuthError::InvalidCredentials)?;
+
+    let payload = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|_| AuthError::InvalidCredentials)?;
+    serde_json::from_slice(&payload).map_err(|_| AuthError::InvalidCredentials)
+}
+
+fn sign(secret: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Compares two byte strings in time independent of where they differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
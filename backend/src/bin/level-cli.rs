@@ -0,0 +1,332 @@
+//! Command-line exporter for Roblox Level Builder.
+//!
+//! Runs the same migration, validation and generation as `/api/export` on Space
+//! JSON files or stdin, so levels can be converted offline or in build scripts.
+//! Each failure kind exits with its own status code (see [`USAGE`]).
+
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use backend::migrate::migrate;
+use backend::rbxlx::{export_warnings, generate_export, ExportOptions, OutputFormat};
+use backend::spawn::SpawnClearance;
+use backend::validation::{validate_space_json, ValidationError, ValidationLimits, ValidationWarning};
+
+const USAGE: &str = "\
+Usage: level-cli [OPTIONS] [INPUT]...
+
+Converts Space JSON files into Roblox place or model files.
+
+Each INPUT is a file path or a glob pattern (e.g. 'levels/*.json'). With no
+INPUT, or INPUT '-', Space JSON is read from stdin and written to stdout.
+File inputs are written next to the input with the format's extension.
+
+Options:
+  -f, --format <FORMAT>  Output format: rbxlx (default), rbxl, rbxmx or rbxm
+  -o, --output <PATH>    Output file for a single input ('-' for stdout)
+  -d, --out-dir <DIR>    Directory for output files
+  -m, --merge            Merge contiguous same-color blocks into larger Parts
+      --max-blocks <N>   Block limit to validate against (default 10000)
+      --strict           Reject levels with blocks not connected to the ground
+      --spawn <MODE>     Blocks inside the SpawnLocation: keep (default), relocate
+                         the spawn to the nearest clear spot, or clear the blocks
+  -h, --help             Print this help
+
+Exit codes:
+  0   Success
+  1   Invalid command-line arguments
+  2   Input or output file error
+  3   Invalid Space JSON payload or unsupported schema version
+  4   Place generation failed
+  10  INVALID_SCHEMA_VERSION
+  11  BLOCK_COUNT_EXCEEDED
+  12  COORDINATE_OUT_OF_BOUNDS
+  13  INVALID_COLOR_FORMAT
+  14  DUPLICATE_POSITION
+  16  INVALID_MATERIAL
+  17  INVALID_BLOCK_KIND
+  18  PROPERTY_OUT_OF_RANGE
+  19  INVALID_BLOCK_SIZE
+  21  INVALID_WORLD_SETTING
+  22  INVALID_TEAM
+  23  INVALID_SPAWN_POINT
+  24  UNKNOWN_TEAM
+  25  FLOATING_BLOCKS (with --strict)
+  26  TEAM_COUNT_EXCEEDED
+  27  SPAWN_COUNT_EXCEEDED
+
+Validation warnings (e.g. floating blocks) and the changes made migrating an
+older schema version are printed to stderr and do not change the exit code.
+
+When converting several files, every file is attempted and the exit code is
+that of the first failure.";
+
+const EXIT_USAGE: u8 = 1;
+const EXIT_IO: u8 = 2;
+const EXIT_INVALID_PAYLOAD: u8 = 3;
+const EXIT_GENERATION: u8 = 4;
+
+/// Returns the exit code reported for a validation error.
+fn validation_exit_code(error: &ValidationError) -> u8 {
+    match error {
+        ValidationError::InvalidSchemaVersion { .. } => 10,
+        ValidationError::BlockCountExceeded { .. } => 11,
+        ValidationError::CoordinateOutOfBounds { .. } => 12,
+        ValidationError::InvalidColorFormat { .. } => 13,
+        ValidationError::DuplicatePosition { .. } => 14,
+        ValidationError::InvalidMaterial { .. } => 16,
+        ValidationError::InvalidBlockKind { .. } => 17,
+        ValidationError::PropertyOutOfRange { .. } => 18,
+        ValidationError::InvalidBlockSize { .. } => 19,
+        ValidationError::InvalidWorldSetting { .. } => 21,
+        ValidationError::InvalidTeam { .. } => 22,
+        ValidationError::InvalidSpawnPoint { .. } => 23,
+        ValidationError::UnknownTeam { .. } => 24,
+        ValidationError::FloatingBlocks { .. } => 25,
+        ValidationError::TeamCountExceeded { .. } => 26,
+        ValidationError::SpawnCountExceeded { .. } => 27,
+    }
+}
+
+/// A failed conversion: exit code plus the message printed to stderr.
+struct Failure {
+    code: u8,
+    message: String,
+}
+
+impl Failure {
+    fn new(code: u8, message: impl Into<String>) -> Self {
+        Failure {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Parsed command-line arguments.
+#[derive(Default)]
+struct Args {
+    inputs: Vec<String>,
+    format: OutputFormat,
+    output: Option<String>,
+    out_dir: Option<PathBuf>,
+    merge: bool,
+    spawn_clearance: SpawnClearance,
+    limits: ValidationLimits,
+    help: bool,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or_else(|| format!("Missing value for '{}'.", flag));
+        match arg.as_str() {
+            "-h" | "--help" => parsed.help = true,
+            "-m" | "--merge" => parsed.merge = true,
+            "--strict" => parsed.limits.reject_floating_blocks = true,
+            "-f" | "--format" => {
+                let name = value(&arg)?;
+                parsed.format = OutputFormat::from_name(&name).ok_or_else(|| {
+                    format!("Unsupported format: '{}'. Supported formats: rbxlx, rbxl, rbxmx, rbxm.", name)
+                })?;
+            }
+            "--spawn" => {
+                let name = value(&arg)?;
+                parsed.spawn_clearance = SpawnClearance::from_name(&name).ok_or_else(|| {
+                    format!("Unsupported spawn mode: '{}'. Supported modes: keep, relocate, clear.", name)
+                })?;
+            }
+            "-o" | "--output" => parsed.output = Some(value(&arg)?),
+            "-d" | "--out-dir" => parsed.out_dir = Some(PathBuf::from(value(&arg)?)),
+            "--max-blocks" => {
+                let count = value(&arg)?;
+                parsed.limits.max_blocks = count
+                    .parse()
+                    .ok()
+                    .filter(|&max_blocks: &usize| max_blocks > 0)
+                    .ok_or_else(|| format!("Invalid --max-blocks '{}'. Expected a positive integer.", count))?;
+            }
+            "-" => parsed.inputs.push(arg),
+            flag if flag.starts_with('-') => return Err(format!("Unknown option '{}'.", flag)),
+            _ => parsed.inputs.push(arg),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Where one conversion reads from and writes to. `None` means stdin/stdout.
+struct Job {
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+}
+
+/// Expands inputs (and glob patterns) into conversion jobs.
+fn plan_jobs(args: &Args) -> Result<Vec<Job>, Failure> {
+    let mut inputs = Vec::new();
+    for input in &args.inputs {
+        if input == "-" {
+            inputs.push(None);
+            continue;
+        }
+        let paths: Vec<PathBuf> = glob::glob(input)
+            .map_err(|e| Failure::new(EXIT_USAGE, format!("Invalid pattern '{}': {}", input, e)))?
+            .filter_map(Result::ok)
+            .collect();
+        if paths.is_empty() {
+            return Err(Failure::new(EXIT_IO, format!("No files match '{}'.", input)));
+        }
+        inputs.extend(paths.into_iter().map(Some));
+    }
+    if inputs.is_empty() {
+        inputs.push(None);
+    }
+
+    if args.output.is_some() && inputs.len() > 1 {
+        return Err(Failure::new(
+            EXIT_USAGE,
+            "'--output' requires a single input; use '--out-dir' for batch conversion.",
+        ));
+    }
+    if inputs.iter().filter(|input| input.is_none()).count() > 1 {
+        return Err(Failure::new(EXIT_USAGE, "stdin ('-') can only be read once."));
+    }
+
+    let extension = args.format.extension();
+    Ok(inputs
+        .into_iter()
+        .map(|input| {
+            let output = match (&args.output, &input) {
+                (Some(output), _) if output == "-" => None,
+                (Some(output), _) => Some(PathBuf::from(output)),
+                (None, None) => args.out_dir.as_ref().map(|dir| dir.join(format!("level.{}", extension))),
+                (None, Some(path)) => {
+                    let file_name = Path::new(path.file_name().unwrap_or_default()).with_extension(extension);
+                    Some(match &args.out_dir {
+                        Some(dir) => dir.join(file_name),
+                        None => path.with_file_name(file_name),
+                    })
+                }
+            };
+            Job { input, output }
+        })
+        .collect())
+}
+
+/// What converting a level reported besides its file content.
+struct Notes {
+    /// Changes made migrating the level to the current schema version.
+    migrations: Vec<String>,
+    /// The exported level's validation warnings.
+    warnings: Vec<ValidationWarning>,
+}
+
+/// Converts one Space JSON document into place or model file content, along
+/// with the level's migration changes and validation warnings.
+fn convert(source: &str, args: &Args) -> Result<(Vec<u8>, Notes), Failure> {
+    let raw_payload: serde_json::Value = serde_json::from_str(source)
+        .map_err(|e| Failure::new(EXIT_INVALID_PAYLOAD, format!("INVALID_JSON: {}", e)))?;
+    let (payload, report) = migrate(raw_payload)
+        .map_err(|e| Failure::new(EXIT_INVALID_PAYLOAD, format!("{}: {}", e.error_code(), e.message())))?;
+    validate_space_json(&payload, &args.limits).map_err(|e| {
+        Failure::new(validation_exit_code(&e), format!("{}: {}", e.error_code(), e.message()))
+    })?;
+
+    let options = ExportOptions {
+        merge_blocks: args.merge,
+        spawn_clearance: args.spawn_clearance,
+    };
+    let content = generate_export(&payload, &options, args.format).map_err(|e| {
+        Failure::new(
+            EXIT_GENERATION,
+            format!("Failed to generate .{} file: {}", args.format.extension(), e),
+        )
+    })?;
+    let notes = Notes {
+        migrations: report.changes,
+        warnings: export_warnings(&payload, &args.limits, &options),
+    };
+    Ok((content, notes))
+}
+
+/// Runs a single job, reading its input and writing its output. Returns the
+/// level's migration changes and validation warnings.
+fn run_job(job: &Job, args: &Args) -> Result<Notes, Failure> {
+    let source = match &job.input {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| Failure::new(EXIT_IO, format!("Failed to read: {}", e)))?,
+        None => {
+            let mut source = String::new();
+            io::stdin()
+                .read_to_string(&mut source)
+                .map_err(|e| Failure::new(EXIT_IO, format!("Failed to read stdin: {}", e)))?;
+            source
+        }
+    };
+
+    let (content, notes) = convert(&source, args)?;
+
+    match &job.output {
+        Some(path) => fs::write(path, content)
+            .map_err(|e| Failure::new(EXIT_IO, format!("Failed to write {}: {}", path.display(), e)))?,
+        None => io::stdout()
+            .write_all(&content)
+            .map_err(|e| Failure::new(EXIT_IO, format!("Failed to write stdout: {}", e)))?,
+    }
+    Ok(notes)
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+    if args.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let jobs = match plan_jobs(&args) {
+        Ok(jobs) => jobs,
+        Err(failure) => {
+            eprintln!("error: {}", failure.message);
+            return ExitCode::from(failure.code);
+        }
+    };
+
+    let mut first_failure = None;
+    for job in &jobs {
+        let input_name = job
+            .input
+            .as_ref()
+            .map_or_else(|| "<stdin>".to_string(), |path| path.display().to_string());
+        match run_job(job, &args) {
+            Ok(notes) => {
+                for change in notes.migrations {
+                    eprintln!("{}: migration: {}", input_name, change);
+                }
+                for warning in notes.warnings {
+                    eprintln!("{}: warning: {}: {}", input_name, warning.warning_code(), warning.message());
+                }
+                if let Some(output) = &job.output {
+                    eprintln!("{} -> {}", input_name, output.display());
+                }
+            }
+            Err(failure) => {
+                eprintln!("{}: {}", input_name, failure.message);
+                first_failure.get_or_insert(failure.code);
+            }
+        }
+    }
+
+    match first_failure {
+        Some(code) => ExitCode::from(code),
+        None => ExitCode::SUCCESS,
+    }
+}
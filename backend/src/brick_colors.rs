@@ -0,0 +1,111 @@
+//! Roblox BrickColor palette.
+//!
+//! Maps hex colors to the nearest `BrickColor` number. Team colors in Roblox are
+//! BrickColors rather than free RGB values, so team colors from Space JSON are
+//! snapped to this palette.
+
+use crate::rbxlx::hex_to_color3;
+
+/// Supported BrickColors as `(number, name, (r, g, b))` entries.
+pub const BRICK_COLORS: &[(u16, &str, (u8, u8, u8))] = &[
+    (1, "White", (242, 243, 243)),
+    (5, "Brick yellow", (215, 197, 154)),
+    (18, "Nougat", (204, 142, 105)),
+    (21, "Bright red", (196, 40, 28)),
+    (23, "Bright blue", (13, 105, 172)),
+    (24, "Bright yellow", (245, 205, 48)),
+    (26, "Black", (27, 42, 53)),
+    (28, "Dark green", (40, 127, 71)),
+    (37, "Bright green", (75, 151, 75)),
+    (38, "Dark orange", (160, 95, 53)),
+    (45, "Light blue", (180, 210, 228)),
+    (102, "Medium blue", (110, 153, 202)),
+    (104, "Bright violet", (107, 50, 124)),
+    (106, "Bright orange", (218, 133, 65)),
+    (119, "Br. yellowish green", (164, 189, 71)),
+    (141, "Earth green", (39, 70, 45)),
+    (192, "Reddish brown", (105, 64, 40)),
+    (194, "Medium stone grey", (163, 162, 165)),
+    (199, "Dark stone grey", (99, 95, 98)),
+    (208, "Light stone grey", (229, 228, 223)),
+    (226, "Cool yellow", (253, 234, 141)),
+    (1001, "Institutional white", (248, 248, 248)),
+    (1002, "Mid gray", (205, 205, 205)),
+    (1003, "Really black", (17, 17, 17)),
+    (1004, "Really red", (255, 0, 0)),
+    (1005, "Deep orange", (255, 176, 0)),
+    (1006, "Alder", (180, 128, 255)),
+    (1009, "New Yeller", (255, 255, 0)),
+    (1010, "Really blue", (0, 0, 255)),
+    (1011, "Navy blue", (0, 32, 96)),
+    (1012, "Deep blue", (33, 84, 185)),
+    (1013, "Cyan", (4, 175, 236)),
+    (1014, "CGA brown", (170, 85, 0)),
+    (1015, "Magenta", (170, 0, 170)),
+    (1016, "Pink", (255, 102, 204)),
+    (1018, "Teal", (18, 238, 212)),
+    (1019, "Toothpaste", (0, 255, 255)),
+    (1020, "Lime green", (0, 255, 0)),
+    (1021, "Camo", (58, 125, 21)),
+    (1022, "Grime", (127, 142, 100)),
+    (1023, "Lavender", (140, 91, 159)),
+    (1024, "Pastel light blue", (175, 221, 255)),
+    (1025, "Pastel orange", (255, 201, 201)),
+    (1026, "Pastel violet", (177, 167, 255)),
+    (1027, "Pastel blue-green", (159, 243, 233)),
+    (1028, "Pastel green", (204, 255, 204)),
+    (1029, "Pastel yellow", (255, 255, 204)),
+    (1030, "Pastel brown", (255, 204, 153)),
+    (1031, "Royal purple", (98, 37, 209)),
+    (1032, "Hot pink", (255, 0, 191)),
+];
+
+/// BrickColors handed out, in order, to teams that do not choose a color.
+const DEFAULT_TEAM_COLORS: &[u16] = &[21, 23, 37, 24, 106, 104, 1013, 1016];
+
+/// Returns the BrickColor number closest to a hex color, or `None` if the hex is invalid.
+pub fn nearest_brick_color(hex: &str) -> Option<u16> {
+    let color = hex_to_color3(hex).ok()?;
+    let target = [color.r, color.g, color.b].map(|channel| (channel * 255.0).round() as i32);
+
+    BRICK_COLORS
+        .iter()
+        .min_by_key(|&&(_, _, (r, g, b))| {
+            let rgb = [r as i32, g as i32, b as i32];
+            (0..3).map(|i| (rgb[i] - target[i]).pow(2)).sum::<i32>()
+        })
+        .map(|&(number, _, _)| number)
+}
+
+/// Returns the name of a BrickColor number from [`BRICK_COLORS`].
+pub fn brick_color_name(number: u16) -> Option<&'static str> {
+    BRICK_COLORS
+        .iter()
+        .find(|&&(value, _, _)| value == number)
+        .map(|&(_, name, _)| name)
+}
+
+/// Resolves the BrickColor of every team, in order.
+///
+/// Teams with a hex color get the nearest BrickColor; teams without one get the
+/// next default team color (then any palette color) not already taken. Entries
+/// are `None` only for invalid hex colors.
+pub fn resolve_team_colors(colors: &[Option<&str>]) -> Vec<Option<u16>> {
+    let mut resolved: Vec<Option<u16>> = colors
+        .iter()
+        .map(|color| color.and_then(nearest_brick_color))
+        .collect();
+
+    let mut unused = DEFAULT_TEAM_COLORS
+        .iter()
+        .copied()
+        .chain(BRICK_COLORS.iter().map(|&(number, _, _)| number));
+    for (index, color) in colors.iter().enumerate() {
+        if color.is_some() {
+            continue;
+        }
+        let next = unused.find(|number| !resolved.contains(&Some(*number)));
+        resolved[index] = next;
+    }
+    resolved
+}
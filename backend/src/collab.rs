@@ -0,0 +1,371 @@
+//! Real-time collaborative editing.
+//!
+//! Clients editing the same saved level join its [`Room`] and send block
+//! [`Operation`]s. Each operation is checked with the rules of `validation.rs`
+//! and applied to the room's authoritative [`LiveLevel`], then broadcast to
+//! every client in the room as a [`ServerMessage::Applied`]. Operations are
+//! checked against the sending client's [`ValidationLimits`].
+
+use crate::merge::{merge_levels, MergeConflict};
+use crate::models::{Block, SpaceJSON};
+use crate::storage::{LevelStore, StorageError, StoredLevel};
+use crate::validation::{
+    validate_block, validate_block_count, validate_color_format, validate_space_json, ValidationError,
+    ValidationLimits,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::sync::broadcast;
+
+/// Number of unsent broadcasts kept per client before it must resync from a snapshot.
+const ROOM_CHANNEL_CAPACITY: usize = 256;
+
+/// A block edit sent by a client, e.g. `{ "op": "recolor", "x": 0, "y": 0, "z": 0, "color": "#FF0000" }`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Operation {
+    /// Places a block at an empty position.
+    Add { block: Block },
+    /// Removes the block at a position.
+    Remove { x: i32, y: i32, z: i32 },
+    /// Changes the color of the block at a position.
+    Recolor { x: i32, y: i32, z: i32, color: String },
+}
+
+/// Collaborative editing error variants with associated context data.
+#[derive(Debug, Clone)]
+pub enum CollabError {
+    /// The operation would make the level invalid.
+    Invalid(ValidationError),
+    /// A remove or recolor targeted a position without a block.
+    NoBlockAtPosition { x: i32, y: i32, z: i32 },
+    /// The client sent something that is not an [`Operation`].
+    InvalidOperation { reason: String },
+    /// Loading or saving the level failed.
+    Storage(StorageError),
+}
+
+impl CollabError {
+    /// Returns a machine-readable error code for API responses.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            CollabError::Invalid(error) => error.error_code(),
+            CollabError::NoBlockAtPosition { .. } => "NO_BLOCK_AT_POSITION",
+            CollabError::InvalidOperation { .. } => "INVALID_OPERATION",
+            CollabError::Storage(error) => error.error_code(),
+        }
+    }
+
+    /// Returns a human-readable error message for display to users.
+    pub fn message(&self) -> String {
+        match self {
+            CollabError::Invalid(error) => error.message(),
+            CollabError::NoBlockAtPosition { x, y, z } => {
+                format!("There is no block at ({}, {}, {}).", x, y, z)
+            }
+            CollabError::InvalidOperation { reason } => format!("Invalid operation: {}", reason),
+            CollabError::Storage(error) => error.message(),
+        }
+    }
+}
+
+impl From<ValidationError> for CollabError {
+    fn from(error: ValidationError) -> Self {
+        CollabError::Invalid(error)
+    }
+}
+
+impl From<StorageError> for CollabError {
+    fn from(error: StorageError) -> Self {
+        CollabError::Storage(error)
+    }
+}
+
+/// A message sent from the server to collaborating clients.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ServerMessage {
+    /// The whole level, sent on joining and whenever a client falls behind.
+    Snapshot { version: u64, space: SpaceJSON },
+    /// An operation every client must apply, in `version` order.
+    Applied { version: u64, operation: Operation },
+    /// An operation (or message) from this client that was not applied.
+    Rejected {
+        error: &'static str,
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        operation: Option<Operation>,
+    },
+}
+
+impl ServerMessage {
+    /// Builds the rejection sent to a client whose operation failed.
+    pub fn rejected(error: &CollabError, operation: Option<Operation>) -> Self {
+        ServerMessage::Rejected {
+            error: error.error_code(),
+            message: error.message(),
+            operation,
+        }
+    }
+}
+
+/// The authoritative copy of a level under collaborative editing.
+#[derive(Debug, Clone)]
+pub struct LiveLevel {
+    space: SpaceJSON,
+    version: u64,
+}
+
+impl LiveLevel {
+    /// Starts editing `space` at version 0.
+    pub fn new(space: SpaceJSON) -> Self {
+        LiveLevel { space, version: 0 }
+    }
+
+    pub fn space(&self) -> &SpaceJSON {
+        &self.space
+    }
+
+    /// Number of operations applied so far.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Checks and applies one operation under `limits`, returning the new version.
+    ///
+    /// Added blocks get the per-block, block count and duplicate checks of
+    /// [`validate_space_json`]; spawn blocks are checked against the whole level
+    /// since they depend on its teams and world settings. Recolors get the
+    /// color format check. A rejected operation leaves the level unchanged.
+    pub fn apply(&mut self, operation: &Operation, limits: &ValidationLimits) -> Result<u64, CollabError> {
+        match operation {
+            Operation::Add { block } => {
+                let index = self.space.blocks.len();
+                validate_block_count(index + 1, limits)?;
+                validate_block(block, index, limits)?;
+                if self.position_of(block.x, block.y, block.z).is_some() {
+                    return Err(ValidationError::DuplicatePosition {
+                        x: block.x,
+                        y: block.y,
+                        z: block.z,
+                        index,
+                    }
+                    .into());
+                }
+                if block.spawn.is_some() {
+                    let mut candidate = self.space.clone();
+                    candidate.blocks.push(block.clone());
+                    validate_space_json(&candidate, limits)?;
+                }
+                self.space.blocks.push(block.clone());
+            }
+            Operation::Remove { x, y, z } => {
+                let index = self.require_position(*x, *y, *z)?;
+                self.space.blocks.remove(index);
+            }
+            Operation::Recolor { x, y, z, color } => {
+                let index = self.require_position(*x, *y, *z)?;
+                validate_color_format(color, index)?;
+                self.space.blocks[index].color = color.clone();
+            }
+        }
+        self.version += 1;
+        Ok(self.version)
+    }
+
+    fn position_of(&self, x: i32, y: i32, z: i32) -> Option<usize> {
+        self.space
+            .blocks
+            .iter()
+            .position(|block| (block.x, block.y, block.z) == (x, y, z))
+    }
+
+    fn require_position(&self, x: i32, y: i32, z: i32) -> Result<usize, CollabError> {
+        self.position_of(x, y, z)
+            .ok_or(CollabError::NoBlockAtPosition { x, y, z })
+    }
+}
+
+/// One level's editing session, shared by every client editing it.
+pub struct Room {
+    state: Mutex<RoomState>,
+    updates: broadcast::Sender<ServerMessage>,
+}
+
+struct RoomState {
+    level: LiveLevel,
+    /// Revision of the stored level the room was opened with.
+    revision: u32,
+    clients: usize,
+}
+
+impl Room {
+    fn open(level: StoredLevel) -> Self {
+        let (updates, _) = broadcast::channel(ROOM_CHANNEL_CAPACITY);
+        Room {
+            state: Mutex::new(RoomState {
+                level: LiveLevel::new(level.space),
+                revision: level.revision,
+                clients: 0,
+            }),
+            updates,
+        }
+    }
+
+    /// Applies an operation under the sender's `limits` and broadcasts it to
+    /// every client, including the sender.
+    pub fn apply(&self, operation: Operation, limits: &ValidationLimits) -> Result<u64, CollabError> {
+        let mut state = lock(&self.state);
+        let version = state.level.apply(&operation, limits)?;
+        // Broadcasting under the lock keeps messages in version order; sending
+        // only fails when nobody is listening.
+        let _ = self.updates.send(ServerMessage::Applied { version, operation });
+        Ok(version)
+    }
+
+    /// Returns a [`ServerMessage::Snapshot`] of the current level.
+    pub fn snapshot(&self) -> ServerMessage {
+        let state = lock(&self.state);
+        ServerMessage::Snapshot {
+            version: state.level.version(),
+            space: state.level.space().clone(),
+        }
+    }
+
+    /// Returns a copy of the current level.
+    pub fn space(&self) -> SpaceJSON {
+        lock(&self.state).level.space().clone()
+    }
+}
+
+/// A client's membership in a room.
+pub struct Subscription {
+    pub room: Arc<Room>,
+    /// Operations applied after `snapshot`.
+    pub updates: broadcast::Receiver<ServerMessage>,
+    /// The level as of joining.
+    pub snapshot: ServerMessage,
+}
+
+/// Every open room, keyed by level ID.
+///
+/// A room opens when its first client joins and closes when its last client leaves.
+#[derive(Default)]
+pub struct CollabRooms {
+    rooms: Mutex<HashMap<String, Arc<Room>>>,
+}
+
+impl CollabRooms {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the open room for a level, if any.
+    pub fn get(&self, id: &str) -> Option<Arc<Room>> {
+        lock(&self.rooms).get(id).cloned()
+    }
+
+    /// Joins a level's room, opening it with the level returned by `load` if needed.
+    ///
+    /// `load` runs without holding the room list's lock. If another client
+    /// opens the room in the meantime, its level is used instead.
+    pub fn join<E>(&self, id: &str, load: impl FnOnce() -> Result<StoredLevel, E>) -> Result<Subscription, E> {
+        {
+            let rooms = lock(&self.rooms);
+            if let Some(room) = rooms.get(id) {
+                return Ok(subscribe(Arc::clone(room)));
+            }
+        }
+        let level = load()?;
+        let mut rooms = lock(&self.rooms);
+        let room = rooms.entry(id.to_string()).or_insert_with(|| Arc::new(Room::open(level)));
+        // Counting the client under the list's lock keeps `leave` from closing the room first
+        Ok(subscribe(Arc::clone(room)))
+    }
+
+    /// Leaves a level's room. When the last client leaves, the room closes and,
+    /// if any operation was applied, `save` is called with the edited level and
+    /// the revision the room was opened with.
+    ///
+    /// `save` runs without holding the room list's lock, so a client may reopen
+    /// the room from the level as it was before this save.
+    pub fn leave<E>(&self, id: &str, save: impl FnOnce(&SpaceJSON, u32) -> Result<(), E>) -> Result<(), E> {
+        let room = {
+            let mut rooms = lock(&self.rooms);
+            let Some(room) = rooms.get(id).cloned() else {
+                return Ok(());
+            };
+            let mut state = lock(&room.state);
+            state.clients = state.clients.saturating_sub(1);
+            if state.clients > 0 {
+                return Ok(());
+            }
+            drop(state);
+            rooms.remove(id);
+            room
+        };
+        let state = lock(&room.state);
+        if state.level.version() > 0 {
+            save(state.level.space(), state.revision)?;
+        }
+        Ok(())
+    }
+}
+
+/// Adds a client to a room, returning its subscription.
+fn subscribe(room: Arc<Room>) -> Subscription {
+    let mut state = lock(&room.state);
+    state.clients += 1;
+    // Subscribing under the room lock means no operation is both in the
+    // snapshot and in `updates`, or in neither.
+    let updates = room.updates.subscribe();
+    let snapshot = ServerMessage::Snapshot {
+        version: state.level.version(),
+        space: state.level.space().clone(),
+    };
+    drop(state);
+    Subscription { room, updates, snapshot }
+}
+
+/// Saves a closed room's edited level, which the room opened at `revision`.
+///
+/// If the level was saved since (e.g. by a `PUT`), the room's edits are
+/// three-way merged into the latest revision. Conflicting positions keep the
+/// room's blocks, and if the merged level fails validation against `limits`
+/// the room's level is saved as is; the overwritten save stays available in
+/// the revision history. A level deleted while the room was open stays
+/// deleted.
+///
+/// Returns the conflicts with saves made during the session.
+pub fn save_edits(
+    store: &dyn LevelStore,
+    id: &str,
+    space: &SpaceJSON,
+    revision: u32,
+    limits: &ValidationLimits,
+) -> Result<Vec<MergeConflict>, CollabError> {
+    let latest = match store.put_if_revision(id, space.clone(), revision) {
+        Ok(_) | Err(StorageError::NotFound { .. }) => return Ok(Vec::new()),
+        Err(StorageError::RevisionConflict { .. }) => store.get(id)?,
+        Err(e) => return Err(e.into()),
+    };
+    let base = store.get_revision(id, revision)?;
+    let merge = merge_levels(&base.space, space, &latest.space, limits)?;
+    let invalid = merge
+        .conflicts
+        .iter()
+        .any(|conflict| matches!(conflict, MergeConflict::Invalid { .. }));
+    let saved = if invalid { space.clone() } else { merge.merged };
+    match store.put_if_revision(id, saved, latest.revision) {
+        Ok(_) | Err(StorageError::NotFound { .. }) => Ok(merge.conflicts),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Locks a mutex, recovering the data if a panicking thread poisoned it.
+///
+/// [`LiveLevel::apply`] validates before mutating, so the data stays consistent.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
@@ -0,0 +1,481 @@
+//! Server configuration.
+//!
+//! Settings are read from a TOML file (`CONFIG_FILE`, or `config.toml` in the
+//! working directory if it exists), then overridden by environment variables,
+//! and validated as a whole before the server starts. Every setting has a
+//! default, so both the file and the variables are optional.
+
+use crate::auth::{parse_api_keys, ApiKey, AuthConfig};
+use crate::models::{SpaceJSON, WorldSettings, CURRENT_SCHEMA_VERSION};
+use crate::rate_limit::RateLimitConfig;
+use crate::validation::{validate_space_json, CoordinateBounds, ValidationLimits};
+use axum::http::HeaderValue;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Configuration file read when `CONFIG_FILE` is not set, if present.
+pub const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+/// Configuration error variants with associated context data.
+#[derive(Debug, Clone)]
+pub enum ConfigError {
+    /// The configuration file could not be read or is not valid TOML.
+    InvalidFile { path: String, reason: String },
+    /// An environment variable could not be parsed.
+    InvalidEnvVar { name: &'static str, reason: String },
+    /// A setting (from either source) has an unusable value.
+    InvalidSetting { setting: String, reason: String },
+}
+
+impl ConfigError {
+    /// Returns a machine-readable error code.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            ConfigError::InvalidFile { .. } => "INVALID_CONFIG_FILE",
+            ConfigError::InvalidEnvVar { .. } => "INVALID_ENV_VAR",
+            ConfigError::InvalidSetting { .. } => "INVALID_CONFIG",
+        }
+    }
+
+    /// Returns a human-readable error message for display to operators.
+    pub fn message(&self) -> String {
+        match self {
+            ConfigError::InvalidFile { path, reason } => {
+                format!("Invalid configuration file '{}': {}", path, reason)
+            }
+            ConfigError::InvalidEnvVar { name, reason } => {
+                format!("Invalid environment variable {}: {}", name, reason)
+            }
+            ConfigError::InvalidSetting { setting, reason } => {
+                format!("Invalid setting '{}': {}", setting, reason)
+            }
+        }
+    }
+}
+
+/// Complete server configuration, as read from the `config.toml` sections of the same names.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub server: ServerSettings,
+    /// Block count and coordinate bounds enforced on levels from clients without a tier.
+    pub limits: ValidationLimits,
+    /// Named limits that API keys and tokens can select with their `tier`,
+    /// e.g. a `pro` tier allowing larger levels. Unset fields take the
+    /// built-in defaults, not those of `limits`.
+    pub tiers: BTreeMap<String, ValidationLimits>,
+    /// World settings used for exported levels that leave them out, with the
+    /// same fields as Space JSON's `world` section.
+    pub world: WorldSettings,
+    pub storage: StorageSettings,
+    pub auth: AuthSettings,
+    pub rate_limit: RateLimitConfig,
+}
+
+/// HTTP listener settings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    /// IP address to listen on.
+    pub bind_address: String,
+    pub port: u16,
+    /// Origins allowed to make cross-origin requests, e.g.
+    /// `https://builder.example.com`, or `*` for any origin.
+    pub cors_origins: Vec<String>,
+    /// Largest accepted request body in bytes, except for `/api/import`.
+    pub body_limit_bytes: usize,
+    /// Largest accepted `.rbxlx` upload for `/api/import` in bytes.
+    pub import_body_limit_bytes: usize,
+    /// Identify anonymous clients by the last `X-Forwarded-For` address
+    /// instead of the peer address (only safe behind a trusted proxy).
+    pub trust_proxy_headers: bool,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        ServerSettings {
+            bind_address: "0.0.0.0".to_string(),
+            port: 4000,
+            cors_origins: vec!["*".to_string()],
+            body_limit_bytes: 2 * 1024 * 1024,
+            import_body_limit_bytes: 32 * 1024 * 1024,
+            trust_proxy_headers: false,
+        }
+    }
+}
+
+/// Level store backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreKind {
+    /// One JSON file per level.
+    #[default]
+    Fs,
+    /// A SQLite database.
+    Sqlite,
+}
+
+impl StoreKind {
+    /// Looks up a store kind by name (`fs` or `sqlite`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fs" => Some(StoreKind::Fs),
+            "sqlite" => Some(StoreKind::Sqlite),
+            _ => None,
+        }
+    }
+
+    /// Location used when none is configured: a directory for `fs`, a database file for `sqlite`.
+    pub fn default_path(&self) -> &'static str {
+        match self {
+            StoreKind::Fs => "data/levels",
+            StoreKind::Sqlite => "data/levels.db",
+        }
+    }
+}
+
+/// Level storage settings.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageSettings {
+    pub kind: StoreKind,
+    /// Overrides [`StoreKind::default_path`].
+    pub path: Option<String>,
+}
+
+impl StorageSettings {
+    /// Returns the configured path, or the store kind's default.
+    pub fn path(&self) -> &str {
+        self.path.as_deref().unwrap_or(self.kind.default_path())
+    }
+}
+
+/// Authentication settings. With no API keys and no token secret, every route is open.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthSettings {
+    pub api_keys: Vec<ApiKey>,
+    /// HMAC secret enabling signed bearer tokens.
+    pub token_secret: Option<String>,
+}
+
+impl ServerConfig {
+    /// Loads the configuration file and environment overrides, then validates the result.
+    pub fn load() -> Result<Self, ConfigError> {
+        Self::load_with(|name| env::var(name).ok())
+    }
+
+    /// Like [`ServerConfig::load`], reading environment variables through `var`.
+    pub fn load_with(var: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let mut config = match var("CONFIG_FILE").filter(|path| !path.is_empty()) {
+            Some(path) => Self::from_file(&path)?,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => Self::from_file(DEFAULT_CONFIG_FILE)?,
+            None => ServerConfig::default(),
+        };
+        config.apply_env(var)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reads and parses a configuration file without validating it.
+    pub fn from_file(path: &str) -> Result<Self, ConfigError> {
+        let invalid = |reason: String| ConfigError::InvalidFile {
+            path: path.to_string(),
+            reason,
+        };
+        let text = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+        Self::from_toml(&text).map_err(invalid)
+    }
+
+    /// Parses configuration TOML without validating it. Unknown keys are rejected.
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| e.message().to_string())
+    }
+
+    /// Overrides settings with environment variables read through `var`.
+    ///
+    /// Empty variables are ignored.
+    pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        let var = |name: &str| var(name).filter(|value| !value.is_empty());
+        let server = &mut self.server;
+        if let Some(value) = var("PORT") {
+            server.port = parse_env("PORT", &value)?;
+        }
+        if let Some(value) = var("BIND_ADDRESS") {
+            server.bind_address = value;
+        }
+        if let Some(value) = var("CORS_ORIGINS") {
+            server.cors_origins = value.split(',').map(|origin| origin.trim().to_string()).collect();
+        }
+        if let Some(value) = var("BODY_LIMIT_BYTES") {
+            server.body_limit_bytes = parse_env("BODY_LIMIT_BYTES", &value)?;
+        }
+        if let Some(value) = var("IMPORT_BODY_LIMIT_BYTES") {
+            server.import_body_limit_bytes = parse_env("IMPORT_BODY_LIMIT_BYTES", &value)?;
+        }
+        if let Some(value) = var("TRUST_PROXY_HEADERS") {
+            server.trust_proxy_headers = parse_env("TRUST_PROXY_HEADERS", &value)?;
+        }
+
+        if let Some(value) = var("MAX_BLOCKS") {
+            self.limits.max_blocks = parse_env("MAX_BLOCKS", &value)?;
+        }
+        if let Some(value) = var("MAX_TEAMS") {
+            self.limits.max_teams = parse_env("MAX_TEAMS", &value)?;
+        }
+        if let Some(value) = var("MAX_SPAWNS") {
+            self.limits.max_spawns = parse_env("MAX_SPAWNS", &value)?;
+        }
+        let bounds = &mut self.limits.bounds;
+        for (name, bound) in [
+            ("BOUNDS_MIN_X", &mut bounds.min_x),
+            ("BOUNDS_MAX_X", &mut bounds.max_x),
+            ("BOUNDS_MIN_Y", &mut bounds.min_y),
+            ("BOUNDS_MAX_Y", &mut bounds.max_y),
+            ("BOUNDS_MIN_Z", &mut bounds.min_z),
+            ("BOUNDS_MAX_Z", &mut bounds.max_z),
+        ] {
+            if let Some(value) = var(name) {
+                *bound = parse_env(name, &value)?;
+            }
+        }
+        if let Some(value) = var("REJECT_FLOATING_BLOCKS") {
+            self.limits.reject_floating_blocks = parse_env("REJECT_FLOATING_BLOCKS", &value)?;
+        }
+
+        if let Some(value) = var("LEVEL_STORE") {
+            self.storage.kind = StoreKind::from_name(&value).ok_or_else(|| ConfigError::InvalidEnvVar {
+                name: "LEVEL_STORE",
+                reason: format!("expected 'fs' or 'sqlite', got '{}'", value),
+            })?;
+        }
+        if let Some(value) = var("LEVEL_STORE_PATH") {
+            self.storage.path = Some(value);
+        }
+
+        if let Some(value) = var("API_KEYS") {
+            self.auth.api_keys =
+                parse_api_keys(&value).map_err(|reason| ConfigError::InvalidEnvVar { name: "API_KEYS", reason })?;
+        }
+        if let Some(value) = var("AUTH_TOKEN_SECRET") {
+            self.auth.token_secret = Some(value);
+        }
+
+        let rate_limit = &mut self.rate_limit;
+        if let Some(value) = var("RATE_LIMIT_REQUESTS") {
+            rate_limit.requests = parse_env("RATE_LIMIT_REQUESTS", &value)?;
+        }
+        if let Some(value) = var("RATE_LIMIT_BLOCKS") {
+            rate_limit.blocks = parse_env("RATE_LIMIT_BLOCKS", &value)?;
+        }
+        if let Some(value) = var("RATE_LIMIT_WINDOW_SECONDS") {
+            rate_limit.window_seconds = parse_env("RATE_LIMIT_WINDOW_SECONDS", &value)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that every setting is usable, returning the first problem found.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |setting: &str, reason: String| {
+            Err(ConfigError::InvalidSetting {
+                setting: setting.to_string(),
+                reason,
+            })
+        };
+
+        let server = &self.server;
+        if IpAddr::from_str(&server.bind_address).is_err() {
+            return invalid(
+                "server.bind_address",
+                format!("'{}' is not an IP address", server.bind_address),
+            );
+        }
+        if let Err(reason) = check_cors_origins(&server.cors_origins) {
+            return invalid("server.cors_origins", reason);
+        }
+        if server.body_limit_bytes == 0 {
+            return invalid("server.body_limit_bytes", "must be greater than 0".to_string());
+        }
+        if server.import_body_limit_bytes == 0 {
+            return invalid("server.import_body_limit_bytes", "must be greater than 0".to_string());
+        }
+
+        check_limits("limits", &self.limits)?;
+        for (name, limits) in &self.tiers {
+            check_limits(&format!("tiers.{}", name), limits)?;
+        }
+
+        let defaults = SpaceJSON {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            name: None,
+            blocks: Vec::new(),
+            world: Some(self.world.clone()),
+            teams: Vec::new(),
+            spawns: Vec::new(),
+        };
+        if let Err(error) = validate_space_json(&defaults, &self.limits) {
+            return invalid("world", error.message());
+        }
+
+        if self.storage.path.as_deref() == Some("") {
+            return invalid("storage.path", "cannot be empty".to_string());
+        }
+
+        for api_key in &self.auth.api_keys {
+            if api_key.name.is_empty() {
+                return invalid("auth.api_keys", "every API key needs a name".to_string());
+            }
+            if api_key.key.is_empty() {
+                return invalid("auth.api_keys", format!("API key '{}' is empty", api_key.name));
+            }
+            if api_key.scopes.is_empty() {
+                return invalid(
+                    "auth.api_keys",
+                    format!("API key '{}' needs at least one scope", api_key.name),
+                );
+            }
+            if let Some(tier) = api_key.tier.as_deref().filter(|tier| !self.tiers.contains_key(*tier)) {
+                return invalid(
+                    "auth.api_keys",
+                    format!("API key '{}' uses unknown tier '{}'", api_key.name, tier),
+                );
+            }
+        }
+        if self.auth.token_secret.as_deref() == Some("") {
+            return invalid("auth.token_secret", "cannot be empty".to_string());
+        }
+
+        if self.rate_limit.window_seconds == 0 {
+            return invalid("rate_limit.window_seconds", "must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Returns the limits of a tier, or the default limits for no tier or an unknown one.
+    pub fn limits_for(&self, tier: Option<&str>) -> ValidationLimits {
+        tier.and_then(|tier| self.tiers.get(tier))
+            .copied()
+            .unwrap_or(self.limits)
+    }
+
+    /// Address to listen on. Only valid after [`ServerConfig::validate`] succeeded.
+    pub fn socket_addr(&self) -> SocketAddr {
+        let ip = IpAddr::from_str(&self.server.bind_address).unwrap_or(IpAddr::from([0, 0, 0, 0]));
+        SocketAddr::new(ip, self.server.port)
+    }
+
+    /// Returns the allowed CORS origins, or `None` if any origin is allowed.
+    pub fn cors_origins(&self) -> Option<Vec<HeaderValue>> {
+        if self.server.cors_origins.iter().any(|origin| origin == "*") {
+            return None;
+        }
+        Some(
+            self.server
+                .cors_origins
+                .iter()
+                .filter_map(|origin| HeaderValue::from_str(origin).ok())
+                .collect(),
+        )
+    }
+
+    pub fn auth_config(&self) -> AuthConfig {
+        AuthConfig {
+            api_keys: self.auth.api_keys.clone(),
+            token_secret: self.auth.token_secret.clone().map(String::into_bytes),
+        }
+    }
+}
+
+/// Parses an environment variable's value, naming the variable on failure.
+fn parse_env<T: FromStr>(name: &'static str, value: &str) -> Result<T, ConfigError>
+where
+    T::Err: std::fmt::Display,
+{
+    value.trim().parse().map_err(|e: T::Err| ConfigError::InvalidEnvVar {
+        name,
+        reason: format!("'{}': {}", value, e),
+    })
+}
+
+/// Checks that a block limit is positive and every bound's minimum is at most its maximum.
+fn check_limits(setting: &str, limits: &ValidationLimits) -> Result<(), ConfigError> {
+    let invalid = |field: String, reason: String| ConfigError::InvalidSetting {
+        setting: format!("{}.{}", setting, field),
+        reason,
+    };
+    if limits.max_blocks == 0 {
+        return Err(invalid("max_blocks".to_string(), "must be greater than 0".to_string()));
+    }
+    let CoordinateBounds {
+        min_x,
+        max_x,
+        min_y,
+        max_y,
+        min_z,
+        max_z,
+    } = limits.bounds;
+    for (axis, min, max) in [("x", min_x, max_x), ("y", min_y, max_y), ("z", min_z, max_z)] {
+        if min > max {
+            return Err(invalid(
+                format!("bounds.min_{}", axis),
+                format!("{} is greater than max_{} ({})", min, axis, max),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that origins are `*` alone or a list of `http(s)://host[:port]` origins.
+fn check_cors_origins(origins: &[String]) -> Result<(), String> {
+    if origins.is_empty() {
+        return Err("at least one origin is required; use \"*\" to allow any origin".to_string());
+    }
+    if origins.iter().any(|origin| origin == "*") {
+        if origins.len() > 1 {
+            return Err("\"*\" cannot be combined with other origins".to_string());
+        }
+        return Ok(());
+    }
+    for origin in origins {
+        let Some(host) = origin
+            .strip_prefix("https://")
+            .or_else(|| origin.strip_prefix("http://"))
+        else {
+            return Err(format!("'{}' must start with http:// or https://", origin));
+        };
+        if host.is_empty() || host.contains('/') || HeaderValue::from_str(origin).is_err() {
+            return Err(format!("'{}' is not an origin like https://example.com", origin));
+        }
+    }
+    Ok(())
+}
+
+/// Fills in the world settings a level leaves out from the configured defaults.
+///
+/// Each of `baseplate`, `spawn` and `lighting` is taken as a whole. The default
+/// spawn is skipped for levels with spawn blocks or spawn points, which cannot
+/// be combined with it.
+pub fn apply_default_world(space: &mut SpaceJSON, defaults: &WorldSettings) {
+    let has_spawns = !space.spawns.is_empty() || space.blocks.iter().any(|block| block.spawn.is_some());
+    let baseplate = defaults.baseplate.as_ref();
+    let spawn = defaults.spawn.as_ref().filter(|_| !has_spawns);
+    let lighting = defaults.lighting.as_ref();
+    if baseplate.is_none() && spawn.is_none() && lighting.is_none() {
+        return;
+    }
+
+    let world = space.world.get_or_insert_with(WorldSettings::default);
+    if world.baseplate.is_none() {
+        world.baseplate = baseplate.cloned();
+    }
+    if world.spawn.is_none() {
+        world.spawn = spawn.cloned();
+    }
+    if world.lighting.is_none() {
+        world.lighting = lighting.cloned();
+    }
+}
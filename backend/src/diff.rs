@@ -0,0 +1,95 @@
+//! Block-level diff between two versions of a level.
+//!
+//! Blocks are matched by grid position: a position only present in the newer
+//! level is added, one only present in the older level is removed, and one
+//! present in both with a different color is recolored.
+
+use crate::models::{Block, SpaceJSON};
+use crate::stats::GridPosition;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Differences between two levels, as returned by `/api/levels/:id/diff`.
+///
+/// Every list is ordered by position (x, then y, then z).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LevelDiff {
+    /// Blocks at positions that are empty in the older level.
+    pub added: Vec<Block>,
+    /// Blocks of the older level at positions that are empty in the newer level.
+    pub removed: Vec<Block>,
+    pub recolored: Vec<RecoloredBlock>,
+}
+
+impl LevelDiff {
+    /// Returns `true` if neither level has a block the other lacks or colors differently.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.recolored.is_empty()
+    }
+}
+
+/// A position whose block changed color.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecoloredBlock {
+    #[serde(flatten)]
+    pub position: GridPosition,
+    /// Color in the older level, as written by the client.
+    pub from: String,
+    /// Color in the newer level, as written by the client.
+    pub to: String,
+}
+
+/// Computes the blocks added, removed and recolored going from `old` to `new`.
+///
+/// Colors are compared case-insensitively, with `#RGB` equal to `#RRGGBB`.
+/// If a level has several blocks at one position, the last one counts.
+pub fn diff_levels(old: &SpaceJSON, new: &SpaceJSON) -> LevelDiff {
+    let old_blocks = blocks_by_position(&old.blocks);
+    let new_blocks = blocks_by_position(&new.blocks);
+
+    let mut diff = LevelDiff::default();
+    for (key, old_block) in &old_blocks {
+        match new_blocks.get(key) {
+            None => diff.removed.push((*old_block).clone()),
+            Some(new_block) if !colors_equal(&old_block.color, &new_block.color) => {
+                diff.recolored.push(RecoloredBlock {
+                    position: GridPosition {
+                        x: key.0,
+                        y: key.1,
+                        z: key.2,
+                    },
+                    from: old_block.color.clone(),
+                    to: new_block.color.clone(),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    diff.added = new_blocks
+        .iter()
+        .filter(|(key, _)| !old_blocks.contains_key(key))
+        .map(|(_, block)| (*block).clone())
+        .collect();
+    diff
+}
+
+fn blocks_by_position(blocks: &[Block]) -> BTreeMap<(i32, i32, i32), &Block> {
+    blocks.iter().map(|block| ((block.x, block.y, block.z), block)).collect()
+}
+
+/// Returns `true` if two hex colors are the same, ignoring case and the `#RGB` shorthand.
+pub fn colors_equal(a: &str, b: &str) -> bool {
+    normalize_color(a) == normalize_color(b)
+}
+
+/// Uppercases a hex color and expands the `#RGB` shorthand to `#RRGGBB`.
+fn normalize_color(color: &str) -> String {
+    let color = color.to_ascii_uppercase();
+    match color.strip_prefix('#') {
+        Some(digits) if digits.len() == 3 => {
+            let expanded: String = digits.chars().flat_map(|c| [c, c]).collect();
+            format!("#{}", expanded)
+        }
+        _ => color,
+    }
+}
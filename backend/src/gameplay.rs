@@ -0,0 +1,198 @@
+//! Gameplay block kinds.
+//!
+//! Maps Space JSON block `kind` names to CollectionService tags, and provides the
+//! server Script that gives tagged Parts their behavior in a generated place.
+
+/// Built-in behavior of a gameplay block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockKind {
+    /// Kills any character that touches it.
+    Kill,
+    /// Respawns the player here after they touch it.
+    Checkpoint,
+    /// Awards a win the first time a player touches it in each life.
+    Finish,
+    /// Launches characters upward.
+    Bounce,
+    /// Temporarily raises the character's walk speed.
+    Speed,
+}
+
+/// All block kinds, in documentation order.
+pub const BLOCK_KINDS: &[BlockKind] = &[
+    BlockKind::Kill,
+    BlockKind::Checkpoint,
+    BlockKind::Finish,
+    BlockKind::Bounce,
+    BlockKind::Speed,
+];
+
+impl BlockKind {
+    /// Parses a Space JSON kind name, case-insensitively.
+    pub fn from_name(name: &str) -> Option<Self> {
+        BLOCK_KINDS
+            .iter()
+            .copied()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the kind's Space JSON name.
+    pub fn name(self) -> &'static str {
+        match self {
+            BlockKind::Kill => "kill",
+            BlockKind::Checkpoint => "checkpoint",
+            BlockKind::Finish => "finish",
+            BlockKind::Bounce => "bounce",
+            BlockKind::Speed => "speed",
+        }
+    }
+
+    /// Returns the CollectionService tag applied to Parts of this kind.
+    ///
+    /// Must match the tags [`GAMEPLAY_SCRIPT_SOURCE`] looks up.
+    pub fn tag(self) -> &'static str {
+        match self {
+            BlockKind::Kill => "KillBrick",
+            BlockKind::Checkpoint => "Checkpoint",
+            BlockKind::Finish => "FinishPad",
+            BlockKind::Bounce => "BouncePad",
+            BlockKind::Speed => "SpeedPad",
+        }
+    }
+
+    /// Returns the kind whose tag is `tag`, if any.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        BLOCK_KINDS.iter().copied().find(|kind| kind.tag() == tag)
+    }
+}
+
+/// Upward speed a bounce pad gives a character, in studs per second.
+///
+/// Must match `BOUNCE_VELOCITY` in [`GAMEPLAY_SCRIPT_SOURCE`].
+pub const BOUNCE_VELOCITY: f32 = 80.0;
+
+/// Walk speed on a speed pad's boost, in studs per second.
+///
+/// Must match `SPEED_BOOST` in [`GAMEPLAY_SCRIPT_SOURCE`].
+pub const SPEED_BOOST: f32 = 32.0;
+
+/// Name of the generated server Script.
+pub const GAMEPLAY_SCRIPT_NAME: &str = "LevelGameplay";
+
+/// Luau source of the server Script implementing every block kind.
+///
+/// Behaviors are attached to tagged Parts through CollectionService, so Parts
+/// added at runtime with the same tags work too.
+pub const GAMEPLAY_SCRIPT_SOURCE: &str = r#"-- Generated by Roblox Level Builder. Implements gameplay blocks by tag.
+local CollectionService = game:GetService("CollectionService")
+local Players = game:GetService("Players")
+
+local BOUNCE_VELOCITY = 80
+local SPEED_BOOST = 32
+local SPEED_DURATION = 3
+
+local checkpoints = {}
+local finished = {}
+local boosted = {}
+
+local function getHumanoid(hit)
+	local character = hit.Parent
+	local humanoid = character and character:FindFirstChildOfClass("Humanoid")
+	if humanoid and humanoid.Health > 0 then
+		return humanoid, character
+	end
+	return nil
+end
+
+local function getLeaderstat(player, name)
+	local leaderstats = player:FindFirstChild("leaderstats")
+	if not leaderstats then
+		leaderstats = Instance.new("Folder")
+		leaderstats.Name = "leaderstats"
+		leaderstats.Parent = player
+	end
+	local stat = leaderstats:FindFirstChild(name)
+	if not stat then
+		stat = Instance.new("IntValue")
+		stat.Name = name
+		stat.Parent = leaderstats
+	end
+	return stat
+end
+
+local behaviors = {
+	KillBrick = function(part, humanoid)
+		humanoid.Health = 0
+	end,
+	Checkpoint = function(part, humanoid, character)
+		local player = Players:GetPlayerFromCharacter(character)
+		if player then
+			checkpoints[player] = part
+		end
+	end,
+	FinishPad = function(part, humanoid, character)
+		local player = Players:GetPlayerFromCharacter(character)
+		if player and not finished[player] then
+			finished[player] = true
+			local wins = getLeaderstat(player, "Wins")
+			wins.Value += 1
+		end
+	end,
+	BouncePad = function(part, humanoid, character)
+		local root = character:FindFirstChild("HumanoidRootPart")
+		if root then
+			local velocity = root.AssemblyLinearVelocity
+			root.AssemblyLinearVelocity = Vector3.new(velocity.X, BOUNCE_VELOCITY, velocity.Z)
+		end
+	end,
+	SpeedPad = function(part, humanoid)
+		if boosted[humanoid] then
+			return
+		end
+		boosted[humanoid] = true
+		local walkSpeed = humanoid.WalkSpeed
+		humanoid.WalkSpeed = SPEED_BOOST
+		task.delay(SPEED_DURATION, function()
+			if humanoid.Parent then
+				humanoid.WalkSpeed = walkSpeed
+			end
+			boosted[humanoid] = nil
+		end)
+	end,
+}
+
+for tag, behavior in pairs(behaviors) do
+	local function attach(part)
+		if part:IsA("BasePart") then
+			part.Touched:Connect(function(hit)
+				local humanoid, character = getHumanoid(hit)
+				if humanoid then
+					behavior(part, humanoid, character)
+				end
+			end)
+		end
+	end
+	for _, part in ipairs(CollectionService:GetTagged(tag)) do
+		attach(part)
+	end
+	CollectionService:GetInstanceAddedSignal(tag):Connect(attach)
+end
+
+Players.PlayerAdded:Connect(function(player)
+	player.CharacterAdded:Connect(function(character)
+		finished[player] = nil
+		local checkpoint = checkpoints[player]
+		if checkpoint and checkpoint.Parent then
+			character:WaitForChild("HumanoidRootPart")
+			task.defer(function()
+				character:PivotTo(checkpoint.CFrame + Vector3.new(0, checkpoint.Size.Y / 2 + 3, 0))
+			end)
+		end
+	end)
+end)
+
+Players.PlayerRemoving:Connect(function(player)
+	checkpoints[player] = nil
+	finished[player] = nil
+end)
+"#;
@@ -0,0 +1,59 @@
+//! Part geometry.
+//!
+//! Axis-aligned boxes of the Parts a place export generates, shared by the
+//! checks that reason about where Parts sit: floating block detection,
+//! SpawnLocation clearance and playability analysis.
+
+use crate::models::{BaseplateSettings, Block, BlockSize};
+
+/// Tolerance for comparing box faces, in studs.
+pub(crate) const CONTACT_EPSILON: f32 = 1e-3;
+
+/// Default baseplate size in studs.
+const DEFAULT_BASEPLATE_SIZE: (f32, f32, f32) = (200.0, 16.0, 200.0);
+
+/// Minimum and maximum corners of an axis-aligned box, in studs.
+pub(crate) type PartBox = ([f32; 3], [f32; 3]);
+
+/// Returns the minimum and maximum corners of a block's Part, in studs.
+pub(crate) fn block_box(block: &Block) -> PartBox {
+    let size = block.size.unwrap_or(BlockSize { x: 2.0, y: 2.0, z: 2.0 });
+    let center = [block.x as f32, block.y as f32, block.z as f32];
+    let half = [size.x / 2.0, size.y / 2.0, size.z / 2.0];
+    (
+        [center[0] - half[0], center[1] - half[1], center[2] - half[2]],
+        [center[0] + half[0], center[1] + half[1], center[2] + half[2]],
+    )
+}
+
+/// Returns `true` if two boxes share part of a face (or overlap).
+///
+/// Boxes that only meet along an edge or at a corner are not connected.
+pub(crate) fn boxes_touch(a: &PartBox, b: &PartBox) -> bool {
+    let mut shared_axes = 0;
+    for axis in 0..3 {
+        let overlap = a.1[axis].min(b.1[axis]) - a.0[axis].max(b.0[axis]);
+        if overlap < -CONTACT_EPSILON {
+            return false;
+        }
+        if overlap > CONTACT_EPSILON {
+            shared_axes += 1;
+        }
+    }
+    shared_axes >= 2
+}
+
+/// Returns `true` if two boxes share some volume, not just a face.
+pub(crate) fn boxes_overlap(a: &PartBox, b: &PartBox) -> bool {
+    (0..3).all(|axis| a.1[axis].min(b.1[axis]) - a.0[axis].max(b.0[axis]) > CONTACT_EPSILON)
+}
+
+/// Returns the size of the baseplate a place export generates, or `None` if
+/// the world settings disable it.
+pub fn baseplate_size(settings: Option<&BaseplateSettings>) -> Option<(f32, f32, f32)> {
+    match settings {
+        Some(settings) if settings.enabled == Some(false) => None,
+        Some(BaseplateSettings { size: Some(size), .. }) => Some((size.x, size.y, size.z)),
+        _ => Some(DEFAULT_BASEPLATE_SIZE),
+    }
+}
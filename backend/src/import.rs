@@ -0,0 +1,308 @@
+//! RBXLX import.
+//!
+//! Converts an existing `.rbxlx` place back into Space JSON. Only anchored,
+//! axis-aligned block Parts in Workspace can be represented as voxels; each Part
+//! is snapped to the 2-stud grid and split into 2x2x2 blocks. Everything else is
+//! reported as skipped so callers can tell the user what was left behind.
+
+use crate::gameplay::BlockKind;
+use crate::materials::material_name;
+use crate::models::{Block, SpaceJSON, CURRENT_SCHEMA_VERSION};
+use crate::validation::ValidationLimits;
+use rbx_dom_weak::{ustr, Instance, WeakDom};
+use rbx_types::{Color3, Ref, Variant};
+use std::collections::HashSet;
+use std::io::Cursor;
+
+/// Size of one imported block along each axis, in studs.
+const GRID_SIZE: f32 = 2.0;
+
+/// Maximum distance (in studs) a Part dimension may be from a grid multiple.
+const SIZE_TOLERANCE: f32 = 0.05;
+
+/// Maximum deviation of a rotation matrix entry from the identity.
+const ROTATION_TOLERANCE: f32 = 1e-4;
+
+/// `Enum.Material.Plastic`, the default Part material.
+const DEFAULT_MATERIAL: u32 = 256;
+
+/// `Enum.PartType.Block`, the default Part shape.
+const BLOCK_SHAPE: u32 = 1;
+
+/// Color used when a Part has no readable color (Roblox's default "Medium stone grey").
+const DEFAULT_COLOR: &str = "#A3A2A5";
+
+/// Classes that only group other instances; their children are still imported.
+const CONTAINER_CLASSES: &[&str] = &["Model", "Folder"];
+
+/// Classes that exist in every place and are silently ignored.
+const IGNORED_CLASSES: &[&str] = &["Terrain", "Camera"];
+
+/// An instance that could not be converted into blocks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedInstance {
+    pub name: String,
+    pub class: String,
+    pub reason: String,
+}
+
+/// Result of importing a place: the recovered level plus everything left out.
+#[derive(Debug)]
+pub struct ImportResult {
+    pub space_json: SpaceJSON,
+    pub skipped: Vec<SkippedInstance>,
+}
+
+/// Imports an `.rbxlx` file into Space JSON.
+///
+/// Walks Workspace (descending into Models and Folders) and converts every
+/// anchored, unrotated, block-shaped Part whose size is a multiple of 2 studs.
+/// Parts that would take the level past `limits.max_blocks` blocks or outside
+/// `limits.bounds` are skipped.
+pub fn import_rbxlx(content: &[u8], limits: &ValidationLimits) -> Result<ImportResult, String> {
+    let dom = rbx_xml::from_reader_default(Cursor::new(content))
+        .map_err(|e| format!("Failed to parse XML: {}", e))?;
+    import_dom(&dom, limits)
+}
+
+/// Imports the Workspace of an already-decoded place.
+pub fn import_dom(dom: &WeakDom, limits: &ValidationLimits) -> Result<ImportResult, String> {
+    let workspace_ref = dom
+        .root()
+        .children()
+        .iter()
+        .copied()
+        .find(|&child| dom.get_by_ref(child).is_some_and(|i| i.class == "Workspace"))
+        .ok_or_else(|| "Place has no Workspace".to_string())?;
+
+    let mut importer = Importer {
+        dom,
+        limits,
+        blocks: Vec::new(),
+        positions: HashSet::new(),
+        skipped: Vec::new(),
+    };
+    importer.visit_children(workspace_ref);
+
+    Ok(ImportResult {
+        space_json: SpaceJSON {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            name: None,
+            blocks: importer.blocks,
+            ..Default::default()
+        },
+        skipped: importer.skipped,
+    })
+}
+
+/// Traversal state while importing Workspace.
+struct Importer<'a> {
+    dom: &'a WeakDom,
+    limits: &'a ValidationLimits,
+    blocks: Vec<Block>,
+    positions: HashSet<(i32, i32, i32)>,
+    skipped: Vec<SkippedInstance>,
+}
+
+impl Importer<'_> {
+    fn visit_children(&mut self, parent: Ref) {
+        let Some(parent) = self.dom.get_by_ref(parent) else {
+            return;
+        };
+
+        for &child_ref in parent.children() {
+            let Some(child) = self.dom.get_by_ref(child_ref) else {
+                continue;
+            };
+
+            if CONTAINER_CLASSES.contains(&child.class.as_str()) {
+                self.visit_children(child_ref);
+            } else if IGNORED_CLASSES.contains(&child.class.as_str()) {
+                continue;
+            } else if child.class != "Part" {
+                self.skip(child, "Only Parts can be imported as blocks");
+            } else if child.name == "Baseplate" {
+                self.skip(child, "Baseplate is regenerated on export");
+            } else if let Err(reason) = self.import_part(child) {
+                self.skip(child, &reason);
+            }
+        }
+    }
+
+    fn skip(&mut self, instance: &Instance, reason: &str) {
+        self.skipped.push(SkippedInstance {
+            name: instance.name.clone(),
+            class: instance.class.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Splits a Part into grid blocks, or returns why it cannot be imported.
+    fn import_part(&mut self, part: &Instance) -> Result<(), String> {
+        if !matches!(property(part, &["Anchored"]), Some(Variant::Bool(true))) {
+            return Err("Part is not anchored".to_string());
+        }
+
+        if let Some(Variant::Enum(shape)) = property(part, &["Shape", "shape"]) {
+            if shape.to_u32() != BLOCK_SHAPE {
+                return Err("Part is not block-shaped".to_string());
+            }
+        }
+
+        let cframe = match property(part, &["CFrame"]) {
+            Some(Variant::CFrame(cframe)) => *cframe,
+            _ => return Err("Part has no CFrame".to_string()),
+        };
+        let rotation = cframe.orientation;
+        let identity_rows = [
+            (rotation.x.x, rotation.x.y, rotation.x.z, 1.0, 0.0, 0.0),
+            (rotation.y.x, rotation.y.y, rotation.y.z, 0.0, 1.0, 0.0),
+            (rotation.z.x, rotation.z.y, rotation.z.z, 0.0, 0.0, 1.0),
+        ];
+        let axis_aligned = identity_rows.iter().all(|&(a, b, c, ea, eb, ec)| {
+            (a - ea).abs() < ROTATION_TOLERANCE
+                && (b - eb).abs() < ROTATION_TOLERANCE
+                && (c - ec).abs() < ROTATION_TOLERANCE
+        });
+        if !axis_aligned {
+            return Err("Part is rotated".to_string());
+        }
+
+        let size = match property(part, &["Size", "size"]) {
+            Some(Variant::Vector3(size)) => *size,
+            _ => return Err("Part has no Size".to_string()),
+        };
+        let position = cframe.position;
+        if ![size.x, size.y, size.z, position.x, position.y, position.z].iter().all(|value| value.is_finite()) {
+            return Err("Part has a non-finite size or position".to_string());
+        }
+        let counts = [grid_count(size.x), grid_count(size.y), grid_count(size.z)];
+        let [Some(nx), Some(ny), Some(nz)] = counts else {
+            return Err("Part size is not a multiple of the 2-stud grid".to_string());
+        };
+
+        let max_blocks = self.limits.max_blocks;
+        let fits = (nx as usize)
+            .checked_mul(ny as usize)
+            .and_then(|count| count.checked_mul(nz as usize))
+            .and_then(|count| count.checked_add(self.blocks.len()))
+            .is_some_and(|total| total <= max_blocks);
+        if !fits {
+            return Err(format!("Part would exceed the {} block limit", max_blocks));
+        }
+
+        // Center of the voxel at the Part's minimum corner, snapped to the grid
+        let step = GRID_SIZE as i32;
+        let corner = |center: f32, size: f32, count: i32| {
+            let first = snap_to_grid(center - size / 2.0 + GRID_SIZE / 2.0)?;
+            let last = (count - 1).checked_mul(step).and_then(|span| first.checked_add(span))?;
+            Some((first, last))
+        };
+        let (Some((x0, x1)), Some((y0, y1)), Some((z0, z1))) = (
+            corner(position.x, size.x, nx),
+            corner(position.y, size.y, ny),
+            corner(position.z, size.z, nz),
+        ) else {
+            return Err("Part lies outside the level bounds".to_string());
+        };
+        let bounds = &self.limits.bounds;
+        if !bounds.contains(x0, y0, z0) || !bounds.contains(x1, y1, z1) {
+            return Err("Part lies outside the level bounds".to_string());
+        }
+        let first = (x0, y0, z0);
+
+        let template = block_template(part);
+        let mut added = 0;
+        for iy in 0..ny {
+            for iz in 0..nz {
+                for ix in 0..nx {
+                    let position = (first.0 + ix * step, first.1 + iy * step, first.2 + iz * step);
+                    if self.positions.insert(position) {
+                        self.blocks.push(Block {
+                            x: position.0,
+                            y: position.1,
+                            z: position.2,
+                            ..template.clone()
+                        });
+                        added += 1;
+                    }
+                }
+            }
+        }
+
+        if added == 0 {
+            return Err("Part overlaps blocks that were already imported".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Returns the first property found under any of the given names.
+///
+/// Decoded places may use either canonical (`Size`) or serialized (`size`) names.
+fn property<'a>(instance: &'a Instance, names: &[&str]) -> Option<&'a Variant> {
+    names.iter().find_map(|name| instance.properties.get(&ustr(name)))
+}
+
+/// Returns how many grid cells a Part dimension spans, if it is a grid multiple.
+fn grid_count(size: f32) -> Option<i32> {
+    let count = (size / GRID_SIZE).round();
+    if !(1.0..=i32::MAX as f32).contains(&count) || (size - count * GRID_SIZE).abs() > SIZE_TOLERANCE {
+        return None;
+    }
+    Some(count as i32)
+}
+
+/// Rounds a coordinate to the nearest multiple of the grid size, if it fits in an `i32`.
+fn snap_to_grid(value: f32) -> Option<i32> {
+    let snapped = (value / GRID_SIZE).round() * GRID_SIZE;
+    (i32::MIN as f32..=i32::MAX as f32).contains(&snapped).then_some(snapped as i32)
+}
+
+/// Builds a block (without position) carrying the Part's appearance and gameplay kind.
+fn block_template(part: &Instance) -> Block {
+    let color = match property(part, &["Color", "Color3uint8"]) {
+        Some(Variant::Color3(color)) => color3_to_hex(*color),
+        Some(Variant::Color3uint8(color)) => format!("#{:02X}{:02X}{:02X}", color.r, color.g, color.b),
+        _ => DEFAULT_COLOR.to_string(),
+    };
+
+    let material = match property(part, &["Material"]) {
+        Some(Variant::Enum(material)) => Some(material.to_u32()),
+        Some(Variant::Int32(material)) => Some(*material as u32),
+        _ => None,
+    }
+    .filter(|&value| value != DEFAULT_MATERIAL)
+    .and_then(material_name)
+    .map(str::to_string);
+
+    let fraction = |name: &str| match property(part, &[name]) {
+        Some(Variant::Float32(value)) if *value > 0.0 => Some(value.min(1.0)),
+        _ => None,
+    };
+
+    let kind = match property(part, &["Tags"]) {
+        Some(Variant::Tags(tags)) => tags.iter().find_map(BlockKind::from_tag),
+        _ => None,
+    };
+
+    Block {
+        color,
+        material,
+        transparency: fraction("Transparency"),
+        reflectance: fraction("Reflectance"),
+        kind: kind.map(|kind| kind.name().to_string()),
+        ..Default::default()
+    }
+}
+
+/// Converts a Roblox `Color3` (0.0-1.0 channels) to a `#RRGGBB` hex string.
+pub fn color3_to_hex(color: Color3) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02X}{:02X}{:02X}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    )
+}
@@ -1,7 +1,29 @@
 //! Backend library for Roblox Level Builder.
 //!
-//! Provides Space JSON data models, validation, and `.rbxlx` file generation.
+//! Provides Space JSON data models, schema migration, validation, greedy meshing,
+//! `.rbxlx` file generation (including world settings, gameplay blocks and
+//! SpawnLocation clearance), level statistics, playability analysis, `.rbxlx` import, three-way merges, server-side level storage
+//! with revision history and diffs, real-time collaborative editing, API
+//! authentication, per-client rate limiting, and server configuration.
 
+pub mod auth;
+pub mod brick_colors;
+pub mod collab;
+pub mod config;
+pub mod diff;
+pub mod gameplay;
+pub mod geometry;
+pub mod import;
+pub mod lighting;
+pub mod materials;
+pub mod merge;
+pub mod meshing;
+pub mod migrate;
 pub mod models;
+pub mod playability;
+pub mod rate_limit;
 pub mod rbxlx;
+pub mod spawn;
+pub mod stats;
+pub mod storage;
 pub mod validation;
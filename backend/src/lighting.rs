@@ -0,0 +1,23 @@
+//! Roblox lighting technology names.
+//!
+//! Maps Space JSON lighting technology names to `Enum.Technology` values used in `.rbxlx` files.
+
+/// Supported lighting technologies as `(name, Enum.Technology value)` pairs.
+pub const TECHNOLOGIES: &[(&str, u32)] = &[
+    ("Legacy", 0),
+    ("Voxel", 1),
+    ("Compatibility", 2),
+    ("ShadowMap", 3),
+    ("Future", 4),
+];
+
+/// `Enum.Technology.ShadowMap`, used when a level does not choose a technology.
+pub const DEFAULT_TECHNOLOGY: u32 = 3;
+
+/// Returns the `Enum.Technology` value for a technology name (case-insensitive).
+pub fn technology_value(name: &str) -> Option<u32> {
+    TECHNOLOGIES
+        .iter()
+        .find(|(technology, _)| technology.eq_ignore_ascii_case(name))
+        .map(|&(_, value)| value)
+}
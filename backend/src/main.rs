@@ -1,80 +1,1118 @@
 //! Backend API server for Roblox Level Builder.
 //!
-//! Axum HTTP server providing the `/api/export` endpoint.
-//! Accepts Space JSON from frontend, validates it, generates `.rbxlx` file, and returns it.
+//! Axum HTTP server providing the `/api/export`, `/api/validate` and `/api/import` endpoints.
+//! Accepts Space JSON from frontend, validates it, generates a `.rbxlx` (or binary `.rbxl`)
+//! file, and returns it.
+//! Also reports validation results and level statistics without exporting,
+//! converts uploaded `.rbxlx` places back into Space JSON, saves levels on the
+//! server under `/api/levels`, and hosts collaborative editing over WebSockets.
 
 use axum::{
-    http::{header, StatusCode},
+    body::Bytes,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        ConnectInfo, DefaultBodyLimit, Extension, Path, Query, Request, State,
+    },
+    http::{header, HeaderName, HeaderValue, StatusCode},
+    middleware::{self, Next},
     response::{IntoResponse, Response},
-    routing::{get, post},
+    routing::{delete, get, post},
     Json, Router,
 };
+use serde::Deserialize;
 use serde_json::json;
-use std::env;
-use tower_http::cors::{Any, CorsLayer};
+use std::fmt::Display;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 
+use backend::auth::{
+    issue_token, required_scope, AuthConfig, AuthError, Principal, Scope, TokenClaims, API_KEY_HEADER,
+};
+use backend::collab::{save_edits, CollabError, CollabRooms, Operation, ServerMessage};
+use backend::config::{apply_default_world, ServerConfig, StorageSettings, StoreKind};
+use backend::diff::diff_levels;
+use backend::import::import_rbxlx;
+use backend::merge::merge_levels;
+use backend::migrate::{migrate, MigrationReport};
 use backend::models::SpaceJSON;
-use backend::rbxlx::generate_rbxlx;
-use backend::validation::validate_space_json;
+use backend::playability::analyze_playability;
+use backend::rate_limit::{forwarded_client_address, RateLimitError, RateLimiter};
+use backend::rbxlx::{export_warnings, generate_export, ExportOptions, OutputFormat};
+use backend::spawn::{clear_spawns, SpawnClearance};
+use backend::stats::level_stats;
+use backend::storage::{now_millis, FsLevelStore, LevelStore, SqliteLevelStore, StorageError};
+use backend::validation::{
+    find_floating_islands, validate_space_json, validate_space_json_all,
+    ValidationErrorGroup, ValidationLimits, ValidationWarning,
+};
+
+/// Response header listing the validation warnings of a successful export.
+const WARNINGS_HEADER: HeaderName = HeaderName::from_static("x-validation-warnings");
+
+/// Response header listing the changes made migrating an older payload.
+const MIGRATIONS_HEADER: HeaderName = HeaderName::from_static("x-schema-migrations");
+
+/// Shared state of the HTTP server.
+#[derive(Clone)]
+struct AppState {
+    levels: Arc<dyn LevelStore>,
+    /// Open collaborative editing rooms, keyed by level ID.
+    rooms: Arc<CollabRooms>,
+    auth: Arc<AuthConfig>,
+    limiter: Arc<RateLimiter>,
+    /// Validation limits, default world settings and proxy trust.
+    config: Arc<ServerConfig>,
+}
+
+/// Rate limiting key of the client making a request, set by [`rate_limit_middleware`].
+#[derive(Debug, Clone)]
+struct ClientId(String);
 
 /// API error response containing an error code and human-readable message.
+///
+/// `errors` optionally carries every validation error, grouped by error code.
+/// Errors are sent as `400 Bad Request` unless another status is set.
 #[derive(Debug)]
 struct ApiError {
+    status: StatusCode,
     error: String,
     message: String,
+    errors: Option<Vec<serde_json::Value>>,
+    /// Seconds sent in a `Retry-After` header.
+    retry_after: Option<u64>,
+}
+
+impl ApiError {
+    fn new(error: impl Into<String>, message: impl Into<String>) -> Self {
+        ApiError {
+            status: StatusCode::BAD_REQUEST,
+            error: error.into(),
+            message: message.into(),
+            errors: None,
+            retry_after: None,
+        }
+    }
+
+    fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    fn from_storage(error: StorageError) -> Self {
+        let status = match error {
+            StorageError::NotFound { .. }
+            | StorageError::RevisionNotFound { .. }
+            | StorageError::ShareNotFound => StatusCode::NOT_FOUND,
+            StorageError::RevisionConflict { .. } => StatusCode::CONFLICT,
+            StorageError::ShareExpired | StorageError::ShareRevoked => StatusCode::GONE,
+            StorageError::InvalidId { .. } => StatusCode::BAD_REQUEST,
+            StorageError::Backend { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError::new(error.error_code(), error.message()).with_status(status)
+    }
+
+    fn from_auth(error: AuthError) -> Self {
+        let status = if error.is_forbidden() {
+            StatusCode::FORBIDDEN
+        } else {
+            StatusCode::UNAUTHORIZED
+        };
+        ApiError::new(error.error_code(), error.message()).with_status(status)
+    }
+
+    fn from_rate_limit(error: RateLimitError) -> Self {
+        ApiError {
+            retry_after: Some(error.retry_after_seconds()),
+            ..ApiError::new(error.error_code(), error.message()).with_status(StatusCode::TOO_MANY_REQUESTS)
+        }
+    }
+
+    /// Builds a response listing every collected validation error.
+    fn from_error_groups(groups: &[ValidationErrorGroup]) -> Self {
+        let total: usize = groups.iter().map(|group| group.count).sum();
+        ApiError {
+            status: StatusCode::BAD_REQUEST,
+            error: "VALIDATION_FAILED".to_string(),
+            message: format!("Space JSON failed validation with {} error(s).", total),
+            errors: Some(error_groups_json(groups)),
+            retry_after: None,
+        }
+    }
+}
+
+/// Converts grouped validation errors into their JSON response form.
+fn error_groups_json(groups: &[ValidationErrorGroup]) -> Vec<serde_json::Value> {
+    groups
+        .iter()
+        .map(|group| {
+            json!({
+                "error": group.code,
+                "count": group.count,
+                "messages": group.errors.iter().map(|e| e.message()).collect::<Vec<_>>()
+            })
+        })
+        .collect()
+}
+
+/// Converts validation warnings into their JSON response form.
+///
+/// Block indices are left out of export headers to keep them small.
+fn warnings_json(warnings: &[ValidationWarning], with_blocks: bool) -> Vec<serde_json::Value> {
+    warnings
+        .iter()
+        .map(|warning| {
+            let mut value = json!({
+                "warning": warning.warning_code(),
+                "message": warning.message()
+            });
+            if with_blocks && !warning.blocks().is_empty() {
+                value["blocks"] = json!(warning.blocks());
+            }
+            value
+        })
+        .collect()
+}
+
+/// Parses the `format` query parameter, defaulting to `.rbxlx`.
+fn parse_format(name: Option<&str>) -> Result<OutputFormat, ApiError> {
+    match name {
+        None => Ok(OutputFormat::default()),
+        Some(name) => OutputFormat::from_name(name).ok_or_else(|| {
+            ApiError::new(
+                "INVALID_FORMAT",
+                format!("Unsupported export format: '{}'. Supported formats: rbxlx, rbxl, rbxmx, rbxm.", name),
+            )
+        }),
+    }
+}
+
+/// Parses the `spawn` query parameter, defaulting to keeping blocks as they are.
+fn parse_spawn_clearance(name: Option<&str>) -> Result<SpawnClearance, ApiError> {
+    match name {
+        None => Ok(SpawnClearance::default()),
+        Some(name) => SpawnClearance::from_name(name).ok_or_else(|| {
+            ApiError::new(
+                "INVALID_SPAWN_MODE",
+                format!("Unsupported spawn mode: '{}'. Supported modes: keep, relocate, clear.", name),
+            )
+        }),
+    }
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let body = json!({
+        let mut body = json!({
             "error": self.error,
             "message": self.message
         });
-        (StatusCode::BAD_REQUEST, Json(body)).into_response()
+        if let Some(errors) = self.errors {
+            body["errors"] = serde_json::Value::Array(errors);
+        }
+        let mut response = (self.status, Json(body)).into_response();
+        if let Some(seconds) = self.retry_after {
+            response.headers_mut().insert(header::RETRY_AFTER, seconds.into());
+        }
+        response
+    }
+}
+
+/// Query parameters accepted by `/api/export`.
+#[derive(Debug, Default, Deserialize)]
+struct ExportQuery {
+    /// Merge contiguous same-color blocks into larger Parts.
+    #[serde(default)]
+    merge: bool,
+    /// Report every validation error instead of only the first.
+    #[serde(default)]
+    all_errors: bool,
+    /// Output file format: `rbxlx` (XML place, default), `rbxl` (binary place),
+    /// `rbxmx` (XML model) or `rbxm` (binary model).
+    #[serde(default)]
+    format: Option<String>,
+    /// Reject levels with floating blocks.
+    #[serde(default)]
+    strict: bool,
+    /// Blocks inside a SpawnLocation: `keep` (default), `relocate` or `clear`.
+    #[serde(default)]
+    spawn: Option<String>,
+}
+
+impl ExportQuery {
+    /// Returns the generation options the query asks for.
+    fn options(&self) -> Result<ExportOptions, ApiError> {
+        Ok(ExportOptions {
+            merge_blocks: self.merge,
+            spawn_clearance: parse_spawn_clearance(self.spawn.as_deref())?,
+        })
+    }
+}
+
+/// Returns `limits` with floating blocks rejected if the request asked for `strict` validation.
+fn request_limits(limits: ValidationLimits, strict: bool) -> ValidationLimits {
+    ValidationLimits {
+        reject_floating_blocks: limits.reject_floating_blocks || strict,
+        ..limits
     }
 }
 
 /// Handles POST `/api/export` requests.
 ///
-/// Validates the incoming Space JSON, generates a `.rbxlx` file, and returns it
-/// as a downloadable attachment.
-async fn export_handler(Json(payload): Json<SpaceJSON>) -> Result<Response, ApiError> {
+/// Migrates the incoming Space JSON to the current schema version, validates it,
+/// generates a `.rbxlx` file, and returns it as a downloadable attachment.
+/// Pass `?merge=true` to merge contiguous same-color blocks into larger Parts,
+/// and `?all_errors=true` to receive every validation error (grouped by error
+/// code) instead of only the first. `?format=rbxl` returns a binary `.rbxl`
+/// place instead of XML, and `?format=rbxmx`/`?format=rbxm` return only the
+/// level's blocks wrapped in a reusable Model. `?strict=true` rejects levels
+/// with floating blocks, and `?spawn=relocate`/`?spawn=clear` moves the spawn
+/// or removes the blocks it would be buried in. Warnings about an exported
+/// level are listed in the `X-Validation-Warnings` header, and changes made
+/// migrating an older payload in the `X-Schema-Migrations` header.
+async fn export_handler(
+    State(state): State<AppState>,
+    Extension(client): Extension<ClientId>,
+    Extension(limits): Extension<ValidationLimits>,
+    Query(query): Query<ExportQuery>,
+    Json(raw_payload): Json<serde_json::Value>,
+) -> Result<Response, ApiError> {
+    let format = parse_format(query.format.as_deref())?;
+    let options = query.options()?;
+    let limits = request_limits(limits, query.strict);
+
+    // Upgrade payloads saved by older editors before validating
+    let (payload, report) = migrated(raw_payload)?;
+
     // Validate Space JSON before processing
-    if let Err(validation_error) = validate_space_json(&payload) {
-        return Err(ApiError {
-            error: validation_error.error_code().to_string(),
-            message: validation_error.message(),
-        });
+    if query.all_errors {
+        if let Err(groups) = validate_space_json_all(&payload, &limits) {
+            return Err(ApiError::from_error_groups(&groups));
+        }
+    } else if let Err(validation_error) = validate_space_json(&payload, &limits) {
+        return Err(ApiError::new(
+            validation_error.error_code(),
+            validation_error.message(),
+        ));
     }
 
-    // Generate .rbxlx file from validated Space JSON
-    let rbxlx_content = match generate_rbxlx(&payload) {
+    charge_blocks(&state, &client, &payload)?;
+    let mut response = file_response(&state, payload, limits, options, format, "level").await?;
+    insert_migrations_header(&mut response, &report.changes);
+    Ok(response)
+}
+
+/// Migrates a payload saved by an older editor to the current schema version.
+fn migrated(raw_payload: serde_json::Value) -> Result<(SpaceJSON, MigrationReport), ApiError> {
+    migrate(raw_payload).map_err(|e| ApiError::new(e.error_code(), e.message()))
+}
+
+/// Lists the changes made migrating a payload as JSON in the
+/// `X-Schema-Migrations` header, if there are any.
+fn insert_migrations_header(response: &mut Response, changes: &[String]) {
+    if changes.is_empty() {
+        return;
+    }
+    if let Ok(value) = HeaderValue::from_str(&json!(changes).to_string()) {
+        response.headers_mut().insert(MIGRATIONS_HEADER, value);
+    }
+}
+
+/// Takes an export's blocks from the client's block quota.
+fn charge_blocks(state: &AppState, client: &ClientId, payload: &SpaceJSON) -> Result<(), ApiError> {
+    state
+        .limiter
+        .check_blocks(&client.0, payload.blocks.len(), now_millis())
+        .map_err(ApiError::from_rate_limit)
+}
+
+/// Generates a place or model file and returns it as a downloadable attachment
+/// named `<file_stem>.<extension>`. World settings the level leaves out are
+/// taken from the configured defaults. Any [`export_warnings`] for the
+/// exported level are sent as JSON in the `X-Validation-Warnings` header.
+/// Generation runs off the async runtime.
+async fn file_response(
+    state: &AppState,
+    mut payload: SpaceJSON,
+    limits: ValidationLimits,
+    options: ExportOptions,
+    format: OutputFormat,
+    file_stem: &str,
+) -> Result<Response, ApiError> {
+    apply_default_world(&mut payload, &state.config.world);
+    let (warnings, generated) = tokio::task::spawn_blocking(move || {
+        let warnings = export_warnings(&payload, &limits, &options);
+        (warnings, generate_export(&payload, &options, format))
+    })
+    .await
+    .map_err(|e| {
+        ApiError::new("RBXLX_GENERATION_FAILED", format!("Export generation task failed: {}", e))
+            .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+    })?;
+
+    let place_content = match generated {
         Ok(content) => content,
         Err(e) => {
-            return Err(ApiError {
-                error: "RBXLX_GENERATION_FAILED".to_string(),
-                message: format!("Failed to generate .rbxlx file: {}", e),
-            });
+            return Err(ApiError::new(
+                "RBXLX_GENERATION_FAILED",
+                format!("Failed to generate .{} file: {}", format.extension(), e),
+            ));
         }
     };
 
     // Return file with proper headers
-    let response = Response::builder()
+    let mut response = Response::builder()
         .status(StatusCode::OK)
-        .header(header::CONTENT_TYPE, "application/octet-stream")
+        .header(header::CONTENT_TYPE, format.content_type())
         .header(
             header::CONTENT_DISPOSITION,
-            format!(r#"attachment; filename="level.rbxlx""#),
+            format!(r#"attachment; filename="{}.{}""#, file_stem, format.extension()),
         )
-        .body(rbxlx_content.into())
-        .map_err(|e| ApiError {
-            error: "RESPONSE_BUILD_FAILED".to_string(),
-            message: format!("Failed to build response: {}", e),
+        .body(place_content.into())
+        .map_err(|e| {
+            ApiError::new(
+                "RESPONSE_BUILD_FAILED",
+                format!("Failed to build response: {}", e),
+            )
         })?;
 
+    if !warnings.is_empty() {
+        let header_json = serde_json::Value::Array(warnings_json(&warnings, false)).to_string();
+        if let Ok(value) = HeaderValue::from_str(&header_json) {
+            response.headers_mut().insert(WARNINGS_HEADER, value);
+        }
+    }
     Ok(response)
 }
 
+/// Query parameters accepted by `/api/validate`.
+#[derive(Debug, Default, Deserialize)]
+struct ValidateQuery {
+    /// Estimate Part count and file size with block merging enabled.
+    #[serde(default)]
+    merge: bool,
+    /// Output format to estimate the file size for (same values as `/api/export`).
+    #[serde(default)]
+    format: Option<String>,
+    /// Report floating blocks as errors rather than only listing them.
+    #[serde(default)]
+    strict: bool,
+    /// Spawn clearance mode to estimate the Part count for (same values as `/api/export`).
+    #[serde(default)]
+    spawn: Option<String>,
+}
+
+/// Handles POST `/api/validate` requests.
+///
+/// Migrates and validates Space JSON without generating a file, and returns a
+/// report with every validation error, warnings, floating block islands and
+/// level statistics. Invalid levels still get a `200 OK` report with `valid: false`,
+/// but only their errors: the level may be too large to analyze. Valid levels
+/// are analyzed with the configured default world settings, as for an export.
+/// Only payloads that cannot be migrated are rejected.
+async fn validate_handler(
+    State(state): State<AppState>,
+    Extension(limits): Extension<ValidationLimits>,
+    Query(query): Query<ValidateQuery>,
+    Json(raw_payload): Json<serde_json::Value>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let format = parse_format(query.format.as_deref())?;
+    let limits = request_limits(limits, query.strict);
+    let options = ExportOptions {
+        merge_blocks: query.merge,
+        spawn_clearance: parse_spawn_clearance(query.spawn.as_deref())?,
+    };
+    let (mut payload, report) = migrated(raw_payload)?;
+
+    if let Err(groups) = validate_space_json_all(&payload, &limits) {
+        return Ok(Json(json!({
+            "valid": false,
+            "errors": error_groups_json(&groups),
+            "warnings": [],
+            "migrations": report.changes,
+            "floatingIslands": [],
+            "stats": null
+        })));
+    }
+
+    apply_default_world(&mut payload, &state.config.world);
+    let stats = level_stats(&payload, &limits, &options, format);
+    Ok(Json(json!({
+        "valid": true,
+        "errors": [],
+        "warnings": warnings_json(&export_warnings(&payload, &limits, &options), true),
+        "migrations": report.changes,
+        "floatingIslands": find_floating_islands(&payload),
+        "stats": stats
+    })))
+}
+
+/// Query parameters accepted by `/api/playability`.
+#[derive(Debug, Default, Deserialize)]
+struct PlayabilityQuery {
+    /// Reject levels with floating blocks, as for `/api/export`.
+    #[serde(default)]
+    strict: bool,
+    /// Spawn clearance mode to analyze the exported level with (same values as `/api/export`).
+    #[serde(default)]
+    spawn: Option<String>,
+}
+
+/// Handles POST `/api/playability` requests.
+///
+/// Migrates and validates Space JSON like `/api/export`, then analyzes the
+/// level a place export would generate: which block surfaces a default
+/// character can reach from the spawns, and which checkpoint and finish
+/// blocks it cannot. The analysis runs off the async runtime.
+async fn playability_handler(
+    State(state): State<AppState>,
+    Extension(limits): Extension<ValidationLimits>,
+    Query(query): Query<PlayabilityQuery>,
+    Json(raw_payload): Json<serde_json::Value>,
+) -> Result<Response, ApiError> {
+    let clearance = parse_spawn_clearance(query.spawn.as_deref())?;
+    let limits = request_limits(limits, query.strict);
+    let (mut payload, migration) = migrated(raw_payload)?;
+    validate_space_json(&payload, &limits).map_err(|e| ApiError::new(e.error_code(), e.message()))?;
+
+    apply_default_world(&mut payload, &state.config.world);
+    let report = tokio::task::spawn_blocking(move || analyze_playability(&clear_spawns(&payload, clearance)))
+        .await
+        .map_err(|e| {
+            ApiError::new("PLAYABILITY_FAILED", format!("Playability analysis task failed: {}", e))
+                .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+        })?
+        .map_err(|e| ApiError::new(e.error_code(), e.message()))?;
+    let mut response = Json(report).into_response();
+    insert_migrations_header(&mut response, &migration.changes);
+    Ok(response)
+}
+
+/// Handles POST `/api/import` requests.
+///
+/// Parses an uploaded `.rbxlx` file and returns the recovered Space JSON along
+/// with every instance that could not be converted into blocks. Parts beyond
+/// the caller's block limit or bounds are skipped. Parsing runs off the
+/// async runtime.
+async fn import_handler(
+    Extension(limits): Extension<ValidationLimits>,
+    body: Bytes,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let result = tokio::task::spawn_blocking(move || import_rbxlx(&body, &limits))
+        .await
+        .map_err(|e| {
+            ApiError::new("RBXLX_IMPORT_FAILED", format!("Import task failed: {}", e))
+                .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+        })?
+        .map_err(|e| ApiError::new("RBXLX_IMPORT_FAILED", format!("Failed to import .rbxlx file: {}", e)))?;
+
+    let skipped: Vec<_> = result
+        .skipped
+        .iter()
+        .map(|s| json!({ "name": s.name, "class": s.class, "reason": s.reason }))
+        .collect();
+
+    Ok(Json(json!({
+        "space": result.space_json,
+        "skipped": skipped
+    })))
+}
+
+/// Migrates and validates a level before it is saved or merged, reporting every validation error.
+fn validated_level(
+    raw_payload: serde_json::Value,
+    limits: &ValidationLimits,
+) -> Result<(SpaceJSON, MigrationReport), ApiError> {
+    let (payload, report) = migrated(raw_payload)?;
+    validate_space_json_all(&payload, limits).map_err(|groups| ApiError::from_error_groups(&groups))?;
+    Ok((payload, report))
+}
+
+/// Body of a POST `/api/merge` request: the common ancestor and both edited versions.
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    base: serde_json::Value,
+    ours: serde_json::Value,
+    theirs: serde_json::Value,
+}
+
+/// Handles POST `/api/merge`: three-way merge of two edits of one level.
+///
+/// Each version is migrated and validated first; a failing version is named in
+/// the error message, as is a migrated one in the `X-Schema-Migrations` header.
+/// Responds with `{ merged, conflicts }`.
+async fn merge_handler(
+    Extension(limits): Extension<ValidationLimits>,
+    Json(request): Json<MergeRequest>,
+) -> Result<Response, ApiError> {
+    let mut versions = Vec::new();
+    let mut migrations = Vec::new();
+    for (label, raw_payload) in [("base", request.base), ("ours", request.ours), ("theirs", request.theirs)] {
+        let (space, report) = validated_level(raw_payload, &limits).map_err(|e| ApiError {
+            message: format!("'{}': {}", label, e.message),
+            ..e
+        })?;
+        migrations.extend(report.changes.iter().map(|change| format!("'{}': {}", label, change)));
+        versions.push(space);
+    }
+
+    let merge = merge_levels(&versions[0], &versions[1], &versions[2], &limits)
+        .map_err(|e| ApiError::new(e.error_code(), e.message()))?;
+    let mut response = Json(json!(merge)).into_response();
+    insert_migrations_header(&mut response, &migrations);
+    Ok(response)
+}
+
+/// Runs a blocking level store operation off the async runtime.
+async fn with_store<T, F>(state: &AppState, operation: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce(&dyn LevelStore) -> Result<T, StorageError> + Send + 'static,
+{
+    let store = Arc::clone(&state.levels);
+    tokio::task::spawn_blocking(move || operation(store.as_ref()))
+        .await
+        .map_err(|e| {
+            ApiError::new("STORAGE_FAILED", format!("Level storage task failed: {}", e))
+                .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+        })?
+        .map_err(ApiError::from_storage)
+}
+
+/// Handles POST `/api/levels`: validates and saves a new level under a generated ID.
+async fn create_level_handler(
+    State(state): State<AppState>,
+    Extension(limits): Extension<ValidationLimits>,
+    Json(raw_payload): Json<serde_json::Value>,
+) -> Result<Response, ApiError> {
+    let (space, report) = validated_level(raw_payload, &limits)?;
+    let level = with_store(&state, move |store| store.create(space)).await?;
+    let mut response = (StatusCode::CREATED, Json(level)).into_response();
+    insert_migrations_header(&mut response, &report.changes);
+    Ok(response)
+}
+
+/// Handles GET `/api/levels`: lists saved levels, most recently updated first.
+async fn list_levels_handler(State(state): State<AppState>) -> Result<Json<serde_json::Value>, ApiError> {
+    let levels = with_store(&state, |store| store.list()).await?;
+    Ok(Json(json!({ "levels": levels })))
+}
+
+/// Handles GET `/api/levels/:id`.
+async fn get_level_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Response, ApiError> {
+    let level = with_store(&state, move |store| store.get(&id)).await?;
+    Ok(Json(level).into_response())
+}
+
+/// Handles PUT `/api/levels/:id`: validates and saves a level, creating it if needed.
+async fn put_level_handler(
+    State(state): State<AppState>,
+    Extension(limits): Extension<ValidationLimits>,
+    Path(id): Path<String>,
+    Json(raw_payload): Json<serde_json::Value>,
+) -> Result<Response, ApiError> {
+    let (space, report) = validated_level(raw_payload, &limits)?;
+    let level = with_store(&state, move |store| store.put(&id, space)).await?;
+    let mut response = Json(level).into_response();
+    insert_migrations_header(&mut response, &report.changes);
+    Ok(response)
+}
+
+/// Handles DELETE `/api/levels/:id`.
+async fn delete_level_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    with_store(&state, move |store| store.delete(&id)).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Handles GET `/api/levels/:id/export`.
+///
+/// Exports a saved level like `/api/export`, accepting the same `merge`,
+/// `format`, `strict` and `spawn` query parameters. The file is named after the level ID. While the
+/// level is being edited collaboratively, the room's current state is exported.
+async fn export_level_handler(
+    State(state): State<AppState>,
+    Extension(client): Extension<ClientId>,
+    Extension(limits): Extension<ValidationLimits>,
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let format = parse_format(query.format.as_deref())?;
+    let options = query.options()?;
+    let limits = request_limits(limits, query.strict);
+    let space = match state.rooms.get(&id) {
+        Some(room) => room.space(),
+        None => {
+            let id = id.clone();
+            with_store(&state, move |store| store.get(&id)).await?.space
+        }
+    };
+
+    // Validation rules may have tightened since the level was saved
+    validate_space_json(&space, &limits)
+        .map_err(|e| ApiError::new(e.error_code(), e.message()))?;
+    charge_blocks(&state, &client, &space)?;
+    file_response(&state, space, limits, options, format, &id).await
+}
+
+/// Handles GET `/api/levels/:id/collab`: joins the level's collaborative
+/// editing room over a WebSocket.
+///
+/// The server first sends a `snapshot`, then every `applied` operation in
+/// order. Clients send [`Operation`]s as JSON text messages; invalid ones, and
+/// ones over the client's request rate limit, are answered with `rejected` and
+/// not broadcast. Operations are checked against the limits of the client's
+/// tier. When the last client leaves, the edited level is saved as a new
+/// revision (see [`save_edits`]).
+async fn collab_handler(
+    State(state): State<AppState>,
+    Extension(client): Extension<ClientId>,
+    Extension(limits): Extension<ValidationLimits>,
+    Path(id): Path<String>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    // Check the level exists before upgrading so unknown IDs get a normal 404
+    let level_id = id.clone();
+    with_store(&state, move |store| store.get(&level_id)).await?;
+    Ok(ws.on_upgrade(move |socket| collab_session(state, id, client, limits, socket)))
+}
+
+/// Runs one client's collaborative editing session until it disconnects.
+async fn collab_session(state: AppState, id: String, client: ClientId, limits: ValidationLimits, mut socket: WebSocket) {
+    let (levels, rooms, level_id) = (Arc::clone(&state.levels), Arc::clone(&state.rooms), id.clone());
+    let joined = tokio::task::spawn_blocking(move || rooms.join(&level_id, || levels.get(&level_id))).await;
+    let mut subscription = match joined {
+        Ok(Ok(subscription)) => subscription,
+        Ok(Err(e)) => {
+            let error = ApiError::from_storage(e);
+            let rejected = ServerMessage::Rejected {
+                error: "COLLAB_JOIN_FAILED",
+                message: error.message,
+                operation: None,
+            };
+            let _ = send_message(&mut socket, &rejected).await;
+            return;
+        }
+        Err(_) => return,
+    };
+
+    if send_message(&mut socket, &subscription.snapshot).await {
+        loop {
+            let reply = tokio::select! {
+                incoming = socket.recv() => match incoming {
+                    Some(Ok(Message::Text(text))) => match serde_json::from_str::<Operation>(&text) {
+                        // Every operation counts as a request
+                        Ok(operation) => match state.limiter.check_request(&client.0, now_millis()) {
+                            Ok(()) => match subscription.room.apply(operation.clone(), &limits) {
+                                // The sender sees its operation through the broadcast
+                                Ok(_) => None,
+                                Err(e) => Some(ServerMessage::rejected(&e, Some(operation))),
+                            },
+                            Err(e) => Some(ServerMessage::Rejected {
+                                error: e.error_code(),
+                                message: e.message(),
+                                operation: Some(operation),
+                            }),
+                        },
+                        Err(e) => Some(ServerMessage::rejected(
+                            &CollabError::InvalidOperation { reason: e.to_string() },
+                            None,
+                        )),
+                    },
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => None,
+                },
+                update = subscription.updates.recv() => match update {
+                    Ok(message) => Some(message),
+                    // Too far behind to replay; resync from the current level
+                    Err(RecvError::Lagged(_)) => Some(subscription.room.snapshot()),
+                    Err(RecvError::Closed) => break,
+                },
+            };
+            if let Some(reply) = reply {
+                if !send_message(&mut socket, &reply).await {
+                    break;
+                }
+            }
+        }
+    }
+
+    let (levels, rooms) = (Arc::clone(&state.levels), Arc::clone(&state.rooms));
+    let saved = tokio::task::spawn_blocking(move || {
+        rooms.leave(&id, |space, revision| {
+            let conflicts = save_edits(levels.as_ref(), &id, space, revision, &limits)?;
+            if !conflicts.is_empty() {
+                eprintln!(
+                    "Collaborative edits to level '{}' conflicted with {} change(s) saved during the session and were kept over them",
+                    id,
+                    conflicts.len()
+                );
+            }
+            Ok::<_, CollabError>(())
+        })
+    })
+    .await;
+    if let Ok(Err(e)) = saved {
+        eprintln!("Failed to save collaborative edits: {}", e.message());
+    }
+}
+
+/// Sends a server message as JSON text, returning `false` if the socket is closed.
+async fn send_message(socket: &mut WebSocket, message: &ServerMessage) -> bool {
+    match serde_json::to_string(message) {
+        Ok(text) => socket.send(Message::Text(text)).await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Parses a revision number path segment.
+fn parse_revision(revision: &str) -> Result<u32, ApiError> {
+    revision
+        .parse::<u32>()
+        .ok()
+        .filter(|&revision| revision >= 1)
+        .ok_or_else(|| {
+            ApiError::new(
+                "INVALID_REVISION",
+                format!("Invalid revision '{}'. Revisions are numbered from 1.", revision),
+            )
+        })
+}
+
+/// Handles GET `/api/levels/:id/revisions`: lists a level's revisions, newest first.
+async fn list_revisions_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let revisions = with_store(&state, move |store| store.list_revisions(&id)).await?;
+    Ok(Json(json!({ "revisions": revisions })))
+}
+
+/// Handles GET `/api/levels/:id/revisions/:revision`.
+async fn get_revision_handler(
+    State(state): State<AppState>,
+    Path((id, revision)): Path<(String, String)>,
+) -> Result<Response, ApiError> {
+    let revision = parse_revision(&revision)?;
+    let revision = with_store(&state, move |store| store.get_revision(&id, revision)).await?;
+    Ok(Json(revision).into_response())
+}
+
+/// Query parameters accepted by `/api/levels/:id/diff`.
+#[derive(Debug, Default, Deserialize)]
+struct DiffQuery {
+    /// Older revision. Defaults to the revision before `to`.
+    from: Option<String>,
+    /// Newer revision. Defaults to the latest revision.
+    to: Option<String>,
+}
+
+/// Handles GET `/api/levels/:id/diff`: blocks added, removed and recolored
+/// between two revisions of a level.
+async fn diff_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<DiffQuery>,
+) -> Result<Response, ApiError> {
+    let from = query.from.as_deref().map(parse_revision).transpose()?;
+    let to = query.to.as_deref().map(parse_revision).transpose()?;
+    let (from, to) = with_store(&state, move |store| {
+        let to = match to {
+            Some(to) => to,
+            None => store.get(&id)?.revision,
+        };
+        // Diffing the first revision against "the one before" compares it with itself
+        let from = from.unwrap_or_else(|| to.saturating_sub(1).max(1));
+        Ok((store.get_revision(&id, from)?, store.get_revision(&id, to)?))
+    })
+    .await?;
+
+    let diff = diff_levels(&from.space, &to.space);
+    Ok(Json(json!({
+        "from": from.revision,
+        "to": to.revision,
+        "added": diff.added,
+        "removed": diff.removed,
+        "recolored": diff.recolored,
+    }))
+    .into_response())
+}
+
+/// Longest allowed share link lifetime (one year), in seconds.
+const MAX_SHARE_EXPIRY_SECONDS: u64 = 365 * 24 * 60 * 60;
+
+/// Body of a POST `/api/levels/:id/share` request.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShareRequest {
+    /// Seconds until the link expires. Links never expire when absent.
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+/// Handles POST `/api/levels/:id/share`: mints a read-only share link.
+///
+/// The body is optional; `{ "expiresIn": 3600 }` makes the link expire after an hour.
+async fn create_share_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let request: ShareRequest = if body.is_empty() {
+        ShareRequest::default()
+    } else {
+        serde_json::from_slice(&body)
+            .map_err(|e| ApiError::new("INVALID_SHARE_REQUEST", format!("Invalid share request: {}", e)))?
+    };
+    let expires_at = match request.expires_in {
+        None => None,
+        Some(seconds) if (1..=MAX_SHARE_EXPIRY_SECONDS).contains(&seconds) => {
+            Some(now_millis() + seconds * 1000)
+        }
+        Some(seconds) => {
+            return Err(ApiError::new(
+                "INVALID_SHARE_REQUEST",
+                format!(
+                    "Invalid expiresIn {}. Expected 1 to {} seconds.",
+                    seconds, MAX_SHARE_EXPIRY_SECONDS
+                ),
+            ))
+        }
+    };
+
+    let share = with_store(&state, move |store| store.create_share(&id, expires_at)).await?;
+    let url = format!("/api/shared/{}", share.token);
+    let mut body = serde_json::to_value(&share).unwrap_or_default();
+    body["url"] = json!(url);
+    Ok((StatusCode::CREATED, Json(body)).into_response())
+}
+
+/// Handles DELETE `/api/levels/:id/share/:token`: revokes a share link.
+async fn revoke_share_handler(
+    State(state): State<AppState>,
+    Path((id, token)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    with_store(&state, move |store| store.revoke_share(&id, &token)).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Handles GET `/api/shared/:token`.
+///
+/// Returns the shared level's Space JSON, or with `?format=` (and optionally
+/// `?merge=true`, `?strict=true` or `?spawn=`) the generated place or model file. Expired and revoked links
+/// return `410 Gone`.
+async fn shared_level_handler(
+    State(state): State<AppState>,
+    Extension(client): Extension<ClientId>,
+    Extension(limits): Extension<ValidationLimits>,
+    Path(token): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let format = query.format.as_deref().map(|name| parse_format(Some(name))).transpose()?;
+    let options = query.options()?;
+    let level = with_store(&state, move |store| store.shared_level(&token, now_millis())).await?;
+
+    let Some(format) = format else {
+        return Ok(Json(level.space).into_response());
+    };
+    let limits = request_limits(limits, query.strict);
+    validate_space_json(&level.space, &limits)
+        .map_err(|e| ApiError::new(e.error_code(), e.message()))?;
+    charge_blocks(&state, &client, &level.space)?;
+    file_response(&state, level.space, limits, options, format, &level.id).await
+}
+
+/// Rejects requests without credentials for the route's scope, and records
+/// the [`ValidationLimits`] of the caller's tier for handlers.
+///
+/// Rejected requests count against their IP address's request limit, so
+/// credentials cannot be guessed faster than anonymous clients may call the
+/// API. Only the limits are recorded when no API keys or token secret are
+/// configured.
+async fn auth_middleware(State(state): State<AppState>, mut request: Request, next: Next) -> Result<Response, ApiError> {
+    let principal = match authorize(&state.auth, &request) {
+        Ok(principal) => principal,
+        Err(e) => {
+            state
+                .limiter
+                .check_request(&client_key(&state, &request), now_millis())
+                .map_err(ApiError::from_rate_limit)?;
+            return Err(e);
+        }
+    };
+    let limits = state
+        .config
+        .limits_for(principal.as_ref().and_then(|principal| principal.tier.as_deref()));
+    request.extensions_mut().insert(limits);
+    if let Some(principal) = principal {
+        request.extensions_mut().insert(principal);
+    }
+    Ok(next.run(request).await)
+}
+
+/// Checks a request's credentials against the scope its route requires,
+/// returning the caller if the route is not public.
+fn authorize(auth: &AuthConfig, request: &Request) -> Result<Option<Principal>, ApiError> {
+    if !auth.is_enabled() {
+        return Ok(None);
+    }
+    let Some(scope) = required_scope(request.method().as_str(), request.uri().path()) else {
+        return Ok(None);
+    };
+
+    let header_value = |name: &str| request.headers().get(name).and_then(|value| value.to_str().ok());
+    // Browsers cannot set headers on WebSockets, so upgrades may pass the token in the query
+    let query_token = header_value(header::UPGRADE.as_str())
+        .filter(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+        .and_then(|_| query_param(request.uri().query(), "access_token"))
+        .map(|token| format!("Bearer {}", token));
+    let authorization = header_value(header::AUTHORIZATION.as_str()).or(query_token.as_deref());
+
+    let principal = auth
+        .authenticate(header_value(API_KEY_HEADER), authorization, now_millis())
+        .map_err(ApiError::from_auth)?;
+    if !principal.has_scope(scope) {
+        return Err(ApiError::from_auth(AuthError::InsufficientScope { required: scope }));
+    }
+    Ok(Some(principal))
+}
+
+/// Applies the per-client request limit and records the client for block quotas.
+///
+/// Authenticated clients are limited per API key or token subject, anonymous
+/// ones per IP address. Health checks are never limited.
+async fn rate_limit_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let path = request.uri().path();
+    if path == "/health" || path == "/api/health" {
+        return Ok(next.run(request).await);
+    }
+
+    let client = ClientId(client_key(&state, &request));
+    state
+        .limiter
+        .check_request(&client.0, now_millis())
+        .map_err(ApiError::from_rate_limit)?;
+    request.extensions_mut().insert(client);
+    Ok(next.run(request).await)
+}
+
+/// Returns the rate limiting key of a request's client.
+fn client_key(state: &AppState, request: &Request) -> String {
+    if let Some(principal) = request.extensions().get::<Principal>() {
+        return format!("principal:{}", principal.subject);
+    }
+    let forwarded = state
+        .config
+        .server
+        .trust_proxy_headers
+        .then(|| request.headers().get("x-forwarded-for"))
+        .flatten()
+        .and_then(|value| value.to_str().ok())
+        .and_then(forwarded_client_address)
+        .map(str::to_string);
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(address)| address.ip().to_string());
+    format!("ip:{}", forwarded.or(peer).unwrap_or_default())
+}
+
+/// Returns the raw value of a query parameter.
+fn query_param<'a>(query: Option<&'a str>, name: &str) -> Option<&'a str> {
+    query?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// Default lifetime of tokens minted by `/api/tokens` (one hour), in seconds.
+const DEFAULT_TOKEN_EXPIRY_SECONDS: u64 = 60 * 60;
+
+/// Longest allowed token lifetime (one year), in seconds.
+const MAX_TOKEN_EXPIRY_SECONDS: u64 = 365 * 24 * 60 * 60;
+
+/// Body of a POST `/api/tokens` request.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenRequest {
+    subject: String,
+    scopes: Vec<Scope>,
+    /// Seconds until the token expires. Defaults to one hour.
+    #[serde(default)]
+    expires_in: Option<u64>,
+    /// Validation limits tier (a name under `[tiers]` in the configuration).
+    #[serde(default)]
+    tier: Option<String>,
+}
+
+/// Handles POST `/api/tokens`: mints a signed bearer token (requires `admin`).
+async fn create_token_handler(
+    State(state): State<AppState>,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let invalid = |message: String| ApiError::new("INVALID_TOKEN_REQUEST", message);
+    let Some(secret) = state.auth.token_secret.as_deref() else {
+        return Err(ApiError::new(
+            "TOKENS_DISABLED",
+            "Bearer tokens are disabled because AUTH_TOKEN_SECRET is not set.",
+        ));
+    };
+    let request: TokenRequest =
+        serde_json::from_slice(&body).map_err(|e| invalid(format!("Invalid token request: {}", e)))?;
+    if request.scopes.is_empty() {
+        return Err(invalid("At least one scope is required.".to_string()));
+    }
+    if let Some(tier) = request.tier.as_deref().filter(|tier| !state.config.tiers.contains_key(*tier)) {
+        return Err(invalid(format!("Unknown tier '{}'.", tier)));
+    }
+    let seconds = request.expires_in.unwrap_or(DEFAULT_TOKEN_EXPIRY_SECONDS);
+    if !(1..=MAX_TOKEN_EXPIRY_SECONDS).contains(&seconds) {
+        return Err(invalid(format!(
+            "Invalid expiresIn {}. Expected 1 to {} seconds.",
+            seconds, MAX_TOKEN_EXPIRY_SECONDS
+        )));
+    }
+
+    let claims = TokenClaims {
+        subject: request.subject,
+        scopes: request.scopes,
+        expires_at: now_millis() + seconds * 1000,
+        tier: request.tier,
+    };
+    let token = issue_token(secret, &claims);
+    let body = json!({
+        "token": token,
+        "scopes": claims.scopes,
+        "tier": claims.tier,
+        "expiresAt": claims.expires_at
+    });
+    Ok((StatusCode::CREATED, Json(body)).into_response())
+}
+
+/// Opens the configured level store, creating its directory if needed.
+fn open_level_store(settings: &StorageSettings) -> Result<Arc<dyn LevelStore>, StorageError> {
+    let path = settings.path();
+    Ok(match settings.kind {
+        StoreKind::Fs => Arc::new(FsLevelStore::open(path)?),
+        StoreKind::Sqlite => {
+            if let Some(parent) = std::path::Path::new(path).parent() {
+                std::fs::create_dir_all(parent).map_err(|e| StorageError::Backend { reason: e.to_string() })?;
+            }
+            Arc::new(SqliteLevelStore::open(path)?)
+        }
+    })
+}
+
 /// Handles GET `/health` requests.
 ///
 /// Returns a simple health check response for deployment platforms (e.g., Railway).
@@ -85,38 +1123,92 @@ async fn health_handler() -> impl IntoResponse {
     }))
 }
 
+/// Prints a startup error and exits with status 1.
+fn exit_with(message: impl Display) -> ! {
+    eprintln!("❌ {}", message);
+    std::process::exit(1);
+}
+
 /// Initializes and runs the HTTP server.
 ///
-/// Configures CORS for cross-origin requests and binds to the port specified
-/// by the `PORT` environment variable (defaults to 4000).
+/// Loads and validates the configuration (see [`ServerConfig::load`]),
+/// configures CORS for the allowed origins, opens the level store, and binds
+/// to the configured address (`0.0.0.0:4000` by default). Configuration
+/// problems are reported and exit with status 1.
 #[tokio::main]
 async fn main() {
-    let port = env::var("PORT")
-        .unwrap_or_else(|_| "4000".to_string())
-        .parse::<u16>()
-        .expect("PORT must be a valid u16");
+    let config = ServerConfig::load().unwrap_or_else(|e| exit_with(e.message()));
 
-    // CORS: allow all origins for development (restrict in production)
     let cors = CorsLayer::new()
-        .allow_origin(Any)
+        .allow_origin(match config.cors_origins() {
+            Some(origins) => AllowOrigin::list(origins),
+            None => AllowOrigin::any(),
+        })
         .allow_methods(Any)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers([WARNINGS_HEADER, MIGRATIONS_HEADER]);
+
+    let levels = open_level_store(&config.storage).unwrap_or_else(|e| {
+        exit_with(format!("Failed to open level store at '{}': {}", config.storage.path(), e.message()))
+    });
+    let state = AppState {
+        levels,
+        rooms: Arc::new(CollabRooms::new()),
+        auth: Arc::new(config.auth_config()),
+        limiter: Arc::new(RateLimiter::new(config.rate_limit)),
+        config: Arc::new(config.clone()),
+    };
+    if !state.auth.is_enabled() {
+        println!("⚠️  Authentication disabled: set API_KEYS or AUTH_TOKEN_SECRET to require credentials");
+    }
 
     let app = Router::new()
         .route("/health", get(health_handler))
         .route("/api/health", get(health_handler))
         .route("/api/export", post(export_handler))
+        .route("/api/validate", post(validate_handler))
+        .route("/api/playability", post(playability_handler))
+        .route("/api/merge", post(merge_handler))
+        .route(
+            "/api/import",
+            post(import_handler).layer(DefaultBodyLimit::max(config.server.import_body_limit_bytes)),
+        )
+        .route("/api/levels", post(create_level_handler).get(list_levels_handler))
+        .route(
+            "/api/levels/:id",
+            get(get_level_handler).put(put_level_handler).delete(delete_level_handler),
+        )
+        .route("/api/levels/:id/export", get(export_level_handler))
+        .route("/api/levels/:id/collab", get(collab_handler))
+        .route("/api/levels/:id/revisions", get(list_revisions_handler))
+        .route("/api/levels/:id/revisions/:revision", get(get_revision_handler))
+        .route("/api/levels/:id/diff", get(diff_handler))
+        .route("/api/levels/:id/share", post(create_share_handler))
+        .route("/api/levels/:id/share/:token", delete(revoke_share_handler))
+        .route("/api/shared/:token", get(shared_level_handler))
+        .route("/api/tokens", post(create_token_handler))
+        // Layers run outermost first: authenticate (throttling failures per IP), then rate limit per client
+        .layer(middleware::from_fn_with_state(state.clone(), rate_limit_middleware))
+        .layer(middleware::from_fn_with_state(state.clone(), auth_middleware))
+        .layer(DefaultBodyLimit::max(config.server.body_limit_bytes))
+        .with_state(state)
         .layer(cors);
 
-    let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port))
+    let address = config.socket_addr();
+    let listener = tokio::net::TcpListener::bind(address)
         .await
-        .expect("Failed to bind to address");
+        .unwrap_or_else(|e| exit_with(format!("Failed to bind to {}: {}", address, e)));
 
-    println!("🚀 Backend server running on http://0.0.0.0:{}", port);
+    let port = config.server.port;
+    println!("🚀 Backend server running on http://{}", address);
     println!("📡 Export endpoint: POST http://localhost:{}/api/export", port);
+    println!("🔍 Validate endpoint: POST http://localhost:{}/api/validate", port);
+    println!("🔀 Merge endpoint: POST http://localhost:{}/api/merge", port);
+    println!("📥 Import endpoint: POST http://localhost:{}/api/import", port);
+    println!("💾 Levels endpoint: http://localhost:{}/api/levels", port);
     println!("❤️  Health endpoint: GET http://localhost:{}/health", port);
 
-    axum::serve(listener, app)
-        .await
-        .expect("Server failed to start");
+    if let Err(e) = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await {
+        exit_with(format!("Server failed: {}", e));
+    }
 }
@@ -0,0 +1,61 @@
+//! Roblox material names.
+//!
+//! Maps Space JSON material names to `Enum.Material` values used in `.rbxlx` files.
+
+/// Supported materials as `(name, Enum.Material value)` pairs.
+///
+/// Terrain-only materials (e.g. `Water`, `Air`) are excluded since they cannot be
+/// applied to Parts.
+pub const MATERIALS: &[(&str, u32)] = &[
+    ("Plastic", 256),
+    ("SmoothPlastic", 272),
+    ("Neon", 288),
+    ("Wood", 512),
+    ("WoodPlanks", 528),
+    ("Marble", 784),
+    ("Basalt", 788),
+    ("Slate", 800),
+    ("CrackedLava", 804),
+    ("Concrete", 816),
+    ("Limestone", 820),
+    ("Granite", 832),
+    ("Pavement", 836),
+    ("Brick", 848),
+    ("Pebble", 864),
+    ("Cobblestone", 880),
+    ("Rock", 896),
+    ("Sandstone", 912),
+    ("CorrodedMetal", 1040),
+    ("DiamondPlate", 1056),
+    ("Foil", 1072),
+    ("Metal", 1088),
+    ("Grass", 1280),
+    ("LeafyGrass", 1284),
+    ("Sand", 1296),
+    ("Fabric", 1312),
+    ("Snow", 1328),
+    ("Mud", 1344),
+    ("Ground", 1360),
+    ("Asphalt", 1376),
+    ("Salt", 1392),
+    ("Ice", 1536),
+    ("Glacier", 1552),
+    ("Glass", 1568),
+    ("ForceField", 1584),
+];
+
+/// Returns the `Enum.Material` value for a material name (case-insensitive).
+pub fn material_value(name: &str) -> Option<u32> {
+    MATERIALS
+        .iter()
+        .find(|(material, _)| material.eq_ignore_ascii_case(name))
+        .map(|&(_, value)| value)
+}
+
+/// Returns the material name for an `Enum.Material` value.
+pub fn material_name(value: u32) -> Option<&'static str> {
+    MATERIALS
+        .iter()
+        .find(|&&(_, material_value)| material_value == value)
+        .map(|&(name, _)| name)
+}
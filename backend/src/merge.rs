@@ -0,0 +1,157 @@
+//! Three-way merge of concurrent Space JSON edits.
+//!
+//! Given the level both editors started from (`base`) and each editor's
+//! version (`ours` and `theirs`), blocks are merged position by position: a
+//! change made on only one side is kept, and a position changed differently on
+//! both sides is a conflict. Since each side's changes can be valid on their
+//! own but not together (e.g. a spawn for a team the other side removed), the
+//! merged level is validated and every error is a conflict too.
+
+use crate::diff::colors_equal;
+use crate::models::{Block, SpaceJSON};
+use crate::stats::GridPosition;
+use crate::validation::{validate_no_duplicates, validate_space_json_all, ValidationError, ValidationLimits};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Result of a three-way merge, as returned by `/api/merge`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LevelMerge {
+    /// The merged level. Conflicting positions hold `ours`' version.
+    pub merged: SpaceJSON,
+    /// Positions changed differently on both sides, ordered by position,
+    /// followed by the merged level's validation errors.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// A change from both sides that the merge could not reconcile.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum MergeConflict {
+    Block(Box<BlockConflict>),
+    /// The merged level fails validation, e.g. with `UNKNOWN_TEAM`.
+    Invalid { error: &'static str, message: String },
+}
+
+/// A position whose block was changed differently by both sides.
+///
+/// A missing block means the position is empty in that version.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlockConflict {
+    #[serde(flatten)]
+    pub position: GridPosition,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<Block>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ours: Option<Block>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theirs: Option<Block>,
+}
+
+/// Merges `ours` and `theirs`, both edited from `base`.
+///
+/// Root fields (`name`, `world`, `teams`, `spawns`) are merged as whole values:
+/// whichever side changed a field wins, and `ours` wins if both did. The merged
+/// schema version is the newer of the two sides. Blocks are ordered by position.
+/// The merged level is validated against `limits`, and each error is reported
+/// as a [`MergeConflict::Invalid`].
+///
+/// Returns [`ValidationError::DuplicatePosition`] if any input has two blocks
+/// at one position.
+pub fn merge_levels(
+    base: &SpaceJSON,
+    ours: &SpaceJSON,
+    theirs: &SpaceJSON,
+    limits: &ValidationLimits,
+) -> Result<LevelMerge, ValidationError> {
+    for level in [base, ours, theirs] {
+        validate_no_duplicates(&level.blocks)?;
+    }
+
+    let base_blocks = blocks_by_position(&base.blocks);
+    let our_blocks = blocks_by_position(&ours.blocks);
+    let their_blocks = blocks_by_position(&theirs.blocks);
+    let positions: BTreeSet<(i32, i32, i32)> = base_blocks
+        .keys()
+        .chain(our_blocks.keys())
+        .chain(their_blocks.keys())
+        .copied()
+        .collect();
+
+    let mut blocks = Vec::new();
+    let mut conflicts = Vec::new();
+    for position in positions {
+        let base_block = base_blocks.get(&position).copied();
+        let our_block = our_blocks.get(&position).copied();
+        let their_block = their_blocks.get(&position).copied();
+
+        let merged = if same_block(our_block, base_block) {
+            their_block
+        } else if same_block(their_block, base_block) || same_block(our_block, their_block) {
+            our_block
+        } else {
+            conflicts.push(MergeConflict::Block(Box::new(BlockConflict {
+                position: GridPosition {
+                    x: position.0,
+                    y: position.1,
+                    z: position.2,
+                },
+                base: base_block.cloned(),
+                ours: our_block.cloned(),
+                theirs: their_block.cloned(),
+            })));
+            our_block
+        };
+        blocks.extend(merged.cloned());
+    }
+
+    let merged = SpaceJSON {
+        schema_version: ours.schema_version.max(theirs.schema_version),
+        name: merge_field(&base.name, &ours.name, &theirs.name),
+        blocks,
+        world: merge_field(&base.world, &ours.world, &theirs.world),
+        teams: merge_field(&base.teams, &ours.teams, &theirs.teams),
+        spawns: merge_field(&base.spawns, &ours.spawns, &theirs.spawns),
+    };
+    if let Err(groups) = validate_space_json_all(&merged, limits) {
+        conflicts.extend(groups.iter().flat_map(|group| &group.errors).map(|error| MergeConflict::Invalid {
+            error: error.error_code(),
+            message: error.message(),
+        }));
+    }
+    Ok(LevelMerge { merged, conflicts })
+}
+
+fn blocks_by_position(blocks: &[Block]) -> BTreeMap<(i32, i32, i32), &Block> {
+    blocks.iter().map(|block| ((block.x, block.y, block.z), block)).collect()
+}
+
+/// Compares two optional blocks, treating equivalent color spellings as equal.
+fn same_block(a: Option<&Block>, b: Option<&Block>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => {
+            colors_equal(&a.color, &b.color)
+                && Block {
+                    color: String::new(),
+                    ..a.clone()
+                } == Block {
+                    color: String::new(),
+                    ..b.clone()
+                }
+        }
+        _ => false,
+    }
+}
+
+/// Takes `theirs` if only they changed the field, `ours` otherwise.
+///
+/// Fields are compared by their JSON form, so types without `PartialEq` work too.
+fn merge_field<T: Clone + Serialize>(base: &T, ours: &T, theirs: &T) -> T {
+    let as_json = |value: &T| serde_json::to_value(value).ok();
+    if as_json(ours) == as_json(base) {
+        theirs.clone()
+    } else {
+        ours.clone()
+    }
+}
@@ -0,0 +1,174 @@
+//! Greedy meshing of voxel blocks.
+//!
+//! Merges contiguous blocks with the same appearance and kind into axis-aligned
+//! boxes so a level can be exported with far fewer Parts. Blocks sit on a 2-stud
+//! grid, so two blocks are adjacent when their coordinates differ by exactly 2
+//! along one axis.
+
+use crate::models::Block;
+use std::collections::{HashMap, HashSet};
+
+/// Size of one block along each axis, in studs.
+const BLOCK_SIZE: i32 = 2;
+
+/// An axis-aligned box of blocks produced by [`greedy_mesh`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedBox {
+    /// Position of the block at the minimum corner of the box (in studs).
+    pub origin: (i32, i32, i32),
+    /// Number of blocks spanned along X, Y and Z.
+    pub extent: (i32, i32, i32),
+    /// Index of a representative block in the input slice.
+    ///
+    /// Every block inside the box shares this block's appearance.
+    pub source: usize,
+}
+
+impl MergedBox {
+    /// Returns the Part size of the box in studs, assuming standard 2x2x2 blocks.
+    pub fn size(&self) -> (f32, f32, f32) {
+        (
+            (self.extent.0 * BLOCK_SIZE) as f32,
+            (self.extent.1 * BLOCK_SIZE) as f32,
+            (self.extent.2 * BLOCK_SIZE) as f32,
+        )
+    }
+
+    /// Returns the center of the box in studs.
+    pub fn center(&self) -> (f32, f32, f32) {
+        (
+            (self.origin.0 + self.extent.0 - 1) as f32,
+            (self.origin.1 + self.extent.1 - 1) as f32,
+            (self.origin.2 + self.extent.2 - 1) as f32,
+        )
+    }
+
+    /// Returns the positions of every block covered by the box.
+    pub fn voxels(&self) -> Vec<(i32, i32, i32)> {
+        let mut voxels = Vec::with_capacity((self.extent.0 * self.extent.1 * self.extent.2) as usize);
+        for dy in 0..self.extent.1 {
+            for dz in 0..self.extent.2 {
+                for dx in 0..self.extent.0 {
+                    voxels.push((
+                        self.origin.0 + dx * BLOCK_SIZE,
+                        self.origin.1 + dy * BLOCK_SIZE,
+                        self.origin.2 + dz * BLOCK_SIZE,
+                    ));
+                }
+            }
+        }
+        voxels
+    }
+}
+
+/// Blocks may only merge when they share a key.
+///
+/// The lattice offset keeps blocks on different 2-stud grids (e.g. odd and even
+/// X coordinates) apart, since they can never tile into a single box. Blocks with
+/// a custom size and spawn blocks never merge, so they get a key unique to their index.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MergeKey {
+    lattice: (i32, i32, i32),
+    color: String,
+    material: Option<String>,
+    transparency_bits: Option<u32>,
+    reflectance_bits: Option<u32>,
+    kind: Option<String>,
+    unique: Option<usize>,
+}
+
+fn merge_key(block: &Block, index: usize) -> MergeKey {
+    MergeKey {
+        lattice: (
+            block.x.rem_euclid(BLOCK_SIZE),
+            block.y.rem_euclid(BLOCK_SIZE),
+            block.z.rem_euclid(BLOCK_SIZE),
+        ),
+        color: block.color.to_ascii_uppercase(),
+        material: block.material.as_ref().map(|m| m.to_ascii_lowercase()),
+        transparency_bits: block.transparency.map(f32::to_bits),
+        reflectance_bits: block.reflectance.map(f32::to_bits),
+        kind: block.kind.as_ref().map(|k| k.to_ascii_lowercase()),
+        unique: (block.size.is_some() || block.spawn.is_some()).then_some(index),
+    }
+}
+
+/// Merges contiguous blocks with identical appearance and kind into boxes.
+///
+/// Grows each box along X first, then Z, then Y, which favours the flat floors
+/// and walls typical of hand-built levels. Every input block is covered by
+/// exactly one box; duplicate positions are covered once. Blocks with a custom
+/// `size` and spawn blocks are always emitted as single-block boxes.
+pub fn greedy_mesh(blocks: &[Block]) -> Vec<MergedBox> {
+    let mut groups: HashMap<MergeKey, Vec<usize>> = HashMap::new();
+    let mut group_order = Vec::new();
+    for (index, block) in blocks.iter().enumerate() {
+        let key = merge_key(block, index);
+        let members = groups.entry(key.clone()).or_default();
+        if members.is_empty() {
+            group_order.push(key);
+        }
+        members.push(index);
+    }
+
+    let mut boxes = Vec::new();
+    for key in group_order {
+        mesh_group(blocks, &groups[&key], &mut boxes);
+    }
+    boxes
+}
+
+/// Meshes one group of mergeable blocks, appending the resulting boxes.
+fn mesh_group(blocks: &[Block], members: &[usize], boxes: &mut Vec<MergedBox>) {
+    let mut cells: HashMap<(i32, i32, i32), usize> = HashMap::with_capacity(members.len());
+    for &index in members {
+        let block = &blocks[index];
+        cells.entry((block.x, block.y, block.z)).or_insert(index);
+    }
+
+    // Visit cells bottom-up so boxes grow from a deterministic corner
+    let mut order: Vec<(i32, i32, i32)> = cells.keys().copied().collect();
+    order.sort_by_key(|&(x, y, z)| (y, z, x));
+
+    let mut used: HashSet<(i32, i32, i32)> = HashSet::with_capacity(cells.len());
+    let is_free = |used: &HashSet<(i32, i32, i32)>, cell: (i32, i32, i32)| {
+        cells.contains_key(&cell) && !used.contains(&cell)
+    };
+
+    for origin in order {
+        if used.contains(&origin) {
+            continue;
+        }
+        let (x0, y0, z0) = origin;
+
+        let mut width = 1;
+        while is_free(&used, (x0 + width * BLOCK_SIZE, y0, z0)) {
+            width += 1;
+        }
+
+        let mut depth = 1;
+        while (0..width).all(|dx| is_free(&used, (x0 + dx * BLOCK_SIZE, y0, z0 + depth * BLOCK_SIZE))) {
+            depth += 1;
+        }
+
+        let mut height = 1;
+        while (0..depth).all(|dz| {
+            (0..width).all(|dx| {
+                is_free(
+                    &used,
+                    (x0 + dx * BLOCK_SIZE, y0 + height * BLOCK_SIZE, z0 + dz * BLOCK_SIZE),
+                )
+            })
+        }) {
+            height += 1;
+        }
+
+        let merged = MergedBox {
+            origin,
+            extent: (width, height, depth),
+            source: cells[&origin],
+        };
+        used.extend(merged.voxels());
+        boxes.push(merged);
+    }
+}
@@ -0,0 +1,169 @@
+//! Space JSON schema migration.
+//!
+//! Upgrades payloads saved by older editors to [`CURRENT_SCHEMA_VERSION`] before
+//! deserialization. Each migration step upgrades the raw JSON by exactly one
+//! version, so a payload is walked forward through every intermediate schema.
+
+use crate::models::{SpaceJSON, CURRENT_SCHEMA_VERSION};
+use serde_json::{Map, Value};
+
+/// Oldest schema version that can be migrated.
+pub const OLDEST_SCHEMA_VERSION: u32 = 1;
+
+/// Migration error variants with associated context data.
+#[derive(Debug, Clone)]
+pub enum MigrationError {
+    InvalidPayload { reason: String },
+    MissingSchemaVersion,
+    UnsupportedSchemaVersion { version: u64 },
+}
+
+impl MigrationError {
+    /// Returns a machine-readable error code for API responses.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            MigrationError::InvalidPayload { .. } => "INVALID_PAYLOAD",
+            MigrationError::MissingSchemaVersion => "MISSING_SCHEMA_VERSION",
+            MigrationError::UnsupportedSchemaVersion { .. } => "INVALID_SCHEMA_VERSION",
+        }
+    }
+
+    /// Returns a human-readable error message for display to users.
+    pub fn message(&self) -> String {
+        match self {
+            MigrationError::InvalidPayload { reason } => {
+                format!("Invalid Space JSON payload: {}", reason)
+            }
+            MigrationError::MissingSchemaVersion => {
+                "Space JSON is missing a numeric 'schemaVersion' field.".to_string()
+            }
+            MigrationError::UnsupportedSchemaVersion { version } => {
+                format!(
+                    "Unsupported schema version: {}. Supported versions: {} to {}.",
+                    version, OLDEST_SCHEMA_VERSION, CURRENT_SCHEMA_VERSION
+                )
+            }
+        }
+    }
+}
+
+/// Summary of the changes made while migrating a payload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationReport {
+    /// Schema version of the payload as received.
+    pub from_version: u32,
+    /// Schema version after migration (always [`CURRENT_SCHEMA_VERSION`]).
+    pub to_version: u32,
+    /// Human-readable description of each change, in the order applied.
+    pub changes: Vec<String>,
+}
+
+impl MigrationReport {
+    /// Returns `true` if the payload was already at the current version.
+    pub fn is_noop(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// A single migration step, upgrading a payload from one version to the next.
+///
+/// Receives the root object and returns descriptions of the changes it made.
+type MigrationStep = fn(&mut Map<String, Value>) -> Vec<String>;
+
+/// Migration steps indexed by the version they upgrade from.
+const MIGRATIONS: &[(u32, MigrationStep)] = &[(1, migrate_v1_to_v2)];
+
+/// Top-level fields defined by schema version 1.
+const V1_ROOT_FIELDS: &[&str] = &["schemaVersion", "name", "blocks"];
+
+/// Block fields defined by schema version 1.
+const V1_BLOCK_FIELDS: &[&str] = &["x", "y", "z", "color"];
+
+/// Upgrades a version 1 payload to version 2.
+///
+/// Version 2 only adds optional fields, so the blocks carry over as-is.
+/// Fields that version 1 never defined (on the root or on blocks) are removed
+/// rather than being reinterpreted with version 2 meaning.
+fn migrate_v1_to_v2(root: &mut Map<String, Value>) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    let unknown: Vec<String> = root
+        .keys()
+        .filter(|key| !V1_ROOT_FIELDS.contains(&key.as_str()))
+        .cloned()
+        .collect();
+    for field in unknown {
+        root.remove(&field);
+        changes.push(format!("Removed field '{}' (not part of schema version 1).", field));
+    }
+
+    if let Some(Value::Array(blocks)) = root.get_mut("blocks") {
+        for (index, block) in blocks.iter_mut().enumerate() {
+            if let Value::Object(fields) = block {
+                let unknown: Vec<String> = fields
+                    .keys()
+                    .filter(|key| !V1_BLOCK_FIELDS.contains(&key.as_str()))
+                    .cloned()
+                    .collect();
+                for field in unknown {
+                    fields.remove(&field);
+                    changes.push(format!(
+                        "Removed field '{}' from block {} (not part of schema version 1).",
+                        field, index
+                    ));
+                }
+            }
+        }
+    }
+
+    changes
+}
+
+/// Reads the `schemaVersion` field of a raw payload.
+fn schema_version(root: &Map<String, Value>) -> Result<u64, MigrationError> {
+    root.get("schemaVersion")
+        .and_then(Value::as_u64)
+        .ok_or(MigrationError::MissingSchemaVersion)
+}
+
+/// Upgrades a raw Space JSON value to [`CURRENT_SCHEMA_VERSION`].
+///
+/// Applies each migration step in turn, updating `schemaVersion` after every
+/// step. Payloads already at the current version are returned unchanged.
+pub fn migrate_value(mut value: Value) -> Result<(Value, MigrationReport), MigrationError> {
+    let root = value.as_object_mut().ok_or_else(|| MigrationError::InvalidPayload {
+        reason: "expected a JSON object".to_string(),
+    })?;
+
+    let version = schema_version(root)?;
+    if version < OLDEST_SCHEMA_VERSION as u64 || version > CURRENT_SCHEMA_VERSION as u64 {
+        return Err(MigrationError::UnsupportedSchemaVersion { version });
+    }
+
+    let from_version = version as u32;
+    let mut changes = Vec::new();
+    for &(step_from, step) in MIGRATIONS {
+        if step_from < from_version {
+            continue;
+        }
+        changes.extend(step(root));
+        root.insert("schemaVersion".to_string(), Value::from(step_from + 1));
+        changes.push(format!("Upgraded schemaVersion {} to {}.", step_from, step_from + 1));
+    }
+
+    let report = MigrationReport {
+        from_version,
+        to_version: CURRENT_SCHEMA_VERSION,
+        changes,
+    };
+    Ok((value, report))
+}
+
+/// Upgrades a raw Space JSON value and deserializes it into a [`SpaceJSON`].
+pub fn migrate(value: Value) -> Result<(SpaceJSON, MigrationReport), MigrationError> {
+    let (value, report) = migrate_value(value)?;
+    let space_json = serde_json::from_value(value).map_err(|e| MigrationError::InvalidPayload {
+        reason: e.to_string(),
+    })?;
+    Ok((space_json, report))
+}
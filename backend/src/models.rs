@@ -1,29 +1,183 @@
 //! Space JSON data models.
 //!
-//! Defines structures for deserializing Space JSON from the frontend.
+//! Defines structures for (de)serializing Space JSON exchanged with the frontend.
 //! Schema corresponds to `frontend/src/export/serialize.ts`.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+/// Newest Space JSON schema version understood by the backend.
+///
+/// Version 1 payloads are still accepted; version 2 adds optional block
+/// appearance fields (`material`, `transparency`, `reflectance`, `size`), spawn
+/// and gameplay blocks, and the optional `world`, `teams` and `spawns` sections.
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
 
 /// Root object of a Space JSON payload.
 ///
 /// Contains metadata and the list of blocks that make up a level.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct SpaceJSON {
     #[serde(rename = "schemaVersion")]
     pub schema_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     pub blocks: Vec<Block>,
+    /// Baseplate, spawn and lighting settings (schema version 2).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub world: Option<WorldSettings>,
+    /// Teams that spawns can belong to (schema version 2).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub teams: Vec<Team>,
+    /// Free-standing spawn points (schema version 2).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spawns: Vec<SpawnPoint>,
 }
 
 /// A single voxel block with position and color.
 ///
 /// Coordinates are in Roblox studs (already scaled 2x from Three.js units by frontend).
-#[derive(Debug, Clone, Deserialize)]
+/// Fields after `color` require schema version 2 and fall back to Roblox defaults when absent.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
 pub struct Block {
     pub x: i32,
     pub y: i32,
     pub z: i32,
     /// Hex color string in `#RRGGBB` or `#RGB` format.
     pub color: String,
+    /// Roblox material name (e.g. `"Wood"`), matched case-insensitively.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub material: Option<String>,
+    /// Transparency from 0.0 (opaque) to 1.0 (invisible).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transparency: Option<f32>,
+    /// Reflectance from 0.0 to 1.0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reflectance: Option<f32>,
+    /// Part size in studs. Blocks are 2x2x2 when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<BlockSize>,
+    /// Exports the block as a SpawnLocation instead of a plain Part.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spawn: Option<BlockSpawn>,
+    /// Gameplay behavior (e.g. `"kill"`, `"checkpoint"`), matched case-insensitively.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+/// Spawn settings of a spawn block.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct BlockSpawn {
+    /// Name of the team that spawns here. Neutral (anyone spawns) when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team: Option<String>,
+}
+
+/// Non-cubic block dimensions in studs.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct BlockSize {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// A position in studs.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct WorldPosition {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// World settings applied around the level's blocks.
+///
+/// Every setting is optional; anything left out keeps the generator's default.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct WorldSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseplate: Option<BaseplateSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spawn: Option<SpawnSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lighting: Option<LightingSettings>,
+}
+
+/// Baseplate settings. The baseplate's top surface always sits at Y=0.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BaseplateSettings {
+    /// Whether to generate a baseplate at all. Defaults to `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Baseplate size in studs. Defaults to 200x16x200.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<BlockSize>,
+    /// Hex color string in `#RRGGBB` or `#RGB` format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Roblox material name, matched case-insensitively.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub material: Option<String>,
+}
+
+/// SpawnLocation placement. Defaults to a fixed spawn at `(0, 0.5, 0)`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SpawnSettings {
+    /// Place the spawn above the center of the level's blocks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto: Option<bool>,
+    /// Explicit spawn center in studs. Cannot be combined with `auto`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<WorldPosition>,
+}
+
+/// Lighting service settings.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LightingSettings {
+    /// Lighting technology name (e.g. `"ShadowMap"`, `"Future"`). Defaults to ShadowMap.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub technology: Option<String>,
+    /// Time of day in hours, from 0 to 24.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_of_day: Option<f32>,
+    /// Ambient light hex color.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ambient: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fog: Option<FogSettings>,
+}
+
+/// Distance fog settings.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct FogSettings {
+    /// Fog hex color.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Distance in studs where fog begins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<f32>,
+    /// Distance in studs where fog is fully opaque.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<f32>,
+}
+
+/// A team players can be assigned to.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Team {
+    /// Unique team name, referenced by spawns.
+    pub name: String,
+    /// Hex color, snapped to the nearest Roblox BrickColor. A distinct color is
+    /// picked automatically when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+/// A free-standing SpawnLocation, centered at a position in studs.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SpawnPoint {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    /// Name of the team that spawns here. Neutral (anyone spawns) when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team: Option<String>,
 }
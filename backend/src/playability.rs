@@ -0,0 +1,448 @@
+//! Playability analysis.
+//!
+//! Estimates which parts of a level a player can get to from the spawn. The
+//! level is split into 2-stud columns on the block grid, and a default Roblox
+//! character walks and jumps between the top surfaces in them: it needs
+//! [`CHARACTER_HEIGHT`] of headroom to stand, jumps [`JUMP_HEIGHT`] studs high
+//! under [`GRAVITY`], covers [`WALK_SPEED`] studs per second in the air,
+//! and falls any distance unharmed. Bounce and speed blocks raise the jump
+//! velocity and walk speed of jumps starting on them, and kill blocks can't be
+//! stood on. Obstacles along a jump's arc are not checked, so the analysis can
+//! be optimistic around walls and overhangs. Levels covering more than
+//! [`MAX_COLUMNS`] columns are not analyzed.
+
+use crate::gameplay::{BlockKind, BOUNCE_VELOCITY, SPEED_BOOST};
+use crate::models::SpaceJSON;
+use crate::geometry::{baseplate_size, block_box, boxes_touch, PartBox};
+use crate::rbxlx::{spawn_pad_positions, SPAWN_PAD_SIZE};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::ops::RangeInclusive;
+
+/// Roblox's default `Workspace.Gravity`, in studs per second squared.
+pub const GRAVITY: f32 = 196.2;
+
+/// Default `Humanoid.JumpHeight`, in studs.
+pub const JUMP_HEIGHT: f32 = 7.2;
+
+/// Default `Humanoid.WalkSpeed`, in studs per second.
+pub const WALK_SPEED: f32 = 16.0;
+
+/// Height of a default character, in studs.
+pub const CHARACTER_HEIGHT: f32 = 5.0;
+
+/// Width of a column, matching the 2-stud block grid and a character's footprint.
+const COLUMN_SIZE: f32 = 2.0;
+
+/// Columns of baseplate analyzed around the level's Parts.
+const GROUND_MARGIN: i32 = 2;
+
+/// Columns per side of the cells footings are grouped in while searching for jumps.
+const CELL_COLUMNS: i32 = 4;
+
+/// Tolerance for comparing heights and distances, in studs.
+const EPSILON: f32 = 1e-3;
+
+/// Most Part columns analyzed, counting one per column each Part (and the
+/// baseplate around the level) reaches into.
+pub const MAX_COLUMNS: usize = 100_000;
+
+/// Playability analysis error variants with associated context data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayabilityError {
+    LevelTooLarge { columns: usize, limit: usize },
+}
+
+impl PlayabilityError {
+    /// Returns a machine-readable error code for API responses.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            PlayabilityError::LevelTooLarge { .. } => "LEVEL_TOO_LARGE_TO_ANALYZE",
+        }
+    }
+
+    /// Returns a human-readable error message for display to users.
+    pub fn message(&self) -> String {
+        match self {
+            PlayabilityError::LevelTooLarge { columns, limit } => format!(
+                "Level covers {} {}-stud columns, more than the {} playability analysis supports.",
+                columns, COLUMN_SIZE, limit
+            ),
+        }
+    }
+}
+
+/// A top surface a character can stand on, one column wide.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Surface {
+    /// Index of the block whose top this is.
+    pub block: usize,
+    /// Center of the column, in studs.
+    pub x: i32,
+    pub z: i32,
+    /// Height of the surface, in studs.
+    pub y: f32,
+    /// Whether a player can get here from a spawn.
+    pub reachable: bool,
+}
+
+/// Result of [`analyze_playability`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayabilityReport {
+    /// `true` if the level has a finish block and every checkpoint and finish
+    /// block can be reached.
+    pub completable: bool,
+    /// Every block surface a character can stand on, by block index.
+    pub surfaces: Vec<Surface>,
+    /// Indices of checkpoint blocks no player can touch.
+    pub unreachable_checkpoints: Vec<usize>,
+    /// Indices of finish blocks no player can touch.
+    pub unreachable_finishes: Vec<usize>,
+}
+
+/// A Part that characters collide with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Part {
+    Block(usize),
+    SpawnPad,
+    Baseplate,
+}
+
+/// The part of a Part's height inside one column.
+struct Span {
+    bottom: f32,
+    top: f32,
+    part: Part,
+}
+
+/// A surface in the reachability graph, including spawn pads and the baseplate.
+struct Footing {
+    column: (i32, i32),
+    y: f32,
+    /// Free height above the surface.
+    headroom: f32,
+    /// Lowest index of the blocks the surface belongs to.
+    block: Option<usize>,
+    spawn: bool,
+    jump_velocity: f32,
+    walk_speed: f32,
+}
+
+impl Footing {
+    /// Highest a jump from here can rise before hitting the jump's apex or the ceiling.
+    fn max_rise(&self) -> f32 {
+        (self.jump_velocity.powi(2) / (2.0 * GRAVITY)).min(self.headroom - CHARACTER_HEIGHT)
+    }
+
+    /// Longest gap a jump from here can cross while landing `rise` studs higher.
+    fn reach(&self, rise: f32) -> f32 {
+        let velocity = self.jump_velocity;
+        let airtime = (velocity + (velocity.powi(2) - 2.0 * GRAVITY * rise).max(0.0).sqrt()) / GRAVITY;
+        self.walk_speed * airtime
+    }
+
+    /// Highest a jump from here can land across a gap of `gap` studs.
+    fn max_rise_across(&self, gap: f32) -> f32 {
+        let airtime = gap / self.walk_speed;
+        if airtime <= self.jump_velocity / GRAVITY {
+            // Still rising when the gap is crossed
+            return self.max_rise();
+        }
+        (self.jump_velocity * airtime - GRAVITY * airtime.powi(2) / 2.0).min(self.max_rise())
+    }
+
+    /// The space a character standing or jumping here can touch.
+    fn touch_box(&self) -> PartBox {
+        let (x, z) = (self.column.0 as f32 * COLUMN_SIZE, self.column.1 as f32 * COLUMN_SIZE);
+        let half = COLUMN_SIZE / 2.0;
+        (
+            [x - half, self.y, z - half],
+            [x + half, self.y + CHARACTER_HEIGHT + self.max_rise().max(0.0), z + half],
+        )
+    }
+}
+
+/// Returns the indices of the columns a Part from `min` to `max` reaches into along one axis.
+fn column_range(min: f32, max: f32) -> RangeInclusive<i32> {
+    let half = COLUMN_SIZE / 2.0;
+    let first = ((min + EPSILON - half) / COLUMN_SIZE).floor() as i32 + 1;
+    let last = ((max - EPSILON + half) / COLUMN_SIZE).ceil() as i32 - 1;
+    first..=last
+}
+
+/// Returns the number of columns in a range from [`column_range`].
+fn range_len(range: &RangeInclusive<i32>) -> usize {
+    (i64::from(*range.end()) - i64::from(*range.start()) + 1).max(0) as usize
+}
+
+/// Splits the Parts a place export generates into columns, without building
+/// more than [`MAX_COLUMNS`] of them.
+fn columns(space_json: &SpaceJSON) -> Result<HashMap<(i32, i32), Vec<Span>>, PlayabilityError> {
+    let (pad_x, pad_y, pad_z) = SPAWN_PAD_SIZE;
+    let pads = spawn_pad_positions(space_json).into_iter().map(|(x, y, z)| {
+        let pad = (
+            [x - pad_x / 2.0, y - pad_y / 2.0, z - pad_z / 2.0],
+            [x + pad_x / 2.0, y + pad_y / 2.0, z + pad_z / 2.0],
+        );
+        (pad, Part::SpawnPad)
+    });
+    let footprints: Vec<_> = space_json
+        .blocks
+        .iter()
+        .enumerate()
+        .map(|(index, block)| (block_box(block), Part::Block(index)))
+        .chain(pads)
+        .map(|((min, max), part)| (column_range(min[0], max[0]), column_range(min[2], max[2]), min[1], max[1], part))
+        .filter(|(columns_i, columns_k, ..)| !columns_i.is_empty() && !columns_k.is_empty())
+        .collect();
+
+    // The baseplate only matters around the level, where characters walk between Parts
+    let baseplate = space_json.world.as_ref().and_then(|world| world.baseplate.as_ref());
+    let ground = baseplate_size(baseplate).filter(|_| !footprints.is_empty()).map(|(size_x, size_y, size_z)| {
+        let first_i = footprints.iter().map(|(columns_i, ..)| *columns_i.start()).min().unwrap_or_default();
+        let last_i = footprints.iter().map(|(columns_i, ..)| *columns_i.end()).max().unwrap_or_default();
+        let first_k = footprints.iter().map(|(_, columns_k, ..)| *columns_k.start()).min().unwrap_or_default();
+        let last_k = footprints.iter().map(|(_, columns_k, ..)| *columns_k.end()).max().unwrap_or_default();
+        let plate_i = column_range(-size_x / 2.0, size_x / 2.0);
+        let plate_k = column_range(-size_z / 2.0, size_z / 2.0);
+        (
+            (first_i - GROUND_MARGIN).max(*plate_i.start())..=(last_i + GROUND_MARGIN).min(*plate_i.end()),
+            (first_k - GROUND_MARGIN).max(*plate_k.start())..=(last_k + GROUND_MARGIN).min(*plate_k.end()),
+            -size_y,
+            0.0,
+            Part::Baseplate,
+        )
+    });
+
+    // Counted before anything is built, so huge Parts cost nothing
+    let count = footprints
+        .iter()
+        .chain(&ground)
+        .fold(0usize, |count, (columns_i, columns_k, ..)| {
+            count.saturating_add(range_len(columns_i).saturating_mul(range_len(columns_k)))
+        });
+    if count > MAX_COLUMNS {
+        return Err(PlayabilityError::LevelTooLarge {
+            columns: count,
+            limit: MAX_COLUMNS,
+        });
+    }
+
+    let mut columns: HashMap<(i32, i32), Vec<Span>> = HashMap::new();
+    for (columns_i, columns_k, bottom, top, part) in footprints.into_iter().chain(ground) {
+        for i in columns_i {
+            for k in columns_k.clone() {
+                columns.entry((i, k)).or_default().push(Span { bottom, top, part });
+            }
+        }
+    }
+    Ok(columns)
+}
+
+/// Returns every surface a character can stand on without being killed.
+fn footings(space_json: &SpaceJSON) -> Result<Vec<Footing>, PlayabilityError> {
+    let kinds: Vec<_> = space_json
+        .blocks
+        .iter()
+        .map(|block| block.kind.as_deref().and_then(BlockKind::from_name))
+        .collect();
+    let mut footings = Vec::new();
+    for (column, mut spans) in columns(space_json)? {
+        // Walking down from the highest top, a surface's ceiling is the lowest
+        // bottom of the spans above it
+        spans.sort_by(|a, b| b.top.total_cmp(&a.top));
+        let mut ceiling = f32::INFINITY;
+        let mut rest = spans.as_slice();
+        while let Some(highest) = rest.first() {
+            let y = highest.top;
+            let (level, below) = rest.split_at(rest.iter().take_while(|span| span.top >= y - EPSILON).count());
+            rest = below;
+            let headroom = ceiling - y;
+            ceiling = level.iter().map(|span| span.bottom).fold(ceiling, f32::min);
+            if headroom < CHARACTER_HEIGHT - EPSILON {
+                continue;
+            }
+
+            let mut footing = Footing {
+                column,
+                y,
+                headroom,
+                block: None,
+                spawn: false,
+                jump_velocity: (2.0 * GRAVITY * JUMP_HEIGHT).sqrt(),
+                walk_speed: WALK_SPEED,
+            };
+            let mut kill = false;
+            for span in level {
+                let Part::Block(index) = span.part else {
+                    footing.spawn |= span.part == Part::SpawnPad;
+                    continue;
+                };
+                footing.block = Some(footing.block.map_or(index, |block: usize| block.min(index)));
+                footing.spawn |= space_json.blocks[index].spawn.is_some();
+                match kinds[index] {
+                    Some(BlockKind::Kill) => kill = true,
+                    Some(BlockKind::Bounce) => footing.jump_velocity = BOUNCE_VELOCITY,
+                    Some(BlockKind::Speed) => footing.walk_speed = SPEED_BOOST,
+                    _ => {}
+                }
+            }
+            if !kill {
+                footings.push(footing);
+            }
+        }
+    }
+    Ok(footings)
+}
+
+/// Edge-to-edge distance between columns `di` and `dk` columns apart, in studs.
+fn column_gap(di: i32, dk: i32) -> f32 {
+    let (gap_i, gap_k) = ((di.abs() - 1).max(0), (dk.abs() - 1).max(0));
+    COLUMN_SIZE * ((gap_i * gap_i + gap_k * gap_k) as f32).sqrt()
+}
+
+/// Returns the cell of [`CELL_COLUMNS`] by [`CELL_COLUMNS`] columns a column is in.
+fn cell_of(column: (i32, i32)) -> (i32, i32) {
+    (column.0.div_euclid(CELL_COLUMNS), column.1.div_euclid(CELL_COLUMNS))
+}
+
+/// Returns how many columns `index` is from the nearest column of a cell along one axis.
+fn columns_to_cell(index: i32, cell: i32) -> i32 {
+    let (first, last) = (cell * CELL_COLUMNS, cell * CELL_COLUMNS + CELL_COLUMNS - 1);
+    (first - index).max(index - last).max(0)
+}
+
+/// Returns which footings a character can get to from a spawn.
+fn reachable_footings(footings: &[Footing]) -> Vec<bool> {
+    let mut reachable: Vec<bool> = footings.iter().map(|footing| footing.spawn).collect();
+    // Footings not reached yet, lowest first, so a jump's candidates in a cell are a prefix
+    let mut cells: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+    for (index, footing) in footings.iter().enumerate().filter(|&(index, _)| !reachable[index]) {
+        cells.entry(cell_of(footing.column)).or_default().push(index);
+    }
+    for cell in cells.values_mut() {
+        cell.sort_by(|&a, &b| footings[a].y.total_cmp(&footings[b].y));
+    }
+    let mut by_height: Vec<usize> = (0..footings.len()).collect();
+    by_height.sort_by(|&a, &b| footings[a].y.total_cmp(&footings[b].y));
+    let mut lowest = 0;
+
+    let mut queue: VecDeque<usize> = (0..footings.len()).filter(|&index| reachable[index]).collect();
+    while let Some(index) = queue.pop_front() {
+        while lowest < by_height.len() && reachable[by_height[lowest]] {
+            lowest += 1;
+        }
+        let Some(&lowest_unreached) = by_height.get(lowest) else {
+            break;
+        };
+        let from = &footings[index];
+        // Falling to the lowest surface left gives a jump its longest reach
+        let radius = (from.reach(footings[lowest_unreached].y - from.y) / COLUMN_SIZE).floor() as i32 + 1;
+        let (first_i, first_k) = cell_of((from.column.0 - radius, from.column.1 - radius));
+        let (last_i, last_k) = cell_of((from.column.0 + radius, from.column.1 + radius));
+        for cell_i in first_i..=last_i {
+            for cell_k in first_k..=last_k {
+                let Some(cell) = cells.get_mut(&(cell_i, cell_k)) else {
+                    continue;
+                };
+                // Surfaces higher than a jump to the cell's nearest column can land are out of reach
+                let cell_gap = column_gap(
+                    columns_to_cell(from.column.0, cell_i),
+                    columns_to_cell(from.column.1, cell_k),
+                );
+                let highest = from.y + from.max_rise_across(cell_gap) + EPSILON;
+                let candidates = cell.partition_point(|&target| footings[target].y <= highest);
+
+                let mut kept = 0;
+                for position in 0..candidates {
+                    let target = cell[position];
+                    let to = &footings[target];
+                    let gap = column_gap(to.column.0 - from.column.0, to.column.1 - from.column.1);
+                    if to.column != from.column && gap <= from.reach(to.y - from.y) + EPSILON {
+                        reachable[target] = true;
+                        queue.push_back(target);
+                    } else {
+                        cell[kept] = target;
+                        kept += 1;
+                    }
+                }
+                cell.drain(kept..candidates);
+                if cell.is_empty() {
+                    cells.remove(&(cell_i, cell_k));
+                }
+            }
+        }
+    }
+    reachable
+}
+
+/// Analyzes how far players get into a level from its spawns.
+///
+/// The level is analyzed as a place export generates it, with its spawn pads
+/// (or spawn blocks) and baseplate. A checkpoint or finish block counts as
+/// reached when a character standing on, or jumping from, a reachable surface
+/// would touch it. A spawn buried in blocks leaves the whole level unreachable.
+///
+/// Fails without analyzing anything if the level covers more than [`MAX_COLUMNS`] columns.
+pub fn analyze_playability(space_json: &SpaceJSON) -> Result<PlayabilityReport, PlayabilityError> {
+    let footings = footings(space_json)?;
+    let reachable = reachable_footings(&footings);
+
+    let mut by_column: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+    for (index, footing) in footings.iter().enumerate() {
+        if reachable[index] {
+            by_column.entry(footing.column).or_default().push(index);
+        }
+    }
+    let touched = |goal: &PartBox| {
+        let (first_i, last_i) = column_range(goal.0[0], goal.1[0]).into_inner();
+        let (first_k, last_k) = column_range(goal.0[2], goal.1[2]).into_inner();
+        (first_i - 1..=last_i + 1).any(|i| {
+            (first_k - 1..=last_k + 1).any(|k| {
+                by_column
+                    .get(&(i, k))
+                    .is_some_and(|indices| indices.iter().any(|&index| boxes_touch(goal, &footings[index].touch_box())))
+            })
+        })
+    };
+
+    let mut unreachable_checkpoints = Vec::new();
+    let mut unreachable_finishes = Vec::new();
+    let mut has_finish = false;
+    for (index, block) in space_json.blocks.iter().enumerate() {
+        let unreachable = match block.kind.as_deref().and_then(BlockKind::from_name) {
+            Some(BlockKind::Checkpoint) => &mut unreachable_checkpoints,
+            Some(BlockKind::Finish) => {
+                has_finish = true;
+                &mut unreachable_finishes
+            }
+            _ => continue,
+        };
+        if !touched(&block_box(block)) {
+            unreachable.push(index);
+        }
+    }
+
+    let mut surfaces: Vec<Surface> = footings
+        .iter()
+        .zip(&reachable)
+        .filter_map(|(footing, &reachable)| {
+            Some(Surface {
+                block: footing.block?,
+                x: footing.column.0 * COLUMN_SIZE as i32,
+                z: footing.column.1 * COLUMN_SIZE as i32,
+                y: footing.y,
+                reachable,
+            })
+        })
+        .collect();
+    surfaces.sort_by(|a, b| (a.block, a.x, a.z).cmp(&(b.block, b.x, b.z)).then(a.y.total_cmp(&b.y)));
+
+    Ok(PlayabilityReport {
+        completable: has_finish && unreachable_checkpoints.is_empty() && unreachable_finishes.is_empty(),
+        surfaces,
+        unreachable_checkpoints,
+        unreachable_finishes,
+    })
+}
@@ -0,0 +1,228 @@
+//! Per-client rate limiting.
+//!
+//! Each client (an API key, token subject or IP address) gets two token
+//! buckets: one for requests and one for blocks exported. A bucket holds up
+//! to its per-window budget and refills continuously at that budget per
+//! window, so clients can burst up to the budget and then continue at the
+//! average rate.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+/// Number of tracked clients above which idle (full) buckets are dropped, at
+/// most once per window.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Budgets per client. A budget of 0 disables that limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// Requests allowed per window.
+    pub requests: u32,
+    /// Blocks allowed to be exported per window.
+    pub blocks: u32,
+    /// Window length in seconds.
+    pub window_seconds: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        RateLimitConfig {
+            requests: 120,
+            blocks: 100_000,
+            window_seconds: 60,
+        }
+    }
+}
+
+/// Rate limit error variants with associated context data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RateLimitError {
+    RequestLimitExceeded { retry_after_seconds: u64 },
+    BlockQuotaExceeded { blocks: usize, retry_after_seconds: u64 },
+}
+
+impl RateLimitError {
+    /// Returns a machine-readable error code for API responses.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            RateLimitError::RequestLimitExceeded { .. } => "RATE_LIMITED",
+            RateLimitError::BlockQuotaExceeded { .. } => "BLOCK_QUOTA_EXCEEDED",
+        }
+    }
+
+    /// Returns a human-readable error message for display to users.
+    pub fn message(&self) -> String {
+        match self {
+            RateLimitError::RequestLimitExceeded { retry_after_seconds } => format!(
+                "Too many requests. Try again in {} second(s).",
+                retry_after_seconds
+            ),
+            RateLimitError::BlockQuotaExceeded {
+                blocks,
+                retry_after_seconds,
+            } => format!(
+                "Exporting {} more block(s) would exceed your block quota. Try again in {} second(s).",
+                blocks, retry_after_seconds
+            ),
+        }
+    }
+
+    /// Seconds the client should wait before retrying (the `Retry-After` value).
+    pub fn retry_after_seconds(&self) -> u64 {
+        match self {
+            RateLimitError::RequestLimitExceeded { retry_after_seconds }
+            | RateLimitError::BlockQuotaExceeded {
+                retry_after_seconds, ..
+            } => *retry_after_seconds,
+        }
+    }
+}
+
+/// A token bucket holding up to `capacity` tokens.
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: f64,
+    /// Unix milliseconds of the last refill.
+    updated_at: u64,
+}
+
+impl TokenBucket {
+    fn full(capacity: u32, now: u64) -> Self {
+        TokenBucket {
+            tokens: f64::from(capacity),
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, capacity: u32, window_ms: f64, now: u64) {
+        let elapsed = now.saturating_sub(self.updated_at) as f64;
+        let capacity = f64::from(capacity);
+        self.tokens = (self.tokens + elapsed * capacity / window_ms).min(capacity);
+        self.updated_at = self.updated_at.max(now);
+    }
+
+    /// Milliseconds until `amount` tokens are available, or `None` if they are now.
+    fn wait_for(&self, amount: f64, capacity: u32, window_ms: f64) -> Option<u64> {
+        if self.tokens >= amount {
+            return None;
+        }
+        let per_ms = f64::from(capacity) / window_ms;
+        Some(((amount - self.tokens) / per_ms).ceil() as u64)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ClientBuckets {
+    requests: TokenBucket,
+    blocks: TokenBucket,
+}
+
+/// Returns the client address from an `X-Forwarded-For` header value.
+///
+/// Proxies append the address they received the request from, so only the
+/// rightmost entry was added by the trusted proxy; entries to its left come
+/// from the client and may be spoofed.
+pub fn forwarded_client_address(header: &str) -> Option<&str> {
+    header
+        .rsplit(',')
+        .next()
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+}
+
+/// Every tracked client's buckets.
+#[derive(Debug, Default)]
+struct Clients {
+    buckets: HashMap<String, ClientBuckets>,
+    /// Unix milliseconds of the last prune.
+    pruned_at: u64,
+}
+
+/// Tracks request and block budgets for every client.
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    clients: Mutex<Clients>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        RateLimiter {
+            config,
+            clients: Mutex::new(Clients::default()),
+        }
+    }
+
+    pub fn config(&self) -> RateLimitConfig {
+        self.config
+    }
+
+    /// Takes one request from `client`'s budget at time `now` (Unix milliseconds).
+    pub fn check_request(&self, client: &str, now: u64) -> Result<(), RateLimitError> {
+        let capacity = self.config.requests;
+        self.take(client, now, capacity, 1, |buckets| &mut buckets.requests)
+            .map_err(|retry_after_seconds| RateLimitError::RequestLimitExceeded { retry_after_seconds })
+    }
+
+    /// Takes `blocks` exported blocks from `client`'s budget at time `now`.
+    ///
+    /// Exports larger than the whole budget are allowed once the bucket is
+    /// full, so the largest valid level can always be exported eventually.
+    pub fn check_blocks(&self, client: &str, blocks: usize, now: u64) -> Result<(), RateLimitError> {
+        let capacity = self.config.blocks;
+        let amount = blocks.min(capacity as usize) as u32;
+        self.take(client, now, capacity, amount, |buckets| &mut buckets.blocks)
+            .map_err(|retry_after_seconds| RateLimitError::BlockQuotaExceeded {
+                blocks,
+                retry_after_seconds,
+            })
+    }
+
+    /// Takes `amount` tokens from one of a client's buckets, returning the
+    /// seconds to wait on failure.
+    fn take(
+        &self,
+        client: &str,
+        now: u64,
+        capacity: u32,
+        amount: u32,
+        bucket: impl FnOnce(&mut ClientBuckets) -> &mut TokenBucket,
+    ) -> Result<(), u64> {
+        if capacity == 0 || amount == 0 {
+            return Ok(());
+        }
+        let window_ms = f64::from(self.config.window_seconds.max(1)) * 1000.0;
+        let mut clients = self.clients.lock().unwrap_or_else(PoisonError::into_inner);
+        // Pruning scans every client, so it runs at most once per window
+        if clients.buckets.len() >= PRUNE_THRESHOLD && now.saturating_sub(clients.pruned_at) as f64 >= window_ms {
+            self.prune(&mut clients.buckets, now, window_ms);
+            clients.pruned_at = now;
+        }
+
+        let buckets = clients.buckets.entry(client.to_string()).or_insert_with(|| ClientBuckets {
+            requests: TokenBucket::full(self.config.requests, now),
+            blocks: TokenBucket::full(self.config.blocks, now),
+        });
+        let bucket = bucket(buckets);
+        bucket.refill(capacity, window_ms, now);
+        match bucket.wait_for(f64::from(amount), capacity, window_ms) {
+            None => {
+                bucket.tokens -= f64::from(amount);
+                Ok(())
+            }
+            Some(wait_ms) => Err(wait_ms.div_ceil(1000).max(1)),
+        }
+    }
+
+    /// Forgets clients whose buckets have refilled completely; they would be
+    /// recreated full anyway.
+    fn prune(&self, clients: &mut HashMap<String, ClientBuckets>, now: u64, window_ms: f64) {
+        let RateLimitConfig { requests, blocks, .. } = self.config;
+        clients.retain(|_, buckets| {
+            buckets.requests.refill(requests, window_ms, now);
+            buckets.blocks.refill(blocks, window_ms, now);
+            buckets.requests.tokens < f64::from(requests) || buckets.blocks.tokens < f64::from(blocks)
+        });
+    }
+}
@@ -2,10 +2,21 @@
 //!
 //! Converts Space JSON to valid `.rbxlx` files using `rbx-dom-weak` and `rbx-xml`.
 //! Generates a complete Roblox DataModel with Workspace, services, and level geometry.
+//! The same DataModel can also be written as a binary `.rbxl` file with `rbx-binary`,
+//! or the level geometry alone can be exported as a reusable `.rbxmx`/`.rbxm` model.
 
-use crate::models::{Block, SpaceJSON};
-use rbx_dom_weak::{InstanceBuilder, WeakDom};
-use rbx_types::{CFrame, Color3, Vector3, Vector2, Matrix3, Content};
+use crate::gameplay::{BlockKind, GAMEPLAY_SCRIPT_NAME, GAMEPLAY_SCRIPT_SOURCE};
+use crate::materials::material_value;
+use crate::geometry::baseplate_size;
+use crate::meshing::{greedy_mesh, MergedBox};
+use crate::brick_colors::resolve_team_colors;
+use crate::lighting::{technology_value, DEFAULT_TECHNOLOGY};
+use crate::models::{BaseplateSettings, Block, LightingSettings, SpaceJSON};
+use crate::spawn::{blocks_obstructing_spawns, clear_spawns, SpawnClearance};
+use crate::validation::{collect_warnings, ValidationLimits, ValidationWarning};
+use rbx_dom_weak::{ustr, InstanceBuilder, WeakDom};
+use rbx_types::{BrickColor, CFrame, Color3, Vector3, Vector2, Matrix3, Content, ContentId, Enum, Ref, Tags, Variant};
+use std::collections::HashMap;
 use std::io::Cursor;
 
 /// Converts a hex color string to Roblox `Color3` (RGB values in 0.0-1.0 range).
@@ -51,9 +62,7 @@ pub fn hex_to_color3(hex: &str) -> Result<Color3, String> {
 ///
 /// Returns `(0.0, 1.0, 0.0)` if no blocks exist.
 ///
-/// NOTE: Currently unused. SpawnLocation uses fixed position `(0, 0.5, 0)`.
-/// Preserved for future use where dynamic spawn positioning may be needed.
-#[allow(dead_code)]
+/// Used for the SpawnLocation when the level's world settings set `spawn.auto`.
 pub fn calculate_spawn_position(blocks: &[Block]) -> (f32, f32, f32) {
     if blocks.is_empty() {
         return (0.0, 1.0, 0.0);
@@ -77,11 +86,176 @@ pub fn calculate_spawn_position(blocks: &[Block]) -> (f32, f32, f32) {
     (center_x, spawn_y, center_z)
 }
 
+/// Options controlling how a level is exported.
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Merge contiguous same-color blocks into larger Parts (see [`greedy_mesh`]).
+    pub merge_blocks: bool,
+    /// How place exports handle blocks inside a SpawnLocation (see [`clear_spawns`]).
+    pub spawn_clearance: SpawnClearance,
+}
+
+/// Collects the warnings for a level exported with `options`.
+///
+/// Adds [`ValidationWarning::SpawnOverlap`] to the [`collect_warnings`] when
+/// the export keeps obstructed spawns as they are.
+pub fn export_warnings(
+    space_json: &SpaceJSON,
+    limits: &ValidationLimits,
+    options: &ExportOptions,
+) -> Vec<ValidationWarning> {
+    let mut warnings = collect_warnings(space_json, limits);
+    if options.spawn_clearance == SpawnClearance::Keep {
+        let overlapping = blocks_obstructing_spawns(space_json);
+        if !overlapping.is_empty() {
+            warnings.push(ValidationWarning::SpawnOverlap { blocks: overlapping });
+        }
+    }
+    warnings
+}
+
+/// File format a level is written in.
+///
+/// Place formats contain a complete DataModel; model formats contain only a
+/// single `Model` wrapping the level's block Parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Roblox XML place format (`.rbxlx`).
+    #[default]
+    Xml,
+    /// Roblox binary place format (`.rbxl`), several times smaller than XML.
+    Binary,
+    /// Roblox XML model format (`.rbxmx`).
+    ModelXml,
+    /// Roblox binary model format (`.rbxm`).
+    ModelBinary,
+}
+
+impl OutputFormat {
+    /// Parses a format name as accepted by the export API (`rbxlx`, `rbxl`, `rbxmx` or `rbxm`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "rbxlx" | "xml" => Some(OutputFormat::Xml),
+            "rbxl" | "binary" => Some(OutputFormat::Binary),
+            "rbxmx" => Some(OutputFormat::ModelXml),
+            "rbxm" => Some(OutputFormat::ModelBinary),
+            _ => None,
+        }
+    }
+
+    /// Returns the file extension (without a leading dot).
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Xml => "rbxlx",
+            OutputFormat::Binary => "rbxl",
+            OutputFormat::ModelXml => "rbxmx",
+            OutputFormat::ModelBinary => "rbxm",
+        }
+    }
+
+    /// Returns the MIME type used when serving the file.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            OutputFormat::Xml | OutputFormat::ModelXml => "application/xml",
+            OutputFormat::Binary | OutputFormat::ModelBinary => "application/octet-stream",
+        }
+    }
+
+    /// Returns `true` for the model formats (`.rbxmx`/`.rbxm`).
+    pub fn is_model(&self) -> bool {
+        matches!(self, OutputFormat::ModelXml | OutputFormat::ModelBinary)
+    }
+
+    /// Returns `true` for the binary formats (`.rbxl`/`.rbxm`).
+    pub fn is_binary(&self) -> bool {
+        matches!(self, OutputFormat::Binary | OutputFormat::ModelBinary)
+    }
+}
+
+/// BrickColor number of each team, keyed by team name.
+type TeamColors = HashMap<String, u16>;
+
+/// Resolves every team's BrickColor (see [`resolve_team_colors`]).
+fn team_colors(space_json: &SpaceJSON) -> TeamColors {
+    let colors: Vec<Option<&str>> = space_json.teams.iter().map(|team| team.color.as_deref()).collect();
+    space_json
+        .teams
+        .iter()
+        .zip(resolve_team_colors(&colors))
+        .filter_map(|(team, color)| Some((team.name.clone(), color?)))
+        .collect()
+}
+
+/// Sets the SpawnLocation properties shared by every spawn.
+///
+/// Spawns without a team (or with a team that has no color) are neutral, so
+/// any player can spawn there.
+fn with_spawn_properties(spawn: InstanceBuilder, team: Option<&str>, team_colors: &TeamColors) -> InstanceBuilder {
+    let team_color = team
+        .and_then(|team| team_colors.get(team))
+        .and_then(|&number| BrickColor::from_number(number));
+
+    let spawn = spawn
+        .with_property("Neutral", team_color.is_none())
+        .with_property("Duration", 0i32)
+        .with_property("Enabled", true)
+        .with_property("AllowTeamChangeOnTouch", false);
+    match team_color {
+        Some(color) => spawn.with_property("TeamColor", color),
+        None => spawn,
+    }
+}
+
 /// Creates a Roblox Part instance from a Block.
 ///
 /// Block coordinates are pre-scaled (2x) by the frontend. Each block becomes
-/// a 2x2x2 stud Part in Roblox.
-fn create_part_from_block(block: &Block, referent_id: usize) -> InstanceBuilder {
+/// a 2x2x2 stud Part in Roblox, unless the block specifies its own `size`.
+fn create_part_from_block(block: &Block, referent_id: usize, team_colors: &TeamColors) -> InstanceBuilder {
+    let position = Vector3::new(block.x as f32, block.y as f32, block.z as f32);
+    let size = match block.size {
+        Some(size) => Vector3::new(size.x, size.y, size.z),
+        None => Vector3::new(2.0, 2.0, 2.0),
+    };
+    create_part(block, position, size, referent_id, team_colors)
+}
+
+/// Creates a single Roblox Part covering a merged box of blocks.
+///
+/// The Part takes its appearance from the box's representative block.
+/// Single-block boxes keep the block's own size.
+fn create_part_from_box(
+    merged: &MergedBox,
+    blocks: &[Block],
+    referent_id: usize,
+    team_colors: &TeamColors,
+) -> InstanceBuilder {
+    if merged.extent == (1, 1, 1) {
+        return create_part_from_block(&blocks[merged.source], referent_id, team_colors);
+    }
+
+    let (cx, cy, cz) = merged.center();
+    let (sx, sy, sz) = merged.size();
+    create_part(
+        &blocks[merged.source],
+        Vector3::new(cx, cy, cz),
+        Vector3::new(sx, sy, sz),
+        referent_id,
+        team_colors,
+    )
+}
+
+/// Creates an anchored Part with the block's appearance at the given position and size.
+///
+/// Optional material, transparency and reflectance are only written when set,
+/// so version 1 blocks keep Roblox's defaults. Spawn blocks become SpawnLocations,
+/// and gameplay blocks are tagged for the generated gameplay Script.
+fn create_part(
+    block: &Block,
+    position: Vector3,
+    size: Vector3,
+    referent_id: usize,
+    team_colors: &TeamColors,
+) -> InstanceBuilder {
     let color = hex_to_color3(&block.color).unwrap_or_else(|e| {
         eprintln!(
             "Warning: Failed to parse color '{}' for block {}: {}. Using default gray.",
@@ -90,68 +264,289 @@ fn create_part_from_block(block: &Block, referent_id: usize) -> InstanceBuilder
         Color3::new(0.5, 0.5, 0.5)
     });
 
-    let position = Vector3::new(block.x as f32, block.y as f32, block.z as f32);
     let cframe = CFrame::new(position, Matrix3::identity());
 
-    InstanceBuilder::new("Part")
-        .with_property("Name", format!("Block{}", referent_id))
+    let class = if block.spawn.is_some() { "SpawnLocation" } else { "Part" };
+    let mut part = InstanceBuilder::new(class)
+        .with_name(format!("Block{}", referent_id))
         .with_property("CFrame", cframe)
-        .with_property("Size", Vector3::new(2.0, 2.0, 2.0))
+        .with_property("Size", size)
         .with_property("Color", color)
-        .with_property("Anchored", true)
+        .with_property("Anchored", true);
+
+    if let Some(material) = block.material.as_deref().and_then(material_value) {
+        part = part.with_property("Material", Enum::from_u32(material));
+    }
+    if let Some(transparency) = block.transparency {
+        part = part.with_property("Transparency", transparency);
+    }
+    if let Some(reflectance) = block.reflectance {
+        part = part.with_property("Reflectance", reflectance);
+    }
+    if let Some(spawn) = &block.spawn {
+        part = with_spawn_properties(part, spawn.team.as_deref(), team_colors);
+    }
+    if let Some(kind) = block.kind.as_deref().and_then(BlockKind::from_name) {
+        part = part.with_property("Tags", Tags::from(vec![kind.tag().to_string()]));
+    }
+
+    part
+}
+
+/// Creates the server Script implementing gameplay blocks, or `None` if the level has none.
+fn create_gameplay_script(space_json: &SpaceJSON) -> Option<InstanceBuilder> {
+    if !space_json.blocks.iter().any(|block| block.kind.is_some()) {
+        return None;
+    }
+    Some(
+        InstanceBuilder::new("Script")
+            .with_name(GAMEPLAY_SCRIPT_NAME)
+            .with_property("Source", GAMEPLAY_SCRIPT_SOURCE),
+    )
 }
 
-/// Generates `.rbxlx` file content from Space JSON.
+/// Generates `.rbxlx` file content from Space JSON using default [`ExportOptions`].
 ///
 /// Creates a complete Roblox DataModel with:
-/// - Workspace containing Terrain, Baseplate, user blocks, and SpawnLocation
-/// - Required services (Players, Lighting, ReplicatedStorage, etc.)
+/// - Workspace containing Terrain, Baseplate, user blocks, and SpawnLocations
+/// - Required services (Players, Lighting, ReplicatedStorage, etc.), plus Teams
+///   when the level defines teams
+/// - A gameplay Script in ServerScriptService when the level has gameplay blocks
 pub fn generate_rbxlx(space_json: &SpaceJSON) -> Result<Vec<u8>, String> {
+    generate_rbxlx_with_options(space_json, &ExportOptions::default())
+}
+
+/// Generates `.rbxlx` file content from Space JSON with the given export options.
+///
+/// With [`ExportOptions::merge_blocks`] set, each merged box becomes one Part
+/// sized to cover all of its blocks; otherwise every block becomes its own Part.
+pub fn generate_rbxlx_with_options(
+    space_json: &SpaceJSON,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, String> {
+    generate_export(space_json, options, OutputFormat::Xml)
+}
+
+/// Generates place or model file content from Space JSON in the requested format.
+pub fn generate_export(
+    space_json: &SpaceJSON,
+    options: &ExportOptions,
+    format: OutputFormat,
+) -> Result<Vec<u8>, String> {
+    let dom = if format.is_model() {
+        build_model_dom(space_json, options)
+    } else {
+        build_place_dom(space_json, options)
+    };
+    write_dom(&dom, format)
+}
+
+/// Inserts the level's blocks as Parts under `parent`, returning their referents.
+fn insert_block_parts(
+    dom: &mut WeakDom,
+    parent: Ref,
+    space_json: &SpaceJSON,
+    options: &ExportOptions,
+    team_colors: &TeamColors,
+) -> Vec<Ref> {
+    if options.merge_blocks {
+        greedy_mesh(&space_json.blocks)
+            .iter()
+            .enumerate()
+            .map(|(index, merged)| {
+                dom.insert(parent, create_part_from_box(merged, &space_json.blocks, index, team_colors))
+            })
+            .collect()
+    } else {
+        space_json
+            .blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| dom.insert(parent, create_part_from_block(block, index, team_colors)))
+            .collect()
+    }
+}
+
+/// Builds a DOM whose only top-level instance is a `Model` holding the level's Parts.
+///
+/// No Baseplate, SpawnLocation or services are added, so the model can be
+/// inserted into an existing game. Gameplay blocks bring their Script along
+/// inside the model. The first Part becomes the `PrimaryPart`,
+/// and the model pivots around the center of the Parts' bounding box.
+pub fn build_model_dom(space_json: &SpaceJSON, options: &ExportOptions) -> WeakDom {
     let mut dom = WeakDom::new(InstanceBuilder::new("DataModel"));
-    let root_ref = dom.root_ref();
+    let model_name = space_json.name.as_deref().unwrap_or("Level");
+    let model_ref = dom.insert(
+        dom.root_ref(),
+        InstanceBuilder::new("Model").with_name(model_name),
+    );
 
-    // Create Workspace and Terrain
-    let workspace_ref = dom.insert(root_ref, InstanceBuilder::new("Workspace"));
-    dom.insert(workspace_ref, InstanceBuilder::new("Terrain"));
+    let part_refs = insert_block_parts(&mut dom, model_ref, space_json, options, &team_colors(space_json));
+    if let Some(script) = create_gameplay_script(space_json) {
+        dom.insert(model_ref, script);
+    }
+    let pivot = parts_bounding_center(&dom, &part_refs).unwrap_or(Vector3::new(0.0, 0.0, 0.0));
 
-    // Add Baseplate (200x16x200 studs, top surface at Y=0)
-    let baseplate_color = Color3::new(0.5, 0.5, 0.5);
-    let baseplate_position = Vector3::new(0.0, -8.0, 0.0);
-    let baseplate_cframe = CFrame::new(baseplate_position, Matrix3::identity());
-    dom.insert(
-        workspace_ref,
+    if let Some(model) = dom.get_by_ref_mut(model_ref) {
+        if let Some(&primary_part) = part_refs.first() {
+            model.properties.insert(ustr("PrimaryPart"), Variant::Ref(primary_part));
+        }
+        model.properties.insert(
+            ustr("WorldPivotData"),
+            Variant::OptionalCFrame(Some(CFrame::new(pivot, Matrix3::identity()))),
+        );
+    }
+
+    dom
+}
+
+/// Returns the center of the axis-aligned box enclosing the given Parts.
+fn parts_bounding_center(dom: &WeakDom, part_refs: &[Ref]) -> Option<Vector3> {
+    let mut bounds: Option<(Vector3, Vector3)> = None;
+    for part in part_refs.iter().filter_map(|&r| dom.get_by_ref(r)) {
+        let (Some(Variant::CFrame(cframe)), Some(Variant::Vector3(size))) = (
+            part.properties.get(&ustr("CFrame")),
+            part.properties.get(&ustr("Size")),
+        ) else {
+            continue;
+        };
+        let p = cframe.position;
+        let low = Vector3::new(p.x - size.x / 2.0, p.y - size.y / 2.0, p.z - size.z / 2.0);
+        let high = Vector3::new(p.x + size.x / 2.0, p.y + size.y / 2.0, p.z + size.z / 2.0);
+        bounds = Some(match bounds {
+            None => (low, high),
+            Some((min, max)) => (
+                Vector3::new(min.x.min(low.x), min.y.min(low.y), min.z.min(low.z)),
+                Vector3::new(max.x.max(high.x), max.y.max(high.y), max.z.max(high.z)),
+            ),
+        });
+    }
+
+    bounds.map(|(min, max)| {
+        Vector3::new((min.x + max.x) / 2.0, (min.y + max.y) / 2.0, (min.z + max.z) / 2.0)
+    })
+}
+
+/// `Enum.Material.Plastic`, the default baseplate material.
+const DEFAULT_BASEPLATE_MATERIAL: u32 = 256;
+
+/// Height of the SpawnLocation Part in studs.
+const SPAWN_HEIGHT: f32 = 1.0;
+
+/// Size of a generated SpawnLocation pad in studs.
+pub const SPAWN_PAD_SIZE: (f32, f32, f32) = (12.0, SPAWN_HEIGHT, 12.0);
+
+/// Creates the Baseplate Part, or `None` if the world settings disable it.
+///
+/// The baseplate's top surface stays at Y=0 whatever its size.
+fn create_baseplate(settings: Option<&BaseplateSettings>) -> Option<InstanceBuilder> {
+    let (x, y, z) = baseplate_size(settings)?;
+    let size = Vector3::new(x, y, z);
+    let settings = settings.cloned().unwrap_or_default();
+    let color = settings
+        .color
+        .as_deref()
+        .and_then(|color| hex_to_color3(color).ok())
+        .unwrap_or(Color3::new(0.5, 0.5, 0.5));
+    let material = settings
+        .material
+        .as_deref()
+        .and_then(material_value)
+        .unwrap_or(DEFAULT_BASEPLATE_MATERIAL);
+    let cframe = CFrame::new(Vector3::new(0.0, -size.y / 2.0, 0.0), Matrix3::identity());
+
+    Some(
         InstanceBuilder::new("Part")
-            .with_property("Name", "Baseplate")
-            .with_property("CFrame", baseplate_cframe)
-            .with_property("Size", Vector3::new(200.0, 16.0, 200.0))
-            .with_property("Color", baseplate_color)
+            .with_name("Baseplate")
+            .with_property("CFrame", cframe)
+            .with_property("Size", size)
+            .with_property("Color", color)
             .with_property("Anchored", true)
             .with_property("Locked", true)
-            .with_property("Material", 256i32), // Plastic
-    );
+            .with_property("Material", Enum::from_u32(material)),
+    )
+}
 
-    // Add user-placed blocks as Parts
-    for (index, block) in space_json.blocks.iter().enumerate() {
-        let part = create_part_from_block(block, index);
-        dom.insert(workspace_ref, part);
-    }
-
-    // Add SpawnLocation (fixed at origin, 12x1x12 studs)
-    let spawn_position = Vector3::new(0.0, 0.5, 0.0);
-    let spawn_cframe = CFrame::new(spawn_position, Matrix3::identity());
-    let spawn_location_ref = dom.insert(
-        workspace_ref,
-        InstanceBuilder::new("SpawnLocation")
-            .with_property("Name", "SpawnLocation")
-            .with_property("Anchored", true)
-            .with_property("CanCollide", true)
-            .with_property("Neutral", true)
-            .with_property("Duration", 0.0f32)
-            .with_property("Size", Vector3::new(12.0, 1.0, 12.0))
-            .with_property("CFrame", spawn_cframe)
-            .with_property("Enabled", true)
-            .with_property("AllowTeamChangeOnTouch", false),
-    );
+/// Returns the center of the SpawnLocation for a level.
+///
+/// Uses the explicit `world.spawn.position` if given, sits on top of the
+/// level's blocks (see [`calculate_spawn_position`]) with `world.spawn.auto`,
+/// and otherwise rests on the baseplate at the origin.
+fn spawn_position(space_json: &SpaceJSON) -> Vector3 {
+    let spawn = space_json.world.as_ref().and_then(|world| world.spawn.as_ref());
+    if let Some(position) = spawn.and_then(|spawn| spawn.position) {
+        return Vector3::new(position.x, position.y, position.z);
+    }
+    if spawn.is_some_and(|spawn| spawn.auto == Some(true)) {
+        let (x, top, z) = calculate_spawn_position(&space_json.blocks);
+        return Vector3::new(x, top + SPAWN_HEIGHT / 2.0, z);
+    }
+    Vector3::new(0.0, SPAWN_HEIGHT / 2.0, 0.0)
+}
+
+/// Returns the centers of the SpawnLocation pads a place export inserts besides
+/// spawn blocks: one per free-standing spawn point, or the default spawn if the
+/// level has no spawns of its own.
+pub fn spawn_pad_positions(space_json: &SpaceJSON) -> Vec<(f32, f32, f32)> {
+    let has_spawn_blocks = space_json.blocks.iter().any(|block| block.spawn.is_some());
+    if space_json.spawns.is_empty() && !has_spawn_blocks {
+        let position = spawn_position(space_json);
+        return vec![(position.x, position.y, position.z)];
+    }
+    space_json.spawns.iter().map(|spawn| (spawn.x, spawn.y, spawn.z)).collect()
+}
+
+/// Creates the Lighting service with the level's lighting settings applied.
+///
+/// Properties that are not set keep Roblox's defaults, except `Technology`,
+/// which defaults to ShadowMap.
+fn create_lighting(settings: Option<&LightingSettings>) -> InstanceBuilder {
+    let settings = settings.cloned().unwrap_or_default();
+    let technology = settings
+        .technology
+        .as_deref()
+        .and_then(technology_value)
+        .unwrap_or(DEFAULT_TECHNOLOGY);
+
+    let mut lighting = InstanceBuilder::new("Lighting").with_property("Technology", Enum::from_u32(technology));
+
+    if let Some(time_of_day) = settings.time_of_day {
+        lighting = lighting.with_property("ClockTime", time_of_day);
+    }
+    if let Some(ambient) = settings.ambient.as_deref().and_then(|color| hex_to_color3(color).ok()) {
+        lighting = lighting.with_property("Ambient", ambient);
+    }
+    if let Some(fog) = settings.fog {
+        if let Some(color) = fog.color.as_deref().and_then(|color| hex_to_color3(color).ok()) {
+            lighting = lighting.with_property("FogColor", color);
+        }
+        if let Some(start) = fog.start {
+            lighting = lighting.with_property("FogStart", start);
+        }
+        if let Some(end) = fog.end {
+            lighting = lighting.with_property("FogEnd", end);
+        }
+    }
+
+    lighting
+}
+
+/// Inserts a 12x1x12 stud SpawnLocation, with the standard spawn texture on its top face.
+fn insert_spawn_location(
+    dom: &mut WeakDom,
+    parent: Ref,
+    position: Vector3,
+    team: Option<&str>,
+    team_colors: &TeamColors,
+) {
+    let spawn_cframe = CFrame::new(position, Matrix3::identity());
+    let spawn = InstanceBuilder::new("SpawnLocation")
+        .with_name("SpawnLocation")
+        .with_property("Anchored", true)
+        .with_property("CanCollide", true)
+        .with_property("Size", Vector3::new(SPAWN_PAD_SIZE.0, SPAWN_PAD_SIZE.1, SPAWN_PAD_SIZE.2))
+        .with_property("CFrame", spawn_cframe);
+    let spawn_location_ref = dom.insert(parent, with_spawn_properties(spawn, team, team_colors));
 
     // Add Decal to SpawnLocation (texture on top face)
     let decal_color = Color3::new(1.0, 1.0, 1.0);
@@ -165,24 +560,71 @@ pub fn generate_rbxlx(space_json: &SpaceJSON) -> Result<Vec<u8>, String> {
             .with_property("NormalMap", null_content.clone())
             .with_property("RoughnessMap", null_content.clone())
             .with_property("Texture", spawn_texture_content)
-            .with_property("TexturePack", null_content)
+            .with_property("TexturePack", ContentId::new())
             .with_property("TexturePackMetadata", String::new())
             .with_property("Transparency", 0.0f32)
             .with_property("UVOffset", Vector2::new(0.0, 0.0))
             .with_property("UVScale", Vector2::new(1.0, 1.0))
             .with_property("ZIndex", 1i32)
-            .with_property("Face", 1i32) // Top face
-            .with_property("Name", "Decal"),
+            .with_property("Face", Enum::from_u32(1)) // Top face
+            .with_name("Decal"),
     );
+}
+
+/// Builds the Roblox DataModel for a level without serializing it.
+///
+/// Blocks inside a SpawnLocation are handled as `options.spawn_clearance` asks.
+/// The returned DOM can be written in any place [`OutputFormat`] with [`write_dom`].
+pub fn build_place_dom(space_json: &SpaceJSON, options: &ExportOptions) -> WeakDom {
+    let space_json = &*clear_spawns(space_json, options.spawn_clearance);
+    let mut dom = WeakDom::new(InstanceBuilder::new("DataModel"));
+    let root_ref = dom.root_ref();
+
+    // Create Workspace and Terrain
+    let workspace_ref = dom.insert(root_ref, InstanceBuilder::new("Workspace"));
+    dom.insert(workspace_ref, InstanceBuilder::new("Terrain"));
+
+    // Add Baseplate (200x16x200 studs by default, top surface at Y=0)
+    let baseplate_settings = space_json.world.as_ref().and_then(|world| world.baseplate.as_ref());
+    if let Some(baseplate) = create_baseplate(baseplate_settings) {
+        dom.insert(workspace_ref, baseplate);
+    }
+
+    // Add user-placed blocks as Parts
+    let team_colors = team_colors(space_json);
+    insert_block_parts(&mut dom, workspace_ref, space_json, options, &team_colors);
+
+    // Add free-standing spawn points, or the default SpawnLocation if the level has no spawns
+    let has_spawn_blocks = space_json.blocks.iter().any(|block| block.spawn.is_some());
+    for spawn in &space_json.spawns {
+        let position = Vector3::new(spawn.x, spawn.y, spawn.z);
+        insert_spawn_location(&mut dom, workspace_ref, position, spawn.team.as_deref(), &team_colors);
+    }
+    if space_json.spawns.is_empty() && !has_spawn_blocks {
+        insert_spawn_location(&mut dom, workspace_ref, spawn_position(space_json), None, &team_colors);
+    }
 
     // Add required Roblox services
     dom.insert(root_ref, InstanceBuilder::new("Players"));
-    dom.insert(
-        root_ref,
-        InstanceBuilder::new("Lighting")
-            .with_property("Technology", 3i32), // ShadowMap
-    );
+    if !space_json.teams.is_empty() {
+        let teams_ref = dom.insert(root_ref, InstanceBuilder::new("Teams"));
+        for team in &space_json.teams {
+            let mut team_builder = InstanceBuilder::new("Team")
+                .with_name(team.name.as_str())
+                .with_property("AutoAssignable", true);
+            if let Some(color) = team_colors.get(&team.name).and_then(|&number| BrickColor::from_number(number)) {
+                team_builder = team_builder.with_property("TeamColor", color);
+            }
+            dom.insert(teams_ref, team_builder);
+        }
+    }
+    let lighting_settings = space_json.world.as_ref().and_then(|world| world.lighting.as_ref());
+    dom.insert(root_ref, create_lighting(lighting_settings));
     dom.insert(root_ref, InstanceBuilder::new("ReplicatedStorage"));
+    if let Some(script) = create_gameplay_script(space_json) {
+        let server_scripts_ref = dom.insert(root_ref, InstanceBuilder::new("ServerScriptService"));
+        dom.insert(server_scripts_ref, script);
+    }
     dom.insert(root_ref, InstanceBuilder::new("StarterGui"));
     dom.insert(root_ref, InstanceBuilder::new("StarterPack"));
 
@@ -190,17 +632,23 @@ pub fn generate_rbxlx(space_json: &SpaceJSON) -> Result<Vec<u8>, String> {
     dom.insert(starter_player_ref, InstanceBuilder::new("StarterPlayerScripts"));
     dom.insert(starter_player_ref, InstanceBuilder::new("StarterCharacterScripts"));
 
-    // Serialize to XML (services as direct children of <roblox>, not wrapped in DataModel)
+    dom
+}
+
+/// Serializes a DOM built by [`build_place_dom`] or [`build_model_dom`] in the given format.
+///
+/// The root's children are written as top-level instances: the services of a
+/// place (direct children of `<roblox>` in XML, not wrapped in a DataModel), or
+/// the single `Model` of a model export.
+pub fn write_dom(dom: &WeakDom, format: OutputFormat) -> Result<Vec<u8>, String> {
     let top_level_refs: Vec<_> = dom.root().children().to_vec();
     let mut output = Vec::new();
-    rbx_xml::to_writer_default(Cursor::new(&mut output), &dom, &top_level_refs)
-        .map_err(|e| format!("Failed to serialize to XML: {}", e))?;
-
-    // Fix Lighting Technology property (rbx_xml outputs <int>, Roblox expects <token>)
-    let xml_string = String::from_utf8(output)
-        .map_err(|e| format!("Failed to convert XML to string: {}", e))?;
-    let result = xml_string
-        .replace(r#"<int name="Technology">3</int>"#, r#"<token name="Technology">3</token>"#);
-
-    Ok(result.into_bytes())
+    if format.is_binary() {
+        rbx_binary::to_writer(Cursor::new(&mut output), dom, &top_level_refs)
+            .map_err(|e| format!("Failed to serialize to binary: {}", e))?;
+    } else {
+        rbx_xml::to_writer_default(Cursor::new(&mut output), dom, &top_level_refs)
+            .map_err(|e| format!("Failed to serialize to XML: {}", e))?;
+    }
+    Ok(output)
 }
@@ -0,0 +1,234 @@
+//! SpawnLocation clearance.
+//!
+//! A generated SpawnLocation needs its 12x1x12 pad and the space above it for
+//! players to stand in. Blocks inside that volume trap players when they
+//! spawn, so place exports can either move the spawn to the nearest clear spot
+//! or remove the blocks in the way (see [`SpawnClearance`]).
+
+use crate::geometry::{block_box, boxes_overlap, PartBox};
+use crate::models::{SpaceJSON, SpawnSettings, WorldPosition};
+use crate::rbxlx::{spawn_pad_positions, SPAWN_PAD_SIZE};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Headroom kept clear above a SpawnLocation pad for a standing player, in studs.
+pub const PLAYER_CLEARANCE: f32 = 6.0;
+
+/// Spacing of candidate spawn positions, matching the 2-stud block grid.
+const SEARCH_STEP: f32 = 2.0;
+
+/// Farthest a spawn is moved sideways looking for a clear spot, in grid steps.
+const MAX_SEARCH_STEPS: i32 = 64;
+
+/// Side of the square (x, z) cells blocks are indexed by, in studs: one spawn
+/// pad, so a column overlaps at most a few cells.
+const COLUMN_CELL_SIZE: f32 = SPAWN_PAD_SIZE.0;
+
+/// Blocks covering more cells than this are checked in every column instead
+/// of being indexed.
+const MAX_INDEXED_CELLS: i64 = 64;
+
+/// What a place export does about blocks inside a SpawnLocation's volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpawnClearance {
+    /// Export the level as is.
+    #[default]
+    Keep,
+    /// Move each obstructed SpawnLocation to the nearest clear position.
+    Relocate,
+    /// Remove the blocks intersecting any SpawnLocation's volume.
+    Clear,
+}
+
+impl SpawnClearance {
+    /// Parses a clearance mode name (`keep`, `relocate` or `clear`), case-insensitively.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "keep" => Some(SpawnClearance::Keep),
+            "relocate" => Some(SpawnClearance::Relocate),
+            "clear" => Some(SpawnClearance::Clear),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the box a SpawnLocation centered at `center` needs clear: its pad
+/// plus [`PLAYER_CLEARANCE`] above it.
+fn spawn_volume(center: (f32, f32, f32)) -> PartBox {
+    let (x, y, z) = center;
+    let (size_x, size_y, size_z) = SPAWN_PAD_SIZE;
+    (
+        [x - size_x / 2.0, y - size_y / 2.0, z - size_z / 2.0],
+        [x + size_x / 2.0, y + size_y / 2.0 + PLAYER_CLEARANCE, z + size_z / 2.0],
+    )
+}
+
+/// Returns the indices of blocks inside the volume of a SpawnLocation centered at `center`.
+fn obstructing(boxes: &[PartBox], center: (f32, f32, f32)) -> Vec<usize> {
+    let volume = spawn_volume(center);
+    (0..boxes.len()).filter(|&index| boxes_overlap(&boxes[index], &volume)).collect()
+}
+
+/// Returns the indices of blocks inside the volume of any SpawnLocation pad a
+/// place export generates (the default spawn or free-standing spawn points), in
+/// ascending order.
+pub fn blocks_obstructing_spawns(space_json: &SpaceJSON) -> Vec<usize> {
+    let boxes: Vec<_> = space_json.blocks.iter().map(block_box).collect();
+    let mut blocks: Vec<usize> = spawn_pad_positions(space_json)
+        .into_iter()
+        .flat_map(|center| obstructing(&boxes, center))
+        .collect();
+    blocks.sort_unstable();
+    blocks.dedup();
+    blocks
+}
+
+/// Block boxes indexed by the (x, z) cells they cover, so searching a column
+/// only checks the blocks near it.
+struct ColumnIndex<'a> {
+    boxes: &'a [PartBox],
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    /// Blocks covering more than [`MAX_INDEXED_CELLS`] cells.
+    oversized: Vec<usize>,
+}
+
+impl<'a> ColumnIndex<'a> {
+    fn new(boxes: &'a [PartBox]) -> Self {
+        let mut index = ColumnIndex {
+            boxes,
+            cells: HashMap::new(),
+            oversized: Vec::new(),
+        };
+        for (i, part) in boxes.iter().enumerate() {
+            let (xs, zs) = cell_range(part);
+            let cell_count = (i64::from(*xs.end()) - i64::from(*xs.start()) + 1)
+                * (i64::from(*zs.end()) - i64::from(*zs.start()) + 1);
+            if cell_count > MAX_INDEXED_CELLS {
+                index.oversized.push(i);
+                continue;
+            }
+            for cell_x in xs {
+                for cell_z in zs.clone() {
+                    index.cells.entry((cell_x, cell_z)).or_default().push(i);
+                }
+            }
+        }
+        index
+    }
+
+    /// Returns the boxes whose (x, z) footprint may overlap `area`'s. A box
+    /// spanning several cells may be returned more than once.
+    fn near(&self, area: &PartBox) -> impl Iterator<Item = &PartBox> + '_ {
+        let (xs, zs) = cell_range(area);
+        let indexed = xs.flat_map(move |cell_x| {
+            zs.clone()
+                .filter_map(move |cell_z| self.cells.get(&(cell_x, cell_z)))
+                .flatten()
+        });
+        indexed.chain(&self.oversized).map(|&i| &self.boxes[i])
+    }
+}
+
+/// Returns the ranges of cell coordinates along X and Z that a box covers.
+fn cell_range(part: &PartBox) -> (std::ops::RangeInclusive<i32>, std::ops::RangeInclusive<i32>) {
+    let cell = |value: f32| (value / COLUMN_CELL_SIZE).floor() as i32;
+    (cell(part.0[0])..=cell(part.1[0]), cell(part.0[2])..=cell(part.1[2]))
+}
+
+/// Returns the clear spawn position at `x`/`z` closest to height `y`: `y`
+/// itself, or resting on the highest block whose top is above the pad's bottom.
+fn clear_position_in_column(columns: &ColumnIndex, x: f32, y: f32, z: f32) -> (f32, f32, f32) {
+    let volume = spawn_volume((x, y, z));
+    let column = ([volume.0[0], volume.0[1], volume.0[2]], [volume.1[0], f32::MAX, volume.1[2]]);
+    let mut column_top = None;
+    let mut blocked = false;
+    for part in columns.near(&column).filter(|part| boxes_overlap(part, &column)) {
+        blocked |= boxes_overlap(part, &volume);
+        column_top = Some(column_top.map_or(part.1[1], |top: f32| top.max(part.1[1])));
+    }
+    match column_top {
+        Some(top) if blocked => (x, top + SPAWN_PAD_SIZE.1 / 2.0, z),
+        _ => (x, y, z),
+    }
+}
+
+/// Finds the clear SpawnLocation position nearest to `center`.
+///
+/// Candidates lie on the 2-stud grid around `center`, at `center`'s height or
+/// on top of the blocks there, up to [`MAX_SEARCH_STEPS`] steps away. Standing
+/// on the blocks above `center` is always possible, so a position is always found.
+fn nearest_clear_position(columns: &ColumnIndex, center: (f32, f32, f32)) -> (f32, f32, f32) {
+    let distance = |(x, y, z): (f32, f32, f32)| {
+        ((x - center.0).powi(2) + (y - center.1).powi(2) + (z - center.2).powi(2)).sqrt()
+    };
+    let mut best = clear_position_in_column(columns, center.0, center.1, center.2);
+    for ring in 1..=MAX_SEARCH_STEPS {
+        // Every candidate on this ring is at least this far away
+        if distance(best) <= ring as f32 * SEARCH_STEP {
+            break;
+        }
+        for dx in -ring..=ring {
+            for dz in -ring..=ring {
+                if dx.abs() != ring && dz.abs() != ring {
+                    continue;
+                }
+                let x = center.0 + dx as f32 * SEARCH_STEP;
+                let z = center.2 + dz as f32 * SEARCH_STEP;
+                let candidate = clear_position_in_column(columns, x, center.1, z);
+                if distance(candidate) < distance(best) {
+                    best = candidate;
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Returns the level as a place export with `clearance` generates it.
+///
+/// [`SpawnClearance::Relocate`] moves each obstructed spawn point, or the
+/// default spawn by setting `world.spawn.position`. [`SpawnClearance::Clear`]
+/// drops every block in [`blocks_obstructing_spawns`]. Spawn blocks are never
+/// moved. The level is borrowed unchanged when nothing is in the way.
+pub fn clear_spawns(space_json: &SpaceJSON, clearance: SpawnClearance) -> Cow<'_, SpaceJSON> {
+    match clearance {
+        SpawnClearance::Keep => Cow::Borrowed(space_json),
+        SpawnClearance::Clear => {
+            let obstructing = blocks_obstructing_spawns(space_json);
+            if obstructing.is_empty() {
+                return Cow::Borrowed(space_json);
+            }
+            let mut level = space_json.clone();
+            level.blocks = space_json
+                .blocks
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| obstructing.binary_search(index).is_err())
+                .map(|(_, block)| block.clone())
+                .collect();
+            Cow::Owned(level)
+        }
+        SpawnClearance::Relocate => {
+            let boxes: Vec<_> = space_json.blocks.iter().map(block_box).collect();
+            let columns = ColumnIndex::new(&boxes);
+            let mut level = Cow::Borrowed(space_json);
+            for (index, center) in spawn_pad_positions(space_json).into_iter().enumerate() {
+                if obstructing(&boxes, center).is_empty() {
+                    continue;
+                }
+                let (x, y, z) = nearest_clear_position(&columns, center);
+                let level = level.to_mut();
+                if level.spawns.is_empty() {
+                    level.world.get_or_insert_with(Default::default).spawn = Some(SpawnSettings {
+                        auto: None,
+                        position: Some(WorldPosition { x, y, z }),
+                    });
+                } else {
+                    let spawn = &mut level.spawns[index];
+                    (spawn.x, spawn.y, spawn.z) = (x, y, z);
+                }
+            }
+            level
+        }
+    }
+}
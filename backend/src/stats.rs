@@ -0,0 +1,170 @@
+//! Level statistics.
+//!
+//! Summarizes a Space JSON level without generating a place file: block count,
+//! bounding box, colors used, and the expected size of the exported file.
+
+use crate::meshing::greedy_mesh;
+use crate::models::SpaceJSON;
+use crate::rbxlx::{ExportOptions, OutputFormat};
+use crate::spawn::clear_spawns;
+use crate::validation::ValidationLimits;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Approximate bytes of a generated place besides the level's Parts (services,
+/// Terrain, Lighting, etc.), by encoding.
+const XML_PLACE_OVERHEAD_BYTES: usize = 3_500;
+const BINARY_PLACE_OVERHEAD_BYTES: usize = 1_500;
+
+/// Approximate bytes of a generated model besides its Parts.
+const XML_MODEL_OVERHEAD_BYTES: usize = 400;
+const BINARY_MODEL_OVERHEAD_BYTES: usize = 300;
+
+/// Approximate bytes per Part, by encoding. Binary files store properties
+/// column-wise, so each additional Part costs far less.
+const XML_PART_BYTES: usize = 750;
+const BINARY_PART_BYTES: usize = 60;
+
+/// Statistics of a level, as returned by `/api/validate`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LevelStats {
+    pub block_count: usize,
+    /// The applicable [`ValidationLimits::max_blocks`], so clients can show how
+    /// close the level is to the limit.
+    pub max_blocks: usize,
+    /// Smallest box containing every block position, or `None` for an empty level.
+    pub bounding_box: Option<BoundingBox>,
+    /// Colors used by the level, most used first.
+    pub colors: Vec<ColorCount>,
+    /// Number of Parts (blocks, Baseplate and SpawnLocations) the export will contain.
+    pub estimated_part_count: usize,
+    /// Approximate size of the exported file in bytes.
+    pub estimated_file_size: usize,
+}
+
+/// Axis-aligned bounds of block positions, in studs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct BoundingBox {
+    pub min: GridPosition,
+    pub max: GridPosition,
+}
+
+/// A block position in studs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GridPosition {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// Number of blocks using one color.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColorCount {
+    /// Uppercase hex color as written by the client (e.g. `#FF0000`).
+    pub color: String,
+    pub count: usize,
+}
+
+/// Computes statistics for a level as it would be exported with `options` in `format`.
+///
+/// Expects a level that passed validation against `limits`: estimating the
+/// Part count meshes blocks and clears spawns, which gets slow for levels
+/// beyond the limits.
+pub fn level_stats(
+    space_json: &SpaceJSON,
+    limits: &ValidationLimits,
+    options: &ExportOptions,
+    format: OutputFormat,
+) -> LevelStats {
+    let part_count = estimated_part_count(space_json, options, format);
+    LevelStats {
+        block_count: space_json.blocks.len(),
+        max_blocks: limits.max_blocks,
+        bounding_box: bounding_box(space_json),
+        colors: color_histogram(space_json),
+        estimated_part_count: part_count,
+        estimated_file_size: estimated_file_size(part_count, format),
+    }
+}
+
+fn bounding_box(space_json: &SpaceJSON) -> Option<BoundingBox> {
+    let mut blocks = space_json.blocks.iter();
+    let first = blocks.next()?;
+    let start = GridPosition {
+        x: first.x,
+        y: first.y,
+        z: first.z,
+    };
+    let (min, max) = blocks.fold((start, start), |(min, max), block| {
+        (
+            GridPosition {
+                x: min.x.min(block.x),
+                y: min.y.min(block.y),
+                z: min.z.min(block.z),
+            },
+            GridPosition {
+                x: max.x.max(block.x),
+                y: max.y.max(block.y),
+                z: max.z.max(block.z),
+            },
+        )
+    });
+    Some(BoundingBox { min, max })
+}
+
+/// Counts blocks per color, ordered by count (descending) then color.
+fn color_histogram(space_json: &SpaceJSON) -> Vec<ColorCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for block in &space_json.blocks {
+        *counts.entry(block.color.to_ascii_uppercase()).or_default() += 1;
+    }
+
+    let mut colors: Vec<ColorCount> = counts
+        .into_iter()
+        .map(|(color, count)| ColorCount { color, count })
+        .collect();
+    colors.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.color.cmp(&b.color)));
+    colors
+}
+
+/// Counts the Parts generation will emit, mirroring `build_place_dom` and `build_model_dom`.
+fn estimated_part_count(space_json: &SpaceJSON, options: &ExportOptions, format: OutputFormat) -> usize {
+    let space_json = if format.is_model() {
+        space_json
+    } else {
+        &*clear_spawns(space_json, options.spawn_clearance)
+    };
+    let block_parts = if options.merge_blocks {
+        greedy_mesh(&space_json.blocks).len()
+    } else {
+        space_json.blocks.len()
+    };
+    if format.is_model() {
+        return block_parts;
+    }
+
+    let world = space_json.world.as_ref();
+    let baseplate_enabled = world
+        .and_then(|world| world.baseplate.as_ref())
+        .and_then(|baseplate| baseplate.enabled)
+        .unwrap_or(true);
+    let has_spawn_blocks = space_json.blocks.iter().any(|block| block.spawn.is_some());
+    let spawn_locations = if space_json.spawns.is_empty() && !has_spawn_blocks {
+        1
+    } else {
+        space_json.spawns.len()
+    };
+
+    block_parts + usize::from(baseplate_enabled) + spawn_locations
+}
+
+fn estimated_file_size(part_count: usize, format: OutputFormat) -> usize {
+    let (overhead, per_part) = match (format.is_model(), format.is_binary()) {
+        (false, false) => (XML_PLACE_OVERHEAD_BYTES, XML_PART_BYTES),
+        (false, true) => (BINARY_PLACE_OVERHEAD_BYTES, BINARY_PART_BYTES),
+        (true, false) => (XML_MODEL_OVERHEAD_BYTES, XML_PART_BYTES),
+        (true, true) => (BINARY_MODEL_OVERHEAD_BYTES, BINARY_PART_BYTES),
+    };
+    overhead + part_count * per_part
+}
@@ -0,0 +1,264 @@
+//! Server-side level storage.
+//!
+//! Persists validated Space JSON levels with creation and update timestamps
+//! behind the [`LevelStore`] trait, along with an immutable revision for every
+//! save and read-only share links.
+//! [`FsLevelStore`] keeps one JSON file per level; [`SqliteLevelStore`] keeps
+//! every level in a single SQLite database.
+
+mod fs;
+mod sqlite;
+
+pub use fs::FsLevelStore;
+pub use sqlite::SqliteLevelStore;
+
+use crate::models::SpaceJSON;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Maximum length of a level ID.
+pub const MAX_LEVEL_ID_LENGTH: usize = 64;
+
+/// Storage error variants with associated context data.
+#[derive(Debug, Clone)]
+pub enum StorageError {
+    NotFound { id: String },
+    InvalidId { id: String },
+    RevisionNotFound { id: String, revision: u32 },
+    RevisionConflict { id: String, expected: u32, actual: u32 },
+    ShareNotFound,
+    ShareExpired,
+    ShareRevoked,
+    Backend { reason: String },
+}
+
+impl StorageError {
+    /// Returns a machine-readable error code for API responses.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            StorageError::NotFound { .. } => "LEVEL_NOT_FOUND",
+            StorageError::InvalidId { .. } => "INVALID_LEVEL_ID",
+            StorageError::RevisionNotFound { .. } => "REVISION_NOT_FOUND",
+            StorageError::RevisionConflict { .. } => "REVISION_CONFLICT",
+            StorageError::ShareNotFound => "SHARE_NOT_FOUND",
+            StorageError::ShareExpired => "SHARE_EXPIRED",
+            StorageError::ShareRevoked => "SHARE_REVOKED",
+            StorageError::Backend { .. } => "STORAGE_FAILED",
+        }
+    }
+
+    /// Returns a human-readable error message for display to users.
+    pub fn message(&self) -> String {
+        match self {
+            StorageError::NotFound { id } => format!("Level '{}' does not exist.", id),
+            StorageError::InvalidId { id } => format!(
+                "Invalid level ID '{}'. IDs must be 1 to {} letters, digits, '-' or '_'.",
+                id, MAX_LEVEL_ID_LENGTH
+            ),
+            StorageError::RevisionNotFound { id, revision } => {
+                format!("Level '{}' has no revision {}.", id, revision)
+            }
+            StorageError::RevisionConflict { id, expected, actual } => format!(
+                "Level '{}' is at revision {}, not revision {}: it was saved in the meantime.",
+                id, actual, expected
+            ),
+            StorageError::ShareNotFound => "This share link does not exist.".to_string(),
+            StorageError::ShareExpired => "This share link has expired.".to_string(),
+            StorageError::ShareRevoked => "This share link has been revoked.".to_string(),
+            StorageError::Backend { reason } => format!("Level storage failed: {}", reason),
+        }
+    }
+}
+
+/// A level as stored on the server.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredLevel {
+    pub id: String,
+    /// Unix timestamp in milliseconds of the first save.
+    pub created_at: u64,
+    /// Unix timestamp in milliseconds of the latest save.
+    pub updated_at: u64,
+    /// Number of the latest revision. The first save is revision 1.
+    #[serde(default)]
+    pub revision: u32,
+    pub space: SpaceJSON,
+}
+
+/// A stored level without its blocks, as returned when listing levels.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LevelSummary {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub block_count: usize,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+impl From<&StoredLevel> for LevelSummary {
+    fn from(level: &StoredLevel) -> Self {
+        LevelSummary {
+            id: level.id.clone(),
+            name: level.space.name.clone(),
+            block_count: level.space.blocks.len(),
+            created_at: level.created_at,
+            updated_at: level.updated_at,
+        }
+    }
+}
+
+/// An immutable snapshot of a level, recorded by every save.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LevelRevision {
+    pub level_id: String,
+    pub revision: u32,
+    /// Unix timestamp in milliseconds of the save that created the revision.
+    pub created_at: u64,
+    pub space: SpaceJSON,
+}
+
+/// A revision without its blocks, as returned when listing revisions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionSummary {
+    pub revision: u32,
+    pub created_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub block_count: usize,
+}
+
+impl From<&LevelRevision> for RevisionSummary {
+    fn from(revision: &LevelRevision) -> Self {
+        RevisionSummary {
+            revision: revision.revision,
+            created_at: revision.created_at,
+            name: revision.space.name.clone(),
+            block_count: revision.space.blocks.len(),
+        }
+    }
+}
+
+/// A read-only link to a stored level.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareLink {
+    /// Unguessable token identifying the link (see [`is_share_token`]).
+    pub token: String,
+    pub level_id: String,
+    /// Unix timestamp in milliseconds when the link was created.
+    pub created_at: u64,
+    /// Unix timestamp in milliseconds after which the link stops working, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    pub revoked: bool,
+}
+
+/// Persistent storage for levels and their share links.
+///
+/// Implementations only store what they are given; callers validate levels first.
+pub trait LevelStore: Send + Sync {
+    /// Stores a new level under a freshly generated ID, as revision 1.
+    fn create(&self, space: SpaceJSON) -> Result<StoredLevel, StorageError>;
+
+    /// Loads a level by ID.
+    fn get(&self, id: &str) -> Result<StoredLevel, StorageError>;
+
+    /// Creates or replaces the level with the given ID, recording a new revision.
+    ///
+    /// Replacing a level keeps its `created_at` timestamp.
+    fn put(&self, id: &str, space: SpaceJSON) -> Result<StoredLevel, StorageError>;
+
+    /// Replaces an existing level like [`LevelStore::put`], but only if its latest
+    /// revision is still `expected`.
+    ///
+    /// Fails with [`StorageError::RevisionConflict`] if the level was saved since,
+    /// and with [`StorageError::NotFound`] if it was deleted.
+    fn put_if_revision(&self, id: &str, space: SpaceJSON, expected: u32) -> Result<StoredLevel, StorageError>;
+
+    /// Deletes a level by ID, along with its revisions and share links.
+    fn delete(&self, id: &str) -> Result<(), StorageError>;
+
+    /// Lists every stored level, most recently updated first.
+    fn list(&self) -> Result<Vec<LevelSummary>, StorageError>;
+
+    /// Lists a level's revisions, newest first.
+    fn list_revisions(&self, id: &str) -> Result<Vec<RevisionSummary>, StorageError>;
+
+    /// Loads one revision of a level.
+    fn get_revision(&self, id: &str, revision: u32) -> Result<LevelRevision, StorageError>;
+
+    /// Mints a share link for an existing level, optionally expiring at `expires_at`.
+    fn create_share(&self, level_id: &str, expires_at: Option<u64>) -> Result<ShareLink, StorageError>;
+
+    /// Loads a share link by token, including expired and revoked links.
+    fn get_share(&self, token: &str) -> Result<ShareLink, StorageError>;
+
+    /// Revokes one of a level's share links. Revoking twice is not an error.
+    fn revoke_share(&self, level_id: &str, token: &str) -> Result<(), StorageError>;
+
+    /// Returns the level a share token points to, if the link is usable at time `now`.
+    fn shared_level(&self, token: &str, now: u64) -> Result<StoredLevel, StorageError> {
+        let share = self.get_share(token)?;
+        if share.revoked {
+            return Err(StorageError::ShareRevoked);
+        }
+        if share.expires_at.is_some_and(|expires_at| now >= expires_at) {
+            return Err(StorageError::ShareExpired);
+        }
+        // Deleting a level makes its share links unusable
+        self.get(&share.level_id).map_err(|e| match e {
+            StorageError::NotFound { .. } => StorageError::ShareNotFound,
+            e => e,
+        })
+    }
+}
+
+/// Validates that a level ID is non-empty, at most [`MAX_LEVEL_ID_LENGTH`] long,
+/// and uses only ASCII letters, digits, `-` and `_` (so it is safe as a file name).
+pub fn validate_level_id(id: &str) -> Result<(), StorageError> {
+    let valid = !id.is_empty()
+        && id.len() <= MAX_LEVEL_ID_LENGTH
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(StorageError::InvalidId { id: id.to_string() });
+    }
+    Ok(())
+}
+
+/// Length of a share token in characters.
+const SHARE_TOKEN_LENGTH: usize = 64;
+
+/// Returns `true` if `token` has the shape of a share token (64 lowercase hex digits).
+///
+/// Stores reject anything else as [`StorageError::ShareNotFound`] without a lookup.
+pub fn is_share_token(token: &str) -> bool {
+    token.len() == SHARE_TOKEN_LENGTH && token.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+}
+
+/// Generates a new random level ID.
+fn new_level_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Generates a new share token from two random UUIDs (244 random bits).
+fn new_share_token() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+/// Returns the current time as a Unix timestamp in milliseconds.
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn backend_error(error: impl std::fmt::Display) -> StorageError {
+    StorageError::Backend {
+        reason: error.to_string(),
+    }
+}
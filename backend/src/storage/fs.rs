@@ -0,0 +1,308 @@
+//! Filesystem level storage: one `<id>.json` file per level, one
+//! `revisions/<id>/<revision>.json` file per revision, and one
+//! `shares/<token>.json` file per share link.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+use super::{
+    backend_error, is_share_token, new_level_id, new_share_token, now_millis, validate_level_id, LevelRevision,
+    LevelStore, LevelSummary, RevisionSummary, ShareLink, StorageError, StoredLevel,
+};
+use crate::models::SpaceJSON;
+
+/// Stores each level as a JSON file in a directory.
+pub struct FsLevelStore {
+    dir: PathBuf,
+    /// Serializes writes so concurrent saves of one level cannot interleave.
+    write_lock: Mutex<()>,
+}
+
+impl FsLevelStore {
+    /// Opens a store in `dir`, creating the directory if needed.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, StorageError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(backend_error)?;
+        Ok(FsLevelStore {
+            dir,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    fn level_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    fn revisions_dir(&self, id: &str) -> PathBuf {
+        self.dir.join("revisions").join(id)
+    }
+
+    fn revision_path(&self, id: &str, revision: u32) -> PathBuf {
+        self.revisions_dir(id).join(format!("{}.json", revision))
+    }
+
+    fn shares_dir(&self) -> PathBuf {
+        self.dir.join("shares")
+    }
+
+    fn share_path(&self, token: &str) -> PathBuf {
+        self.shares_dir().join(format!("{}.json", token))
+    }
+
+    fn read_level(&self, path: &Path, id: &str) -> Result<StoredLevel, StorageError> {
+        let content = fs::read(path).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => StorageError::NotFound { id: id.to_string() },
+            _ => backend_error(e),
+        })?;
+        serde_json::from_slice(&content).map_err(backend_error)
+    }
+
+    fn read_revision(&self, path: &Path, id: &str, revision: u32) -> Result<LevelRevision, StorageError> {
+        let content = fs::read(path).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => StorageError::RevisionNotFound {
+                id: id.to_string(),
+                revision,
+            },
+            _ => backend_error(e),
+        })?;
+        serde_json::from_slice(&content).map_err(backend_error)
+    }
+
+    /// Writes the level's current revision, then the level itself.
+    fn write_level(&self, level: &StoredLevel) -> Result<(), StorageError> {
+        let revision = LevelRevision {
+            level_id: level.id.clone(),
+            revision: level.revision,
+            created_at: level.updated_at,
+            space: level.space.clone(),
+        };
+        let revisions_dir = self.revisions_dir(&level.id);
+        fs::create_dir_all(&revisions_dir).map_err(backend_error)?;
+        write_json(&self.revision_path(&level.id, level.revision), &revision)?;
+        write_json(&self.level_path(&level.id), level)
+    }
+
+    /// Creates or replaces a level, requiring an existing level at revision
+    /// `expected` if one is given.
+    fn put_checked(&self, id: &str, space: SpaceJSON, expected: Option<u32>) -> Result<StoredLevel, StorageError> {
+        validate_level_id(id)?;
+        let _guard = self.write_lock.lock().map_err(backend_error)?;
+        let now = now_millis();
+        let (created_at, revision) = match self.read_level(&self.level_path(id), id) {
+            Ok(existing) => (existing.created_at, existing.revision + 1),
+            Err(StorageError::NotFound { .. }) if expected.is_none() => (now, 1),
+            Err(e) => return Err(e),
+        };
+        if let Some(expected) = expected.filter(|&expected| expected != revision - 1) {
+            return Err(StorageError::RevisionConflict {
+                id: id.to_string(),
+                expected,
+                actual: revision - 1,
+            });
+        }
+        let level = StoredLevel {
+            id: id.to_string(),
+            created_at,
+            updated_at: now,
+            revision,
+            space,
+        };
+        self.write_level(&level)?;
+        Ok(level)
+    }
+
+    /// Removes every share link pointing at a level.
+    fn delete_shares(&self, level_id: &str) -> Result<(), StorageError> {
+        let entries = match fs::read_dir(self.shares_dir()) {
+            Ok(entries) => entries,
+            // Nothing has been shared yet
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(backend_error(e)),
+        };
+        for entry in entries {
+            let path = entry.map_err(backend_error)?.path();
+            let Some(token) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".json"))
+            else {
+                continue;
+            };
+            match self.get_share(token) {
+                Ok(share) if share.level_id == level_id => match fs::remove_file(&path) {
+                    Err(e) if e.kind() != ErrorKind::NotFound => return Err(backend_error(e)),
+                    _ => {}
+                },
+                Ok(_) | Err(StorageError::ShareNotFound) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes JSON through a temporary file so readers never see a partial file.
+fn write_json(path: &Path, value: &impl Serialize) -> Result<(), StorageError> {
+    let temp_path = path.with_extension("json.tmp");
+    let content = serde_json::to_vec_pretty(value).map_err(backend_error)?;
+    fs::write(&temp_path, content).map_err(backend_error)?;
+    fs::rename(&temp_path, path).map_err(backend_error)
+}
+
+impl LevelStore for FsLevelStore {
+    fn create(&self, space: SpaceJSON) -> Result<StoredLevel, StorageError> {
+        let _guard = self.write_lock.lock().map_err(backend_error)?;
+        let now = now_millis();
+        let level = StoredLevel {
+            id: new_level_id(),
+            created_at: now,
+            updated_at: now,
+            revision: 1,
+            space,
+        };
+        self.write_level(&level)?;
+        Ok(level)
+    }
+
+    fn get(&self, id: &str) -> Result<StoredLevel, StorageError> {
+        validate_level_id(id)?;
+        self.read_level(&self.level_path(id), id)
+    }
+
+    fn put(&self, id: &str, space: SpaceJSON) -> Result<StoredLevel, StorageError> {
+        self.put_checked(id, space, None)
+    }
+
+    fn put_if_revision(&self, id: &str, space: SpaceJSON, expected: u32) -> Result<StoredLevel, StorageError> {
+        self.put_checked(id, space, Some(expected))
+    }
+
+    fn delete(&self, id: &str) -> Result<(), StorageError> {
+        validate_level_id(id)?;
+        let _guard = self.write_lock.lock().map_err(backend_error)?;
+        fs::remove_file(self.level_path(id)).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => StorageError::NotFound { id: id.to_string() },
+            _ => backend_error(e),
+        })?;
+        match fs::remove_dir_all(self.revisions_dir(id)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => return Err(backend_error(e)),
+            _ => {}
+        }
+        // A level later saved under the same ID must not inherit old links
+        self.delete_shares(id)
+    }
+
+    fn list(&self) -> Result<Vec<LevelSummary>, StorageError> {
+        let mut summaries = Vec::new();
+        for entry in fs::read_dir(&self.dir).map_err(backend_error)? {
+            let path = entry.map_err(backend_error)?.path();
+            let Some(id) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".json"))
+            else {
+                continue;
+            };
+            if validate_level_id(id).is_err() {
+                continue;
+            }
+            match self.read_level(&path, id) {
+                Ok(level) => summaries.push(LevelSummary::from(&level)),
+                // Deleted between listing the directory and reading the file
+                Err(StorageError::NotFound { .. }) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        summaries.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(summaries)
+    }
+
+    fn list_revisions(&self, id: &str) -> Result<Vec<RevisionSummary>, StorageError> {
+        self.get(id)?;
+        let entries = match fs::read_dir(self.revisions_dir(id)) {
+            Ok(entries) => entries,
+            // Saved before revisions were recorded
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(backend_error(e)),
+        };
+        let mut summaries = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(backend_error)?.path();
+            let Some(revision) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".json"))
+                .and_then(|number| number.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            match self.read_revision(&path, id, revision) {
+                Ok(revision) => summaries.push(RevisionSummary::from(&revision)),
+                // Level deleted while listing
+                Err(StorageError::RevisionNotFound { .. }) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        summaries.sort_by_key(|summary| std::cmp::Reverse(summary.revision));
+        Ok(summaries)
+    }
+
+    fn get_revision(&self, id: &str, revision: u32) -> Result<LevelRevision, StorageError> {
+        validate_level_id(id)?;
+        match self.read_revision(&self.revision_path(id, revision), id, revision) {
+            // Distinguish a missing level from a missing revision
+            Err(StorageError::RevisionNotFound { .. }) => {
+                self.get(id)?;
+                Err(StorageError::RevisionNotFound {
+                    id: id.to_string(),
+                    revision,
+                })
+            }
+            result => result,
+        }
+    }
+
+    fn create_share(&self, level_id: &str, expires_at: Option<u64>) -> Result<ShareLink, StorageError> {
+        // Held so the level cannot be deleted between the check and the write
+        let _guard = self.write_lock.lock().map_err(backend_error)?;
+        self.get(level_id)?;
+        let share = ShareLink {
+            token: new_share_token(),
+            level_id: level_id.to_string(),
+            created_at: now_millis(),
+            expires_at,
+            revoked: false,
+        };
+        let path = self.share_path(&share.token);
+        if let Some(shares_dir) = path.parent() {
+            fs::create_dir_all(shares_dir).map_err(backend_error)?;
+        }
+        write_json(&path, &share)?;
+        Ok(share)
+    }
+
+    fn get_share(&self, token: &str) -> Result<ShareLink, StorageError> {
+        if !is_share_token(token) {
+            return Err(StorageError::ShareNotFound);
+        }
+        let content = fs::read(self.share_path(token)).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => StorageError::ShareNotFound,
+            _ => backend_error(e),
+        })?;
+        serde_json::from_slice(&content).map_err(backend_error)
+    }
+
+    fn revoke_share(&self, level_id: &str, token: &str) -> Result<(), StorageError> {
+        let _guard = self.write_lock.lock().map_err(backend_error)?;
+        let mut share = self.get_share(token)?;
+        if share.level_id != level_id {
+            return Err(StorageError::ShareNotFound);
+        }
+        share.revoked = true;
+        write_json(&self.share_path(token), &share)
+    }
+}
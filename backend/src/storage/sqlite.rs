@@ -0,0 +1,347 @@
+//! SQLite level storage: every level in one `levels` table, every revision in
+//! a `revisions` table, and share links in a `shares` table.
+
+use super::{
+    backend_error, is_share_token, new_level_id, new_share_token, now_millis, validate_level_id, LevelStore,
+    LevelRevision, LevelSummary, RevisionSummary, ShareLink, StorageError, StoredLevel,
+};
+use crate::models::SpaceJSON;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS levels (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT,
+    block_count INTEGER NOT NULL,
+    space TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS revisions (
+    level_id TEXT NOT NULL,
+    revision INTEGER NOT NULL,
+    name TEXT,
+    block_count INTEGER NOT NULL,
+    space TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    PRIMARY KEY (level_id, revision)
+);
+CREATE TABLE IF NOT EXISTS shares (
+    token TEXT PRIMARY KEY NOT NULL,
+    level_id TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    expires_at INTEGER,
+    revoked INTEGER NOT NULL DEFAULT 0
+);";
+
+/// Stores levels in a SQLite database, with the Space JSON kept as text.
+pub struct SqliteLevelStore {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteLevelStore {
+    /// Opens (or creates) a database file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        Self::from_connection(Connection::open(path).map_err(backend_error)?)
+    }
+
+    /// Opens a private in-memory database, discarded when the store is dropped.
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::from_connection(Connection::open_in_memory().map_err(backend_error)?)
+    }
+
+    fn from_connection(connection: Connection) -> Result<Self, StorageError> {
+        connection.execute_batch(SCHEMA).map_err(backend_error)?;
+        Ok(SqliteLevelStore {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> Result<MutexGuard<'_, Connection>, StorageError> {
+        self.connection.lock().map_err(backend_error)
+    }
+
+    /// Creates or replaces a level, requiring an existing level at revision
+    /// `expected` if one is given.
+    fn put_checked(&self, id: &str, space: SpaceJSON, expected: Option<u32>) -> Result<StoredLevel, StorageError> {
+        validate_level_id(id)?;
+        let connection = self.connection()?;
+        let now = now_millis();
+        let created_at: Option<i64> = connection
+            .query_row("SELECT created_at FROM levels WHERE id = ?1", params![id], |row| row.get(0))
+            .optional()
+            .map_err(backend_error)?;
+        let latest = latest_revision(&connection, id)?;
+        if let Some(expected) = expected {
+            if created_at.is_none() {
+                return Err(StorageError::NotFound { id: id.to_string() });
+            }
+            if latest != expected {
+                return Err(StorageError::RevisionConflict {
+                    id: id.to_string(),
+                    expected,
+                    actual: latest,
+                });
+            }
+        }
+        let level = StoredLevel {
+            id: id.to_string(),
+            created_at: created_at.map_or(now, |created_at| created_at as u64),
+            updated_at: now,
+            revision: latest + 1,
+            space,
+        };
+        write_level(&connection, &level)?;
+        Ok(level)
+    }
+}
+
+/// Returns the latest revision number of a level, or 0 if it has none.
+fn latest_revision(connection: &Connection, id: &str) -> Result<u32, StorageError> {
+    connection
+        .query_row(
+            "SELECT COALESCE(MAX(revision), 0) FROM revisions WHERE level_id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .map_err(backend_error)
+}
+
+/// Writes a level row, replacing any existing row with the same ID, and
+/// records its current revision.
+fn write_level(connection: &Connection, level: &StoredLevel) -> Result<(), StorageError> {
+    let space = serde_json::to_string(&level.space).map_err(backend_error)?;
+    let transaction = connection.unchecked_transaction().map_err(backend_error)?;
+    transaction
+        .execute(
+            "INSERT INTO revisions (level_id, revision, name, block_count, space, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                level.id,
+                level.revision,
+                level.space.name,
+                level.space.blocks.len() as i64,
+                space,
+                level.updated_at as i64
+            ],
+        )
+        .map_err(backend_error)?;
+    transaction
+        .execute(
+            "INSERT OR REPLACE INTO levels (id, name, block_count, space, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                level.id,
+                level.space.name,
+                level.space.blocks.len() as i64,
+                space,
+                level.created_at as i64,
+                level.updated_at as i64
+            ],
+        )
+        .map_err(backend_error)?;
+    transaction.commit().map_err(backend_error)
+}
+
+impl LevelStore for SqliteLevelStore {
+    fn create(&self, space: SpaceJSON) -> Result<StoredLevel, StorageError> {
+        let connection = self.connection()?;
+        let now = now_millis();
+        let level = StoredLevel {
+            id: new_level_id(),
+            created_at: now,
+            updated_at: now,
+            revision: 1,
+            space,
+        };
+        write_level(&connection, &level)?;
+        Ok(level)
+    }
+
+    fn get(&self, id: &str) -> Result<StoredLevel, StorageError> {
+        validate_level_id(id)?;
+        let connection = self.connection()?;
+        let row = connection
+            .query_row(
+                "SELECT space, created_at, updated_at FROM levels WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?)),
+            )
+            .optional()
+            .map_err(backend_error)?;
+        let (space, created_at, updated_at) = row.ok_or_else(|| StorageError::NotFound { id: id.to_string() })?;
+        Ok(StoredLevel {
+            id: id.to_string(),
+            created_at: created_at as u64,
+            updated_at: updated_at as u64,
+            revision: latest_revision(&connection, id)?,
+            space: serde_json::from_str(&space).map_err(backend_error)?,
+        })
+    }
+
+    fn put(&self, id: &str, space: SpaceJSON) -> Result<StoredLevel, StorageError> {
+        self.put_checked(id, space, None)
+    }
+
+    fn put_if_revision(&self, id: &str, space: SpaceJSON, expected: u32) -> Result<StoredLevel, StorageError> {
+        self.put_checked(id, space, Some(expected))
+    }
+
+    fn delete(&self, id: &str) -> Result<(), StorageError> {
+        validate_level_id(id)?;
+        let connection = self.connection()?;
+        let transaction = connection.unchecked_transaction().map_err(backend_error)?;
+        let deleted = transaction
+            .execute("DELETE FROM levels WHERE id = ?1", params![id])
+            .map_err(backend_error)?;
+        if deleted == 0 {
+            return Err(StorageError::NotFound { id: id.to_string() });
+        }
+        transaction
+            .execute("DELETE FROM revisions WHERE level_id = ?1", params![id])
+            .map_err(backend_error)?;
+        // A level later saved under the same ID must not inherit old links
+        transaction
+            .execute("DELETE FROM shares WHERE level_id = ?1", params![id])
+            .map_err(backend_error)?;
+        transaction.commit().map_err(backend_error)
+    }
+
+    fn list(&self) -> Result<Vec<LevelSummary>, StorageError> {
+        let connection = self.connection()?;
+        let mut statement = connection
+            .prepare(
+                "SELECT id, name, block_count, created_at, updated_at FROM levels
+                 ORDER BY updated_at DESC, id ASC",
+            )
+            .map_err(backend_error)?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok(LevelSummary {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    block_count: row.get::<_, i64>(2)? as usize,
+                    created_at: row.get::<_, i64>(3)? as u64,
+                    updated_at: row.get::<_, i64>(4)? as u64,
+                })
+            })
+            .map_err(backend_error)?;
+        rows.collect::<Result<_, _>>().map_err(backend_error)
+    }
+
+    fn list_revisions(&self, id: &str) -> Result<Vec<RevisionSummary>, StorageError> {
+        self.get(id)?;
+        let connection = self.connection()?;
+        let mut statement = connection
+            .prepare(
+                "SELECT revision, created_at, name, block_count FROM revisions
+                 WHERE level_id = ?1 ORDER BY revision DESC",
+            )
+            .map_err(backend_error)?;
+        let rows = statement
+            .query_map(params![id], |row| {
+                Ok(RevisionSummary {
+                    revision: row.get(0)?,
+                    created_at: row.get::<_, i64>(1)? as u64,
+                    name: row.get(2)?,
+                    block_count: row.get::<_, i64>(3)? as usize,
+                })
+            })
+            .map_err(backend_error)?;
+        rows.collect::<Result<_, _>>().map_err(backend_error)
+    }
+
+    fn get_revision(&self, id: &str, revision: u32) -> Result<LevelRevision, StorageError> {
+        self.get(id)?;
+        let row = self
+            .connection()?
+            .query_row(
+                "SELECT space, created_at FROM revisions WHERE level_id = ?1 AND revision = ?2",
+                params![id, revision],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .optional()
+            .map_err(backend_error)?;
+        let (space, created_at) = row.ok_or_else(|| StorageError::RevisionNotFound {
+            id: id.to_string(),
+            revision,
+        })?;
+        Ok(LevelRevision {
+            level_id: id.to_string(),
+            revision,
+            created_at: created_at as u64,
+            space: serde_json::from_str(&space).map_err(backend_error)?,
+        })
+    }
+
+    fn create_share(&self, level_id: &str, expires_at: Option<u64>) -> Result<ShareLink, StorageError> {
+        validate_level_id(level_id)?;
+        let share = ShareLink {
+            token: new_share_token(),
+            level_id: level_id.to_string(),
+            created_at: now_millis(),
+            expires_at,
+            revoked: false,
+        };
+        // Checked in the same statement so a concurrent delete cannot leave an orphaned link
+        let inserted = self
+            .connection()?
+            .execute(
+                "INSERT INTO shares (token, level_id, created_at, expires_at)
+                 SELECT ?1, ?2, ?3, ?4 WHERE EXISTS (SELECT 1 FROM levels WHERE id = ?2)",
+                params![
+                    share.token,
+                    share.level_id,
+                    share.created_at as i64,
+                    share.expires_at.map(|expires_at| expires_at as i64)
+                ],
+            )
+            .map_err(backend_error)?;
+        if inserted == 0 {
+            return Err(StorageError::NotFound { id: level_id.to_string() });
+        }
+        Ok(share)
+    }
+
+    fn get_share(&self, token: &str) -> Result<ShareLink, StorageError> {
+        if !is_share_token(token) {
+            return Err(StorageError::ShareNotFound);
+        }
+        self.connection()?
+            .query_row(
+                "SELECT level_id, created_at, expires_at, revoked FROM shares WHERE token = ?1",
+                params![token],
+                |row| {
+                    Ok(ShareLink {
+                        token: token.to_string(),
+                        level_id: row.get(0)?,
+                        created_at: row.get::<_, i64>(1)? as u64,
+                        expires_at: row.get::<_, Option<i64>>(2)?.map(|expires_at| expires_at as u64),
+                        revoked: row.get(3)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(backend_error)?
+            .ok_or(StorageError::ShareNotFound)
+    }
+
+    fn revoke_share(&self, level_id: &str, token: &str) -> Result<(), StorageError> {
+        if !is_share_token(token) {
+            return Err(StorageError::ShareNotFound);
+        }
+        let updated = self
+            .connection()?
+            .execute(
+                "UPDATE shares SET revoked = 1 WHERE token = ?1 AND level_id = ?2",
+                params![token, level_id],
+            )
+            .map_err(backend_error)?;
+        if updated == 0 {
+            return Err(StorageError::ShareNotFound);
+        }
+        Ok(())
+    }
+}
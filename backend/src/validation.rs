@@ -2,18 +2,52 @@
 //!
 //! Validates incoming Space JSON payloads before `.rbxlx` generation.
 //! All validators return structured errors with error codes and user-friendly messages.
+//! [`validate_space_json`] stops at the first error; [`validate_space_json_all`]
+//! collects every error so users can fix a level in one pass.
+//! [`find_floating_islands`] reports blocks with no support from the ground,
+//! which are only an error when [`ValidationLimits::reject_floating_blocks`] is set.
+//! Soft issues that don't stop an export are returned separately by
+//! [`collect_warnings`]; warnings that depend on how a level is exported are
+//! added by `rbxlx::export_warnings`.
 
-use crate::models::SpaceJSON;
-use std::collections::HashSet;
+use crate::brick_colors::{brick_color_name, resolve_team_colors};
+use crate::geometry::{baseplate_size, block_box, boxes_touch, CONTACT_EPSILON};
+use crate::lighting::technology_value;
+use crate::gameplay::BlockKind;
+use crate::materials::material_value;
+use crate::models::{Block, BlockSize, SpaceJSON, WorldSettings, CURRENT_SCHEMA_VERSION};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 /// Validation error variants with associated context data.
 #[derive(Debug, Clone)]
 pub enum ValidationError {
     InvalidSchemaVersion { version: u32 },
     BlockCountExceeded { count: usize, limit: usize },
-    CoordinateOutOfBounds { x: i32, y: i32, z: i32, index: usize },
+    TeamCountExceeded { count: usize, limit: usize },
+    SpawnCountExceeded { count: usize, limit: usize },
+    CoordinateOutOfBounds { x: i32, y: i32, z: i32, index: usize, bounds: CoordinateBounds },
     InvalidColorFormat { color: String, index: usize },
     DuplicatePosition { x: i32, y: i32, z: i32, index: usize },
+    InvalidMaterial { material: String, index: usize },
+    InvalidBlockKind { kind: String, index: usize },
+    PropertyOutOfRange { property: &'static str, value: f32, index: usize },
+    InvalidBlockSize { size: BlockSize, index: usize },
+    InvalidWorldSetting { setting: &'static str, reason: String },
+    InvalidTeam { name: String, reason: String },
+    InvalidSpawnPoint { index: usize, reason: String },
+    UnknownTeam { team: String, source: SpawnSource, index: usize },
+    FloatingBlocks { blocks: Vec<usize> },
+}
+
+/// Where a spawn referencing a team was defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnSource {
+    /// A spawn block, indexed into `blocks`.
+    Block,
+    /// A free-standing spawn point, indexed into `spawns`.
+    SpawnPoint,
 }
 
 impl ValidationError {
@@ -25,6 +59,39 @@ impl ValidationError {
             ValidationError::CoordinateOutOfBounds { .. } => "COORDINATE_OUT_OF_BOUNDS",
             ValidationError::InvalidColorFormat { .. } => "INVALID_COLOR_FORMAT",
             ValidationError::DuplicatePosition { .. } => "DUPLICATE_POSITION",
+            ValidationError::InvalidMaterial { .. } => "INVALID_MATERIAL",
+            ValidationError::InvalidBlockKind { .. } => "INVALID_BLOCK_KIND",
+            ValidationError::PropertyOutOfRange { .. } => "PROPERTY_OUT_OF_RANGE",
+            ValidationError::InvalidBlockSize { .. } => "INVALID_BLOCK_SIZE",
+            ValidationError::InvalidWorldSetting { .. } => "INVALID_WORLD_SETTING",
+            ValidationError::TeamCountExceeded { .. } => "TEAM_COUNT_EXCEEDED",
+            ValidationError::SpawnCountExceeded { .. } => "SPAWN_COUNT_EXCEEDED",
+            ValidationError::InvalidTeam { .. } => "INVALID_TEAM",
+            ValidationError::InvalidSpawnPoint { .. } => "INVALID_SPAWN_POINT",
+            ValidationError::UnknownTeam { .. } => "UNKNOWN_TEAM",
+            ValidationError::FloatingBlocks { .. } => "FLOATING_BLOCKS",
+        }
+    }
+
+    /// Returns the position of this error's check in the validation order.
+    ///
+    /// Used to group collected errors in the same order fail-fast validation reports them.
+    fn check_order(&self) -> usize {
+        match self {
+            ValidationError::InvalidSchemaVersion { .. } => 0,
+            ValidationError::BlockCountExceeded { .. } => 1,
+            ValidationError::CoordinateOutOfBounds { .. } => 2,
+            ValidationError::InvalidColorFormat { .. } => 3,
+            ValidationError::InvalidMaterial { .. } => 4,
+            ValidationError::InvalidBlockKind { .. } => 5,
+            ValidationError::PropertyOutOfRange { .. } => 6,
+            ValidationError::InvalidBlockSize { .. } => 7,
+            ValidationError::DuplicatePosition { .. } => 8,
+            ValidationError::InvalidWorldSetting { .. } => 9,
+            ValidationError::TeamCountExceeded { .. } | ValidationError::InvalidTeam { .. } => 10,
+            ValidationError::SpawnCountExceeded { .. } | ValidationError::InvalidSpawnPoint { .. } => 11,
+            ValidationError::UnknownTeam { .. } => 12,
+            ValidationError::FloatingBlocks { .. } => 13,
         }
     }
 
@@ -32,15 +99,18 @@ impl ValidationError {
     pub fn message(&self) -> String {
         match self {
             ValidationError::InvalidSchemaVersion { version } => {
-                format!("Unsupported schema version: {}. Only version 1 is supported.", version)
+                format!(
+                    "Unsupported schema version: {}. Supported versions: 1 to {}.",
+                    version, CURRENT_SCHEMA_VERSION
+                )
             }
             ValidationError::BlockCountExceeded { count, limit } => {
                 format!("Block count ({}) exceeds maximum allowed ({}).", count, limit)
             }
-            ValidationError::CoordinateOutOfBounds { x, y, z, index } => {
+            ValidationError::CoordinateOutOfBounds { x, y, z, index, bounds } => {
                 format!(
-                    "Block at position ({}, {}, {}) [index {}] is out of bounds. Valid range: X/Z: -1000 to 1000, Y: 0 to 1000.",
-                    x, y, z, index
+                    "Block at position ({}, {}, {}) [index {}] is out of bounds. Valid range: {}.",
+                    x, y, z, index, bounds
                 )
             }
             ValidationError::InvalidColorFormat { color, index } => {
@@ -55,95 +125,619 @@ impl ValidationError {
                     x, y, z, index
                 )
             }
+            ValidationError::InvalidMaterial { material, index } => {
+                format!(
+                    "Unknown material '{}' at block index {}. Expected a Roblox material name such as Plastic, Wood or Neon.",
+                    material, index
+                )
+            }
+            ValidationError::InvalidBlockKind { kind, index } => {
+                format!(
+                    "Unknown block kind '{}' at block index {}. Expected one of: kill, checkpoint, finish, bounce, speed.",
+                    kind, index
+                )
+            }
+            ValidationError::PropertyOutOfRange { property, value, index } => {
+                format!(
+                    "Block {} '{}' at index {} is out of range. Expected a value from 0 to 1.",
+                    property, value, index
+                )
+            }
+            ValidationError::InvalidBlockSize { size, index } => {
+                format!(
+                    "Invalid block size ({}, {}, {}) at index {}. Each dimension must be greater than 0 and at most {} studs.",
+                    size.x, size.y, size.z, index, MAX_BLOCK_SIZE
+                )
+            }
+            ValidationError::InvalidWorldSetting { setting, reason } => {
+                format!("Invalid world setting 'world.{}': {}.", setting, reason)
+            }
+            ValidationError::TeamCountExceeded { count, limit } => {
+                format!("Team count ({}) exceeds maximum allowed ({}).", count, limit)
+            }
+            ValidationError::SpawnCountExceeded { count, limit } => {
+                format!(
+                    "Spawn count ({}) exceeds maximum allowed ({}). Spawn blocks and spawn points both count.",
+                    count, limit
+                )
+            }
+            ValidationError::InvalidTeam { name, reason } => {
+                format!("Invalid team '{}': {}.", name, reason)
+            }
+            ValidationError::InvalidSpawnPoint { index, reason } => {
+                format!("Invalid spawn point at index {}: {}.", index, reason)
+            }
+            ValidationError::UnknownTeam { team, source, index } => {
+                let location = match source {
+                    SpawnSource::Block => "Spawn block at block index",
+                    SpawnSource::SpawnPoint => "Spawn point at index",
+                };
+                format!(
+                    "{} {} references unknown team '{}'. Add it to 'teams' first.",
+                    location, index, team
+                )
+            }
+            ValidationError::FloatingBlocks { blocks } => {
+                format!(
+                    "{} block(s) starting at block index {} are not connected to the ground. Connect them to a grounded block or remove them.",
+                    blocks.len(),
+                    blocks.first().copied().unwrap_or_default()
+                )
+            }
         }
     }
 }
 
-/// Validates that schema version is 1 (only supported version).
+/// Validates that schema version is between 1 and [`CURRENT_SCHEMA_VERSION`].
 pub fn validate_schema_version(schema_version: u32) -> Result<(), ValidationError> {
-    if schema_version != 1 {
+    if !(1..=CURRENT_SCHEMA_VERSION).contains(&schema_version) {
         return Err(ValidationError::InvalidSchemaVersion { version: schema_version });
     }
     Ok(())
 }
 
-/// Maximum allowed blocks per level.
+/// Default maximum blocks per level (see [`ValidationLimits`]).
 pub const MAX_BLOCKS: usize = 10_000;
 
-/// Validates that block count does not exceed [`MAX_BLOCKS`].
-pub fn validate_block_count(count: usize) -> Result<(), ValidationError> {
-    if count > MAX_BLOCKS {
+/// Default maximum teams per level (see [`ValidationLimits`]).
+pub const MAX_TEAMS: usize = 32;
+
+/// Default maximum spawns per level, counting spawn blocks and spawn points
+/// (see [`ValidationLimits`]).
+pub const MAX_SPAWNS: usize = 100;
+
+/// Inclusive block coordinate bounds in Roblox studs (already scaled 2x from
+/// Three.js units). Defaults to X/Z: -1000 to 1000, Y: 0 to 1000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CoordinateBounds {
+    pub min_x: i32,
+    pub max_x: i32,
+    pub min_y: i32,
+    pub max_y: i32,
+    pub min_z: i32,
+    pub max_z: i32,
+}
+
+impl Default for CoordinateBounds {
+    fn default() -> Self {
+        CoordinateBounds {
+            min_x: -1000,
+            max_x: 1000,
+            min_y: 0,
+            max_y: 1000,
+            min_z: -1000,
+            max_z: 1000,
+        }
+    }
+}
+
+impl CoordinateBounds {
+    /// Returns `true` if a block position lies within the bounds.
+    pub fn contains(&self, x: i32, y: i32, z: i32) -> bool {
+        (self.min_x..=self.max_x).contains(&x)
+            && (self.min_y..=self.max_y).contains(&y)
+            && (self.min_z..=self.max_z).contains(&z)
+    }
+
+    /// Returns `true` if a position in studs lies within the bounds.
+    fn contains_point(&self, x: f32, y: f32, z: f32) -> bool {
+        (self.min_x as f32..=self.max_x as f32).contains(&x)
+            && (self.min_y as f32..=self.max_y as f32).contains(&y)
+            && (self.min_z as f32..=self.max_z as f32).contains(&z)
+    }
+}
+
+/// Formats the bounds as shown in error messages, e.g. `X/Z: -1000 to 1000, Y: 0 to 1000`.
+impl fmt::Display for CoordinateBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if (self.min_x, self.max_x) == (self.min_z, self.max_z) {
+            write!(
+                f,
+                "X/Z: {} to {}, Y: {} to {}",
+                self.min_x, self.max_x, self.min_y, self.max_y
+            )
+        } else {
+            write!(
+                f,
+                "X: {} to {}, Y: {} to {}, Z: {} to {}",
+                self.min_x, self.max_x, self.min_y, self.max_y, self.min_z, self.max_z
+            )
+        }
+    }
+}
+
+/// Size limits a level must stay within. Defaults to [`MAX_BLOCKS`] blocks
+/// within the default [`CoordinateBounds`], [`MAX_TEAMS`] teams and
+/// [`MAX_SPAWNS`] spawns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationLimits {
+    pub max_blocks: usize,
+    pub bounds: CoordinateBounds,
+    pub max_teams: usize,
+    /// Maximum spawn blocks and spawn points together.
+    pub max_spawns: usize,
+    /// Fail validation with [`ValidationError::FloatingBlocks`] instead of only
+    /// reporting floating islands. Off by default.
+    pub reject_floating_blocks: bool,
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        ValidationLimits {
+            max_blocks: MAX_BLOCKS,
+            bounds: CoordinateBounds::default(),
+            max_teams: MAX_TEAMS,
+            max_spawns: MAX_SPAWNS,
+            reject_floating_blocks: false,
+        }
+    }
+}
+
+/// Validates that block count does not exceed `limits.max_blocks`.
+pub fn validate_block_count(count: usize, limits: &ValidationLimits) -> Result<(), ValidationError> {
+    if count > limits.max_blocks {
         return Err(ValidationError::BlockCountExceeded {
             count,
-            limit: MAX_BLOCKS,
+            limit: limits.max_blocks,
         });
     }
     Ok(())
 }
 
-// Coordinate bounds (in Roblox studs, already scaled 2x from Three.js units)
-const MIN_X: i32 = -1000;
-const MAX_X: i32 = 1000;
-const MIN_Z: i32 = -1000;
-const MAX_Z: i32 = 1000;
-const MIN_Y: i32 = 0;
-const MAX_Y: i32 = 1000;
-
-/// Validates that block coordinates are within allowed bounds.
-///
-/// Bounds: X/Z: -1000 to 1000, Y: 0 to 1000 (in Roblox studs).
-pub fn validate_coordinate_bounds(x: i32, y: i32, z: i32, index: usize) -> Result<(), ValidationError> {
-    if x < MIN_X || x > MAX_X || z < MIN_Z || z > MAX_Z || y < MIN_Y || y > MAX_Y {
-        return Err(ValidationError::CoordinateOutOfBounds { x, y, z, index });
+/// Validates that block coordinates are within `bounds`.
+pub fn validate_coordinate_bounds(
+    x: i32,
+    y: i32,
+    z: i32,
+    index: usize,
+    bounds: &CoordinateBounds,
+) -> Result<(), ValidationError> {
+    if !bounds.contains(x, y, z) {
+        return Err(ValidationError::CoordinateOutOfBounds {
+            x,
+            y,
+            z,
+            index,
+            bounds: *bounds,
+        });
     }
     Ok(())
 }
 
+/// Returns `true` if `color` is a `#RRGGBB` or `#RGB` hex string.
+fn is_hex_color(color: &str) -> bool {
+    let Some(hex_part) = color.strip_prefix('#') else {
+        return false;
+    };
+    (hex_part.len() == 3 || hex_part.len() == 6) && hex_part.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 /// Validates hex color format (`#RRGGBB` or `#RGB`).
 pub fn validate_color_format(color: &str, index: usize) -> Result<(), ValidationError> {
-    if !color.starts_with('#') {
+    if !is_hex_color(color) {
         return Err(ValidationError::InvalidColorFormat {
             color: color.to_string(),
             index,
         });
     }
+    Ok(())
+}
 
-    let hex_part = &color[1..];
-    if hex_part.len() != 3 && hex_part.len() != 6 {
-        return Err(ValidationError::InvalidColorFormat {
-            color: color.to_string(),
+/// Maximum block size along any axis (Roblox's Part size limit), in studs.
+pub const MAX_BLOCK_SIZE: f32 = 2048.0;
+
+/// Validates a block's material name against the supported Roblox materials.
+pub fn validate_material(material: &str, index: usize) -> Result<(), ValidationError> {
+    if material_value(material).is_none() {
+        return Err(ValidationError::InvalidMaterial {
+            material: material.to_string(),
             index,
         });
     }
+    Ok(())
+}
 
-    if !hex_part.chars().all(|c| c.is_ascii_hexdigit()) {
-        return Err(ValidationError::InvalidColorFormat {
-            color: color.to_string(),
+/// Validates a block's gameplay kind against the built-in [`BlockKind`]s.
+pub fn validate_block_kind(kind: &str, index: usize) -> Result<(), ValidationError> {
+    if BlockKind::from_name(kind).is_none() {
+        return Err(ValidationError::InvalidBlockKind {
+            kind: kind.to_string(),
             index,
         });
     }
+    Ok(())
+}
+
+/// Validates that a fractional block property (transparency, reflectance) is within 0 to 1.
+pub fn validate_unit_interval(property: &'static str, value: f32, index: usize) -> Result<(), ValidationError> {
+    if !(0.0..=1.0).contains(&value) {
+        return Err(ValidationError::PropertyOutOfRange { property, value, index });
+    }
+    Ok(())
+}
+
+/// Validates that every block size dimension is positive and at most [`MAX_BLOCK_SIZE`].
+pub fn validate_block_size(size: BlockSize, index: usize) -> Result<(), ValidationError> {
+    let valid = |value: f32| value > 0.0 && value <= MAX_BLOCK_SIZE;
+    if !(valid(size.x) && valid(size.y) && valid(size.z)) {
+        return Err(ValidationError::InvalidBlockSize { size, index });
+    }
+    Ok(())
+}
+
+/// Validates a block's optional schema version 2 fields.
+///
+/// Returns one result per present field so callers can stop at the first
+/// error or collect them all. Version 1 payloads never have these fields:
+/// migration removes them.
+fn validate_block_properties(block: &Block, index: usize) -> Vec<Result<(), ValidationError>> {
+    let mut results = Vec::new();
+    if let Some(material) = &block.material {
+        results.push(validate_material(material, index));
+    }
+    if let Some(kind) = &block.kind {
+        results.push(validate_block_kind(kind, index));
+    }
+    if let Some(transparency) = block.transparency {
+        results.push(validate_unit_interval("transparency", transparency, index));
+    }
+    if let Some(reflectance) = block.reflectance {
+        results.push(validate_unit_interval("reflectance", reflectance, index));
+    }
+    if let Some(size) = block.size {
+        results.push(validate_block_size(size, index));
+    }
+    results
+}
 
+/// Validates a single block's coordinates, color and version 2 properties,
+/// stopping at the first error. `index` is reported in errors.
+pub fn validate_block(block: &Block, index: usize, limits: &ValidationLimits) -> Result<(), ValidationError> {
+    validate_coordinate_bounds(block.x, block.y, block.z, index, &limits.bounds)?;
+    validate_color_format(&block.color, index)?;
+    for result in validate_block_properties(block, index) {
+        result?;
+    }
     Ok(())
 }
 
+/// Maximum time of day in hours.
+const MAX_TIME_OF_DAY: f32 = 24.0;
+
+/// Roblox's default `Lighting.FogEnd`, used when only `fog.start` is given.
+const DEFAULT_FOG_END: f32 = 100_000.0;
+
+/// Validates the optional `world` section.
+///
+/// Returns one result per invalid setting so callers can stop at the first
+/// error or collect them all.
+fn validate_world(world: &WorldSettings, bounds: &CoordinateBounds) -> Vec<Result<(), ValidationError>> {
+    let mut results = Vec::new();
+    let mut check = |valid: bool, setting: &'static str, reason: String| {
+        if !valid {
+            results.push(Err(ValidationError::InvalidWorldSetting { setting, reason }));
+        }
+    };
+
+    if let Some(baseplate) = &world.baseplate {
+        if let Some(size) = baseplate.size {
+            let valid = |value: f32| value > 0.0 && value <= MAX_BLOCK_SIZE;
+            check(
+                valid(size.x) && valid(size.y) && valid(size.z),
+                "baseplate.size",
+                format!(
+                    "each dimension must be greater than 0 and at most {} studs",
+                    MAX_BLOCK_SIZE
+                ),
+            );
+        }
+        if let Some(color) = &baseplate.color {
+            check(is_hex_color(color), "baseplate.color", format!("'{}' is not a #RRGGBB or #RGB color", color));
+        }
+        if let Some(material) = &baseplate.material {
+            check(
+                material_value(material).is_some(),
+                "baseplate.material",
+                format!("unknown material '{}'", material),
+            );
+        }
+    }
+
+    if let Some(spawn) = &world.spawn {
+        check(
+            !(spawn.auto == Some(true) && spawn.position.is_some()),
+            "spawn",
+            "'auto' and 'position' cannot be combined".to_string(),
+        );
+        if let Some(position) = spawn.position {
+            check(
+                bounds.contains_point(position.x, position.y, position.z),
+                "spawn.position",
+                format!("must be within {}", bounds),
+            );
+        }
+    }
+
+    if let Some(lighting) = &world.lighting {
+        if let Some(technology) = &lighting.technology {
+            check(
+                technology_value(technology).is_some(),
+                "lighting.technology",
+                format!(
+                    "unknown technology '{}'. Expected Legacy, Voxel, Compatibility, ShadowMap or Future",
+                    technology
+                ),
+            );
+        }
+        if let Some(time_of_day) = lighting.time_of_day {
+            check(
+                (0.0..=MAX_TIME_OF_DAY).contains(&time_of_day),
+                "lighting.timeOfDay",
+                format!("{} is not an hour from 0 to 24", time_of_day),
+            );
+        }
+        if let Some(ambient) = &lighting.ambient {
+            check(is_hex_color(ambient), "lighting.ambient", format!("'{}' is not a #RRGGBB or #RGB color", ambient));
+        }
+        if let Some(fog) = &lighting.fog {
+            if let Some(color) = &fog.color {
+                check(is_hex_color(color), "lighting.fog.color", format!("'{}' is not a #RRGGBB or #RGB color", color));
+            }
+            let start = fog.start.unwrap_or(0.0);
+            let end = fog.end.unwrap_or(DEFAULT_FOG_END);
+            check(
+                start >= 0.0 && end >= start,
+                "lighting.fog",
+                "start must be at least 0 and end must not be less than start".to_string(),
+            );
+        }
+    }
+
+    results
+}
+
+/// Validates teams, spawn points and the team each spawn references.
+///
+/// Team names must be unique and non-empty, and every team must end up with a
+/// distinct BrickColor, since Roblox identifies teams by color. Returns one
+/// result per problem found. Levels over `limits.max_teams` teams or `limits.max_spawns` spawns are not
+/// checked further.
+fn validate_teams_and_spawns(space_json: &SpaceJSON, limits: &ValidationLimits) -> Vec<Result<(), ValidationError>> {
+    let team_count = space_json.teams.len();
+    let spawn_count = space_json.spawns.len() + space_json.blocks.iter().filter(|block| block.spawn.is_some()).count();
+    let mut results = Vec::new();
+    if team_count > limits.max_teams {
+        results.push(Err(ValidationError::TeamCountExceeded {
+            count: team_count,
+            limit: limits.max_teams,
+        }));
+    }
+    if spawn_count > limits.max_spawns {
+        results.push(Err(ValidationError::SpawnCountExceeded {
+            count: spawn_count,
+            limit: limits.max_spawns,
+        }));
+    }
+    if !results.is_empty() {
+        return results;
+    }
+
+    let bounds = &limits.bounds;
+    let invalid_team = |name: &str, reason: String| {
+        Err(ValidationError::InvalidTeam {
+            name: name.to_string(),
+            reason,
+        })
+    };
+
+    let colors: Vec<Option<&str>> = space_json.teams.iter().map(|team| team.color.as_deref()).collect();
+    let resolved = resolve_team_colors(&colors);
+    let mut names = HashSet::new();
+    let mut color_owners: HashMap<u16, &str> = HashMap::new();
+    for (team, color) in space_json.teams.iter().zip(resolved) {
+        if team.name.trim().is_empty() {
+            results.push(invalid_team(&team.name, "team name must not be empty".to_string()));
+        } else if !names.insert(team.name.as_str()) {
+            results.push(invalid_team(&team.name, "team names must be unique".to_string()));
+        }
+
+        match color {
+            None => results.push(invalid_team(
+                &team.name,
+                format!("'{}' is not a #RRGGBB or #RGB color", team.color.as_deref().unwrap_or_default()),
+            )),
+            Some(number) => {
+                if let Some(owner) = color_owners.insert(number, &team.name) {
+                    results.push(invalid_team(
+                        &team.name,
+                        format!(
+                            "its color maps to BrickColor '{}', which team '{}' already uses",
+                            brick_color_name(number).unwrap_or_default(),
+                            owner
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut check_team = |team: Option<&String>, source: SpawnSource, index: usize| {
+        if let Some(team) = team {
+            if !names.contains(team.as_str()) {
+                results.push(Err(ValidationError::UnknownTeam {
+                    team: team.clone(),
+                    source,
+                    index,
+                }));
+            }
+        }
+    };
+
+    for (index, block) in space_json.blocks.iter().enumerate() {
+        if let Some(spawn) = &block.spawn {
+            check_team(spawn.team.as_ref(), SpawnSource::Block, index);
+        }
+    }
+    for (index, spawn) in space_json.spawns.iter().enumerate() {
+        check_team(spawn.team.as_ref(), SpawnSource::SpawnPoint, index);
+    }
+
+    let has_spawns = !space_json.spawns.is_empty() || space_json.blocks.iter().any(|block| block.spawn.is_some());
+    let has_default_spawn_settings = space_json.world.as_ref().is_some_and(|world| world.spawn.is_some());
+    if has_spawns && has_default_spawn_settings {
+        results.push(Err(ValidationError::InvalidWorldSetting {
+            setting: "spawn",
+            reason: "the default spawn cannot be configured when spawn blocks or spawn points are used".to_string(),
+        }));
+    }
+
+    for (index, spawn) in space_json.spawns.iter().enumerate() {
+        if !bounds.contains_point(spawn.x, spawn.y, spawn.z) {
+            results.push(Err(ValidationError::InvalidSpawnPoint {
+                index,
+                reason: format!("position must be within {}", bounds),
+            }));
+        }
+    }
+
+    results
+}
+
 /// Validates that no two blocks occupy the same position.
-pub fn validate_no_duplicates(blocks: &[crate::models::Block]) -> Result<(), ValidationError> {
+pub fn validate_no_duplicates(blocks: &[Block]) -> Result<(), ValidationError> {
+    match find_duplicates(blocks).into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Returns a [`ValidationError::DuplicatePosition`] for every block that repeats
+/// an earlier block's position.
+fn find_duplicates(blocks: &[Block]) -> Vec<ValidationError> {
     let mut positions = HashSet::new();
-    
+    let mut duplicates = Vec::new();
+
     for (index, block) in blocks.iter().enumerate() {
         let position = (block.x, block.y, block.z);
-        if positions.contains(&position) {
-            return Err(ValidationError::DuplicatePosition {
+        if !positions.insert(position) {
+            duplicates.push(ValidationError::DuplicatePosition {
                 x: block.x,
                 y: block.y,
                 z: block.z,
                 index,
             });
         }
-        positions.insert(position);
     }
-    
-    Ok(())
+
+    duplicates
+}
+
+/// A group of face-connected blocks with no path to the ground.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FloatingIsland {
+    /// Indices of the island's blocks, in ascending order.
+    pub blocks: Vec<usize>,
+}
+
+/// Returns the representative of `index`'s set, compressing the path to it.
+fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+/// Finds groups of blocks that are not supported by the ground.
+///
+/// Blocks are connected when their Parts share part of a face, so blocks of
+/// any `size` are handled. A group is grounded when any of its blocks reaches
+/// down to Y=0, the top of the baseplate; every other group is a floating
+/// island. Levels whose `world.baseplate` is disabled have no ground, so every
+/// group is an island. Islands are ordered by their lowest block index.
+pub fn find_floating_islands(space_json: &SpaceJSON) -> Vec<FloatingIsland> {
+    let blocks = &space_json.blocks;
+    let has_ground = baseplate_size(space_json.world.as_ref().and_then(|world| world.baseplate.as_ref())).is_some();
+    let boxes: Vec<_> = blocks.iter().map(block_box).collect();
+    let mut parents: Vec<usize> = (0..blocks.len()).collect();
+
+    // Sweep along the longest axis so only blocks whose ranges overlap on it are compared
+    let spread = |axis: usize| {
+        let (min, max) = boxes.iter().fold((f32::MAX, f32::MIN), |(min, max), (low, high)| {
+            (min.min(low[axis]), max.max(high[axis]))
+        });
+        max - min
+    };
+    let axis = (0..3).max_by(|&a, &b| spread(a).total_cmp(&spread(b))).unwrap_or(0);
+    let mut order: Vec<usize> = (0..blocks.len()).collect();
+    order.sort_by(|&a, &b| boxes[a].0[axis].total_cmp(&boxes[b].0[axis]));
+    for (position, &a) in order.iter().enumerate() {
+        for &b in &order[position + 1..] {
+            if boxes[b].0[axis] > boxes[a].1[axis] + CONTACT_EPSILON {
+                break;
+            }
+            if boxes_touch(&boxes[a], &boxes[b]) {
+                let (root_a, root_b) = (find_root(&mut parents, a), find_root(&mut parents, b));
+                parents[root_a] = root_b;
+            }
+        }
+    }
+
+    let mut components: Vec<(usize, Vec<usize>)> = Vec::new();
+    let mut component_of_root = HashMap::new();
+    let mut grounded = HashSet::new();
+    for (index, (bottom, _)) in boxes.iter().enumerate() {
+        let root = find_root(&mut parents, index);
+        if has_ground && bottom[1] <= CONTACT_EPSILON {
+            grounded.insert(root);
+        }
+        let component = *component_of_root.entry(root).or_insert_with(|| {
+            components.push((root, Vec::new()));
+            components.len() - 1
+        });
+        components[component].1.push(index);
+    }
+
+    components
+        .into_iter()
+        .filter(|(root, _)| !grounded.contains(root))
+        .map(|(_, blocks)| FloatingIsland { blocks })
+        .collect()
+}
+
+/// Validates that no blocks float, if `limits.reject_floating_blocks` is set.
+///
+/// Returns one result per floating island.
+fn validate_structure(space_json: &SpaceJSON, limits: &ValidationLimits) -> Vec<Result<(), ValidationError>> {
+    if !limits.reject_floating_blocks {
+        return Vec::new();
+    }
+    find_floating_islands(space_json)
+        .into_iter()
+        .map(|island| Err(ValidationError::FloatingBlocks { blocks: island.blocks }))
+        .collect()
 }
 
 /// Validates an entire Space JSON payload.
@@ -151,17 +745,245 @@ pub fn validate_no_duplicates(blocks: &[crate::models::Block]) -> Result<(), Val
 /// Performs all validations in order, returning the first error found (fail-fast):
 /// 1. Schema version
 /// 2. Block count
-/// 3. Each block's coordinates and color
+/// 3. Each block's coordinates, color and version 2 properties
 /// 4. No duplicate positions
-pub fn validate_space_json(space_json: &SpaceJSON) -> Result<(), ValidationError> {
+/// 5. World settings
+/// 6. Team and spawn counts, then teams and spawns
+/// 7. No floating blocks, if `limits.reject_floating_blocks` is set
+///
+/// Block, team and spawn counts and positions are checked against `limits`; pass
+/// `&ValidationLimits::default()` for the standard limits.
+pub fn validate_space_json(space_json: &SpaceJSON, limits: &ValidationLimits) -> Result<(), ValidationError> {
     validate_schema_version(space_json.schema_version)?;
-    validate_block_count(space_json.blocks.len())?;
+    validate_block_count(space_json.blocks.len(), limits)?;
 
     for (index, block) in space_json.blocks.iter().enumerate() {
-        validate_coordinate_bounds(block.x, block.y, block.z, index)?;
-        validate_color_format(&block.color, index)?;
+        validate_block(block, index, limits)?;
     }
 
     validate_no_duplicates(&space_json.blocks)?;
+
+    if let Some(world) = &space_json.world {
+        for result in validate_world(world, &limits.bounds) {
+            result?;
+        }
+    }
+
+    for result in validate_teams_and_spawns(space_json, limits) {
+        result?;
+    }
+
+    for result in validate_structure(space_json, limits) {
+        result?;
+    }
     Ok(())
 }
+
+/// Maximum number of errors kept per error code by [`validate_space_json_all`].
+///
+/// Further errors with the same code are counted but not stored.
+pub const MAX_ERRORS_PER_CODE: usize = 50;
+
+/// All collected errors sharing one [`ValidationError::error_code`].
+#[derive(Debug, Clone)]
+pub struct ValidationErrorGroup {
+    /// Machine-readable error code shared by every error in the group.
+    pub code: &'static str,
+    /// Total number of errors found with this code, including any beyond the cap.
+    pub count: usize,
+    /// The first [`MAX_ERRORS_PER_CODE`] errors, in payload order.
+    pub errors: Vec<ValidationError>,
+}
+
+/// Accumulates errors into capped groups, one per error code.
+#[derive(Default)]
+struct ErrorCollector {
+    groups: Vec<ValidationErrorGroup>,
+}
+
+impl ErrorCollector {
+    fn check(&mut self, result: Result<(), ValidationError>) {
+        if let Err(error) = result {
+            self.push(error);
+        }
+    }
+
+    fn push(&mut self, error: ValidationError) {
+        let code = error.error_code();
+        let group = match self.groups.iter().position(|group| group.code == code) {
+            Some(position) => &mut self.groups[position],
+            None => {
+                self.groups.push(ValidationErrorGroup {
+                    code,
+                    count: 0,
+                    errors: Vec::new(),
+                });
+                self.groups.last_mut().unwrap()
+            }
+        };
+
+        group.count += 1;
+        if group.errors.len() < MAX_ERRORS_PER_CODE {
+            group.errors.push(error);
+        }
+    }
+
+    /// Returns the groups ordered by validation check, or `Ok` if nothing was collected.
+    fn finish(mut self) -> Result<(), Vec<ValidationErrorGroup>> {
+        if self.groups.is_empty() {
+            return Ok(());
+        }
+        self.groups.sort_by_key(|group| group.errors[0].check_order());
+        Err(self.groups)
+    }
+}
+
+/// Validates an entire Space JSON payload, collecting every error.
+///
+/// Runs the same checks as [`validate_space_json`] but keeps going after a
+/// failure. Errors are grouped by error code in validation order, and each
+/// group stores at most [`MAX_ERRORS_PER_CODE`] errors.
+pub fn validate_space_json_all(
+    space_json: &SpaceJSON,
+    limits: &ValidationLimits,
+) -> Result<(), Vec<ValidationErrorGroup>> {
+    let mut collector = ErrorCollector::default();
+
+    collector.check(validate_schema_version(space_json.schema_version));
+    collector.check(validate_block_count(space_json.blocks.len(), limits));
+
+    for (index, block) in space_json.blocks.iter().enumerate() {
+        collector.check(validate_coordinate_bounds(block.x, block.y, block.z, index, &limits.bounds));
+        collector.check(validate_color_format(&block.color, index));
+        for result in validate_block_properties(block, index) {
+            collector.check(result);
+        }
+    }
+
+    for duplicate in find_duplicates(&space_json.blocks) {
+        collector.push(duplicate);
+    }
+
+    if let Some(world) = &space_json.world {
+        for result in validate_world(world, &limits.bounds) {
+            collector.check(result);
+        }
+    }
+
+    for result in validate_teams_and_spawns(space_json, limits) {
+        collector.check(result);
+    }
+
+    for result in validate_structure(space_json, limits) {
+        collector.check(result);
+    }
+
+    collector.finish()
+}
+
+/// Level name the editor assigns to new levels.
+pub const DEFAULT_LEVEL_NAME: &str = "Untitled Level";
+
+/// A soft issue that does not stop a level from being exported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationWarning {
+    /// Blocks whose color uses the `#RGB` shorthand.
+    ShortHexColors { blocks: Vec<usize> },
+    /// The level has no name, or still has the editor's default name.
+    DefaultLevelName,
+    /// A group of blocks not connected to the ground (see [`find_floating_islands`]).
+    FloatingBlocks { blocks: Vec<usize> },
+    /// Blocks inside a generated SpawnLocation's pad or player clearance.
+    /// Only reported for exports that keep obstructed spawns as they are.
+    SpawnOverlap { blocks: Vec<usize> },
+}
+
+impl ValidationWarning {
+    /// Returns a machine-readable warning code for API responses.
+    pub fn warning_code(&self) -> &'static str {
+        match self {
+            ValidationWarning::ShortHexColors { .. } => "SHORT_HEX_COLOR",
+            ValidationWarning::DefaultLevelName => "DEFAULT_LEVEL_NAME",
+            ValidationWarning::FloatingBlocks { .. } => "FLOATING_BLOCKS",
+            ValidationWarning::SpawnOverlap { .. } => "SPAWN_OVERLAP",
+        }
+    }
+
+    /// Returns the indices of the blocks the warning is about, if any.
+    pub fn blocks(&self) -> &[usize] {
+        match self {
+            ValidationWarning::ShortHexColors { blocks }
+            | ValidationWarning::FloatingBlocks { blocks }
+            | ValidationWarning::SpawnOverlap { blocks } => blocks,
+            ValidationWarning::DefaultLevelName => &[],
+        }
+    }
+
+    /// Returns a human-readable warning message for display to users.
+    pub fn message(&self) -> String {
+        let first = self.blocks().first().copied().unwrap_or_default();
+        match self {
+            ValidationWarning::ShortHexColors { blocks } => {
+                format!(
+                    "{} block(s) starting at block index {} use #RGB shorthand colors. Use #RRGGBB to keep colors exact when editing.",
+                    blocks.len(),
+                    first
+                )
+            }
+            ValidationWarning::DefaultLevelName => {
+                format!("The level is named '{}'. Give it a name before sharing it.", DEFAULT_LEVEL_NAME)
+            }
+            ValidationWarning::FloatingBlocks { blocks } => {
+                format!(
+                    "{} block(s) starting at block index {} are not connected to the ground.",
+                    blocks.len(),
+                    first
+                )
+            }
+            ValidationWarning::SpawnOverlap { blocks } => {
+                format!(
+                    "{} block(s) starting at block index {} intersect a SpawnLocation or the space above it, so players may spawn inside them.",
+                    blocks.len(),
+                    first
+                )
+            }
+        }
+    }
+}
+
+/// Collects soft issues with a level that does not need to be valid.
+///
+/// Floating islands are only warnings when `limits.reject_floating_blocks` is
+/// off (otherwise they are errors), and at most [`MAX_ERRORS_PER_CODE`] are
+/// reported. [`ValidationWarning::SpawnOverlap`] depends on how the level is
+/// exported, so it is left to `rbxlx::export_warnings`.
+pub fn collect_warnings(space_json: &SpaceJSON, limits: &ValidationLimits) -> Vec<ValidationWarning> {
+    let mut warnings = Vec::new();
+
+    let short_colors: Vec<usize> = space_json
+        .blocks
+        .iter()
+        .enumerate()
+        .filter(|(_, block)| is_hex_color(&block.color) && block.color.len() == 4)
+        .map(|(index, _)| index)
+        .collect();
+    if !short_colors.is_empty() {
+        warnings.push(ValidationWarning::ShortHexColors { blocks: short_colors });
+    }
+
+    let name = space_json.name.as_deref().map(str::trim).unwrap_or_default();
+    if name.is_empty() || name.eq_ignore_ascii_case(DEFAULT_LEVEL_NAME) {
+        warnings.push(ValidationWarning::DefaultLevelName);
+    }
+
+    if !limits.reject_floating_blocks {
+        warnings.extend(
+            find_floating_islands(space_json)
+                .into_iter()
+                .take(MAX_ERRORS_PER_CODE)
+                .map(|island| ValidationWarning::FloatingBlocks { blocks: island.blocks }),
+        );
+    }
+
+    warnings
+}
@@ -0,0 +1,136 @@
+//! Tests for API key and bearer token authentication.
+
+use backend::auth::{
+    issue_token, parse_api_keys, parse_scopes, required_scope, verify_token, AuthConfig, Scope, TokenClaims,
+};
+
+const SECRET: &[u8] = b"test-secret";
+
+fn config() -> AuthConfig {
+    AuthConfig {
+        api_keys: parse_api_keys("ci:ci-key:export; ops:ops-key:admin").unwrap(),
+        token_secret: Some(SECRET.to_vec()),
+    }
+}
+
+fn token(scopes: Vec<Scope>, expires_at: u64) -> String {
+    issue_token(
+        SECRET,
+        &TokenClaims {
+            subject: "editor".to_string(),
+            scopes,
+            expires_at,
+            tier: None,
+        },
+    )
+}
+
+#[test]
+fn test_api_keys_authenticate_with_their_scopes() {
+    let config = config();
+    let principal = config.authenticate(Some("ci-key"), None, 0).unwrap();
+    assert_eq!(principal.subject, "ci");
+    assert!(principal.has_scope(Scope::Export));
+    assert!(!principal.has_scope(Scope::Store));
+
+    // Admin grants every scope
+    let admin = config.authenticate(Some("ops-key"), None, 0).unwrap();
+    assert!(admin.has_scope(Scope::Store));
+    assert!(admin.has_scope(Scope::Export));
+
+    let error = config.authenticate(Some("wrong"), None, 0).unwrap_err();
+    assert_eq!(error.error_code(), "INVALID_CREDENTIALS");
+    assert!(!error.is_forbidden());
+}
+
+#[test]
+fn test_bearer_tokens_authenticate_until_expiry() {
+    let config = config();
+    let header = format!("Bearer {}", token(vec![Scope::Store], 1_000));
+
+    let principal = config.authenticate(None, Some(&header), 999).unwrap();
+    assert_eq!(principal.subject, "editor");
+    assert!(principal.has_scope(Scope::Store));
+
+    let error = config.authenticate(None, Some(&header), 1_000).unwrap_err();
+    assert_eq!(error.error_code(), "TOKEN_EXPIRED");
+}
+
+#[test]
+fn test_tampered_and_foreign_tokens_are_rejected() {
+    let config = config();
+    let valid = token(vec![Scope::Export], u64::MAX);
+
+    // Swap in claims granting admin, keeping the original signature
+    let (_, signature) = valid.split_once('.').unwrap();
+    let forged_claims = token(vec![Scope::Admin], u64::MAX);
+    let (forged_payload, _) = forged_claims.split_once('.').unwrap();
+    let forged = format!("{}.{}", forged_payload, signature);
+    assert!(verify_token(SECRET, &forged).is_err());
+
+    let foreign = issue_token(
+        b"other-secret",
+        &TokenClaims {
+            subject: "mallory".to_string(),
+            scopes: vec![Scope::Admin],
+            expires_at: u64::MAX,
+            tier: None,
+        },
+    );
+    for bad in [forged, foreign, "not-a-token".to_string()] {
+        let header = format!("Bearer {}", bad);
+        assert_eq!(
+            config.authenticate(None, Some(&header), 0).unwrap_err().error_code(),
+            "INVALID_CREDENTIALS"
+        );
+    }
+
+    // Tokens are rejected outright when no secret is configured
+    let keys_only = AuthConfig {
+        token_secret: None,
+        ..config
+    };
+    let header = format!("Bearer {}", valid);
+    assert!(keys_only.authenticate(None, Some(&header), 0).is_err());
+}
+
+#[test]
+fn test_missing_credentials() {
+    let error = config().authenticate(None, None, 0).unwrap_err();
+    assert_eq!(error.error_code(), "UNAUTHORIZED");
+    assert!(!AuthConfig::default().is_enabled());
+    assert!(config().is_enabled());
+}
+
+#[test]
+fn test_route_scopes() {
+    assert_eq!(required_scope("GET", "/health"), None);
+    assert_eq!(required_scope("GET", "/api/health"), None);
+    assert_eq!(required_scope("GET", "/api/shared/abc"), None);
+    assert_eq!(required_scope("OPTIONS", "/api/export"), None);
+    assert_eq!(required_scope("POST", "/api/export"), Some(Scope::Export));
+    assert_eq!(required_scope("POST", "/api/merge"), Some(Scope::Export));
+    assert_eq!(required_scope("POST", "/api/playability"), Some(Scope::Export));
+    assert_eq!(required_scope("GET", "/api/levels"), Some(Scope::Store));
+    assert_eq!(required_scope("GET", "/api/levels/abc/collab"), Some(Scope::Store));
+    assert_eq!(required_scope("POST", "/api/tokens"), Some(Scope::Admin));
+    assert_eq!(required_scope("GET", "/api/unknown"), Some(Scope::Admin));
+}
+
+#[test]
+fn test_parse_api_keys_and_scopes() {
+    let keys = parse_api_keys("a:key-a:export,store;b:key-b:export:pro").unwrap();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0].scopes, vec![Scope::Export, Scope::Store]);
+    assert_eq!(keys[0].tier, None);
+    assert_eq!(keys[1].tier.as_deref(), Some("pro"));
+    assert!(parse_api_keys("b:key-b:export:").is_err());
+    assert!(parse_api_keys("").unwrap().is_empty());
+
+    let error = parse_api_keys("a:secret-key").unwrap_err();
+    assert!(!error.contains("secret-key"), "errors must not echo keys: {}", error);
+    assert!(parse_api_keys("a:key:superuser").is_err());
+    assert!(parse_api_keys("a::export").is_err());
+    assert!(parse_scopes("").is_err());
+    assert_eq!(parse_scopes(" Admin ").unwrap(), vec![Scope::Admin]);
+}
@@ -0,0 +1,158 @@
+//! Tests for the `level-cli` command-line exporter.
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+const VALID_LEVEL: &str = r##"{"schemaVersion": 1, "blocks": [{"x": 0, "y": 2, "z": 0, "color": "#FF0000"}]}"##;
+
+fn cli() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_level-cli"))
+}
+
+/// Runs the CLI with `stdin` piped in.
+fn run_with_stdin(args: &[&str], stdin: &str) -> Output {
+    let mut child = cli()
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start level-cli");
+    // The CLI may exit before reading stdin (e.g. on usage errors), closing the pipe
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+/// Creates an empty scratch directory unique to the test.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("level-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_cli_converts_stdin_to_stdout() {
+    let output = run_with_stdin(&[], VALID_LEVEL);
+
+    assert_eq!(output.status.code(), Some(0));
+    let xml = String::from_utf8(output.stdout).unwrap();
+    assert!(xml.contains("<roblox"));
+    assert!(xml.contains("Block0"));
+}
+
+#[test]
+fn test_cli_binary_format() {
+    let output = run_with_stdin(&["--format", "rbxl"], VALID_LEVEL);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.starts_with(b"<roblox!"));
+}
+
+#[test]
+fn test_cli_validation_exit_codes() {
+    let cases = [
+        (r#"{"schemaVersion": 9, "blocks": []}"#, 3),
+        (r#"{"schemaVersion": 1, "blocks": [{"x": 0, "y": 0, "z": 0, "color": "red"}]}"#, 13),
+        (r##"{"schemaVersion": 1, "blocks": [{"x": 5000, "y": 0, "z": 0, "color": "#FFF"}]}"##, 12),
+        (r##"{"schemaVersion": 2, "blocks": [{"x": 0, "y": 0, "z": 0, "color": "#FFF", "material": "Lava"}]}"##, 16),
+        ("not json", 3),
+    ];
+    for (payload, code) in cases {
+        let output = run_with_stdin(&[], payload);
+        assert_eq!(output.status.code(), Some(code), "payload: {}", payload);
+        assert!(output.stdout.is_empty());
+    }
+}
+
+#[test]
+fn test_cli_usage_errors() {
+    assert_eq!(run_with_stdin(&["--format", "obj"], VALID_LEVEL).status.code(), Some(1));
+    assert_eq!(run_with_stdin(&["--bogus"], VALID_LEVEL).status.code(), Some(1));
+    assert_eq!(run_with_stdin(&["--help"], "").status.code(), Some(0));
+}
+
+#[test]
+fn test_cli_max_blocks() {
+    let two_blocks = r##"{"schemaVersion": 1, "blocks": [{"x": 0, "y": 2, "z": 0, "color": "#FF0000"}, {"x": 2, "y": 2, "z": 0, "color": "#FF0000"}]}"##;
+    assert_eq!(run_with_stdin(&["--max-blocks", "2"], two_blocks).status.code(), Some(0));
+    let output = run_with_stdin(&["--max-blocks", "1"], two_blocks);
+    assert_eq!(output.status.code(), Some(11));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("maximum allowed (1)"));
+    assert_eq!(run_with_stdin(&["--max-blocks", "0"], VALID_LEVEL).status.code(), Some(1));
+}
+
+#[test]
+fn test_cli_strict_rejects_floating_blocks() {
+    let floating = r##"{"schemaVersion": 1, "blocks": [{"x": 0, "y": 0, "z": 0, "color": "#FF0000"}, {"x": 0, "y": 8, "z": 0, "color": "#FF0000"}]}"##;
+    assert_eq!(run_with_stdin(&[], floating).status.code(), Some(0));
+    let output = run_with_stdin(&["--strict"], floating);
+    assert_eq!(output.status.code(), Some(25));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("block index 1"));
+}
+
+#[test]
+fn test_cli_prints_warnings() {
+    let floating = r##"{"schemaVersion": 1, "name": "Tower", "blocks": [{"x": 20, "y": 8, "z": 0, "color": "#F00"}]}"##;
+    let output = run_with_stdin(&[], floating);
+    assert_eq!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("<stdin>: warning: SHORT_HEX_COLOR"), "{}", stderr);
+    assert!(stderr.contains("<stdin>: warning: FLOATING_BLOCKS"), "{}", stderr);
+}
+
+#[test]
+fn test_cli_prints_migrations() {
+    let level = r##"{"schemaVersion": 1, "name": "Tower", "world": {}, "blocks": [{"x": 0, "y": 0, "z": 0, "color": "#FF0000"}]}"##;
+    let output = run_with_stdin(&[], level);
+    assert_eq!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("<stdin>: migration: Removed field 'world'"), "{}", stderr);
+}
+
+#[test]
+fn test_cli_batch_glob() {
+    let dir = scratch_dir("batch");
+    fs::write(dir.join("first.json"), VALID_LEVEL).unwrap();
+    fs::write(dir.join("second.json"), VALID_LEVEL).unwrap();
+    let out_dir = dir.join("out");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let pattern = dir.join("*.json");
+    let output = cli()
+        .args([pattern.to_str().unwrap(), "--format", "rbxmx", "--out-dir", out_dir.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    let model = fs::read_to_string(out_dir.join("first.rbxmx")).unwrap();
+    assert!(model.contains("Model"));
+    assert!(out_dir.join("second.rbxmx").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_cli_batch_reports_first_failure() {
+    let dir = scratch_dir("failure");
+    fs::write(dir.join("a.json"), r#"{"schemaVersion": 1, "blocks": [{"x": 0, "y": 0, "z": 0, "color": "red"}]}"#).unwrap();
+    fs::write(dir.join("b.json"), VALID_LEVEL).unwrap();
+
+    let output = cli().arg(dir.join("*.json").to_str().unwrap()).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(13));
+    // The valid file is still converted, next to its input
+    assert!(dir.join("b.rbxlx").exists());
+    assert!(!dir.join("a.rbxlx").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_cli_unmatched_glob() {
+    let dir = scratch_dir("unmatched");
+    let output = cli().arg(dir.join("*.json").to_str().unwrap()).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    fs::remove_dir_all(&dir).unwrap();
+}
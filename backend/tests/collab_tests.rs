@@ -0,0 +1,279 @@
+//! Tests for collaborative editing: operation checks and room membership.
+
+mod common;
+
+use backend::collab::{save_edits, CollabRooms, LiveLevel, Operation, ServerMessage};
+use backend::models::{Block, BlockSpawn, SpaceJSON};
+use backend::storage::{LevelStore, SqliteLevelStore, StoredLevel};
+use backend::validation::ValidationLimits;
+use common::{block, level};
+
+fn add(x: i32, color: &str) -> Operation {
+    Operation::Add { block: block(x, 0, 0, color) }
+}
+
+/// A level as loaded from storage at `revision`.
+fn stored(space: SpaceJSON, revision: u32) -> StoredLevel {
+    StoredLevel {
+        id: "level".to_string(),
+        created_at: 0,
+        updated_at: 0,
+        revision,
+        space,
+    }
+}
+
+#[test]
+fn test_operations_apply_in_order() {
+    let mut live = LiveLevel::new(level(vec![block(0, 0, 0, "#FF0000")]));
+    assert_eq!(live.apply(&add(2, "#00FF00"), &ValidationLimits::default()).unwrap(), 1);
+    assert_eq!(
+        live.apply(
+            &Operation::Recolor {
+                x: 0,
+                y: 0,
+                z: 0,
+                color: "#0000FF".to_string()
+            },
+            &ValidationLimits::default()
+        )
+        .unwrap(),
+        2
+    );
+    assert_eq!(live.apply(&Operation::Remove { x: 2, y: 0, z: 0 }, &ValidationLimits::default()).unwrap(), 3);
+
+    assert_eq!(live.version(), 3);
+    assert_eq!(live.space().blocks.len(), 1);
+    assert_eq!(live.space().blocks[0].color, "#0000FF");
+}
+
+#[test]
+fn test_invalid_operations_are_rejected_without_changes() {
+    let mut live = LiveLevel::new(level(vec![block(0, 0, 0, "#FF0000")]));
+
+    let cases = [
+        (add(0, "#FFFFFF"), "DUPLICATE_POSITION"),
+        (add(5000, "#FFFFFF"), "COORDINATE_OUT_OF_BOUNDS"),
+        (add(2, "red"), "INVALID_COLOR_FORMAT"),
+        (Operation::Remove { x: 2, y: 0, z: 0 }, "NO_BLOCK_AT_POSITION"),
+        (
+            Operation::Recolor {
+                x: 0,
+                y: 0,
+                z: 0,
+                color: "#GGGGGG".to_string(),
+            },
+            "INVALID_COLOR_FORMAT",
+        ),
+    ];
+    for (operation, code) in cases {
+        let error = live.apply(&operation, &ValidationLimits::default()).unwrap_err();
+        assert_eq!(error.error_code(), code, "{:?}", operation);
+    }
+    assert_eq!(live.version(), 0);
+    assert_eq!(live.space().blocks[0].color, "#FF0000");
+}
+
+#[test]
+fn test_added_spawn_blocks_must_reference_known_teams() {
+    let mut live = LiveLevel::new(level(vec![]));
+    let spawn = Block {
+        spawn: Some(BlockSpawn {
+            team: Some("Red".to_string()),
+        }),
+        ..block(0, 0, 0, "#FF0000")
+    };
+    let error = live.apply(&Operation::Add { block: spawn }, &ValidationLimits::default()).unwrap_err();
+    assert_eq!(error.error_code(), "UNKNOWN_TEAM");
+    assert!(live.space().blocks.is_empty());
+}
+
+#[test]
+fn test_operations_are_checked_against_the_senders_limits() {
+    let mut live = LiveLevel::new(level(vec![block(0, 0, 0, "#FF0000")]));
+    let free_tier = ValidationLimits {
+        max_blocks: 1,
+        ..ValidationLimits::default()
+    };
+    let error = live.apply(&add(2, "#00FF00"), &free_tier).unwrap_err();
+    assert_eq!(error.error_code(), "BLOCK_COUNT_EXCEEDED");
+    assert_eq!(live.apply(&add(2, "#00FF00"), &ValidationLimits::default()).unwrap(), 1);
+}
+
+#[test]
+fn test_operation_json_shape() {
+    let operation: Operation =
+        serde_json::from_str(r##"{ "op": "recolor", "x": 2, "y": 4, "z": 6, "color": "#FFF" }"##).unwrap();
+    assert_eq!(
+        operation,
+        Operation::Recolor {
+            x: 2,
+            y: 4,
+            z: 6,
+            color: "#FFF".to_string()
+        }
+    );
+
+    let applied = ServerMessage::Applied {
+        version: 1,
+        operation: Operation::Remove { x: 0, y: 0, z: 0 },
+    };
+    assert_eq!(
+        serde_json::to_value(&applied).unwrap(),
+        serde_json::json!({ "type": "applied", "version": 1, "operation": { "op": "remove", "x": 0, "y": 0, "z": 0 } })
+    );
+}
+
+#[test]
+fn test_room_broadcasts_to_every_client() {
+    let rooms = CollabRooms::new();
+    let mut first = rooms.join("level", || Ok::<_, ()>(stored(level(vec![]), 1))).unwrap();
+    first.room.apply(add(0, "#FF0000"), &ValidationLimits::default()).unwrap();
+
+    // Later clients start from a snapshot that includes earlier operations
+    let mut second = rooms
+        .join("level", || -> Result<StoredLevel, ()> { panic!("room is already open") })
+        .unwrap();
+    match &second.snapshot {
+        ServerMessage::Snapshot { version, space } => {
+            assert_eq!(*version, 1);
+            assert_eq!(space.blocks.len(), 1);
+        }
+        other => panic!("expected snapshot, got {:?}", other),
+    }
+
+    second.room.apply(add(2, "#00FF00"), &ValidationLimits::default()).unwrap();
+    assert!(matches!(first.updates.try_recv(), Ok(ServerMessage::Applied { version: 1, .. })));
+    assert!(matches!(first.updates.try_recv(), Ok(ServerMessage::Applied { version: 2, .. })));
+    assert!(matches!(second.updates.try_recv(), Ok(ServerMessage::Applied { version: 2, .. })));
+    assert!(second.updates.try_recv().is_err());
+    assert_eq!(rooms.get("level").unwrap().space().blocks.len(), 2);
+}
+
+#[test]
+fn test_last_client_leaving_saves_edits() {
+    let rooms = CollabRooms::new();
+    let first = rooms.join("level", || Ok::<_, ()>(stored(level(vec![]), 1))).unwrap();
+    let _second = rooms.join("level", || Ok::<_, ()>(stored(level(vec![]), 1))).unwrap();
+    first.room.apply(add(0, "#FF0000"), &ValidationLimits::default()).unwrap();
+
+    let mut saved = None;
+    rooms
+        .leave("level", |space, revision| {
+            saved = Some((space.clone(), revision));
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    assert!(saved.is_none(), "room is still open");
+
+    rooms
+        .leave("level", |space, revision| {
+            saved = Some((space.clone(), revision));
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    // The room reports the revision it was opened at, for conflict checks
+    let (space, revision) = saved.unwrap();
+    assert_eq!((space.blocks.len(), revision), (1, 1));
+    assert!(rooms.get("level").is_none());
+}
+
+#[test]
+fn test_unedited_rooms_are_not_saved() {
+    let rooms = CollabRooms::new();
+    rooms.join("level", || Ok::<_, ()>(stored(level(vec![]), 1))).unwrap();
+    rooms
+        .leave("level", |_, _| -> Result<(), ()> { panic!("nothing to save") })
+        .unwrap();
+
+    // Loading runs without the room list locked
+    rooms
+        .join("other", || {
+            assert!(rooms.get("other").is_none());
+            Ok::<_, ()>(stored(level(vec![]), 1))
+        })
+        .unwrap();
+
+    // Failing to load closes nothing and opens nothing
+    assert!(rooms.join("missing", || Err("not found")).is_err());
+    assert!(rooms.get("missing").is_none());
+}
+
+/// A store holding one level, `level`, at revision 1 with a red block at X=0.
+fn store_with_level() -> SqliteLevelStore {
+    let store = SqliteLevelStore::open_in_memory().unwrap();
+    store.put("level", level(vec![block(0, 0, 0, "#FF0000")])).unwrap();
+    store
+}
+
+#[test]
+fn test_save_edits_saves_a_new_revision() {
+    let store = store_with_level();
+    let edited = level(vec![block(0, 0, 0, "#FF0000"), block(2, 0, 0, "#00FF00")]);
+    save_edits(&store, "level", &edited, 1, &ValidationLimits::default()).unwrap();
+
+    let saved = store.get("level").unwrap();
+    assert_eq!((saved.revision, saved.space.blocks.len()), (2, 2));
+}
+
+#[test]
+fn test_save_edits_merges_saves_made_during_the_session() {
+    let store = store_with_level();
+    // Saved through the REST API while the room was open
+    store.put("level", level(vec![block(0, 0, 0, "#FF0000"), block(4, 0, 0, "#0000FF")])).unwrap();
+
+    let edited = level(vec![block(0, 0, 0, "#FF0000"), block(2, 0, 0, "#00FF00")]);
+    save_edits(&store, "level", &edited, 1, &ValidationLimits::default()).unwrap();
+
+    let saved = store.get("level").unwrap();
+    assert_eq!(saved.revision, 3);
+    let mut xs: Vec<i32> = saved.space.blocks.iter().map(|block| block.x).collect();
+    xs.sort();
+    assert_eq!(xs, vec![0, 2, 4]);
+}
+
+#[test]
+fn test_save_edits_keeps_conflicting_edits() {
+    let store = store_with_level();
+    store.put("level", level(vec![block(0, 0, 0, "#0000FF"), block(4, 0, 0, "#0000FF")])).unwrap();
+
+    let edited = level(vec![block(0, 0, 0, "#00FF00")]);
+    let conflicts = save_edits(&store, "level", &edited, 1, &ValidationLimits::default()).unwrap();
+    assert_eq!(conflicts.len(), 1);
+
+    // The room's edits win the conflict and the other changes are merged in
+    let saved = store.get("level").unwrap();
+    assert_eq!(saved.revision, 3);
+    assert_eq!(saved.space.blocks.len(), 2);
+    assert_eq!(saved.space.blocks[0].color, "#00FF00");
+    // The save made during the session stays in the history
+    let overwritten = store.get_revision("level", 2).unwrap();
+    assert_eq!(overwritten.space.blocks[0].color, "#0000FF");
+}
+
+#[test]
+fn test_save_edits_keeps_edits_when_the_merge_is_invalid() {
+    let store = store_with_level();
+    store.put("level", level(vec![block(0, 0, 0, "#FF0000"), block(4, 0, 0, "#0000FF")])).unwrap();
+
+    let edited = level(vec![block(0, 0, 0, "#FF0000"), block(2, 0, 0, "#00FF00")]);
+    let limits = ValidationLimits {
+        max_blocks: 2,
+        ..Default::default()
+    };
+    let conflicts = save_edits(&store, "level", &edited, 1, &limits).unwrap();
+    assert_eq!(conflicts.len(), 1);
+
+    let saved = store.get("level").unwrap();
+    let xs: Vec<i32> = saved.space.blocks.iter().map(|block| block.x).collect();
+    assert_eq!((saved.revision, xs), (3, vec![0, 2]));
+}
+
+#[test]
+fn test_save_edits_keeps_deleted_levels_deleted() {
+    let store = store_with_level();
+    store.delete("level").unwrap();
+    let edited = level(vec![block(2, 0, 0, "#00FF00")]);
+    save_edits(&store, "level", &edited, 1, &ValidationLimits::default()).unwrap();
+    assert!(store.get("level").is_err());
+}
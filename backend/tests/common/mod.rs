@@ -0,0 +1,26 @@
+//! Fixtures shared by the integration tests.
+
+// Each test crate uses only some of these
+#![allow(dead_code)]
+
+use backend::models::{Block, SpaceJSON};
+
+/// A default 2x2x2 block at (`x`, `y`, `z`).
+pub fn block(x: i32, y: i32, z: i32, color: &str) -> Block {
+    Block {
+        x,
+        y,
+        z,
+        color: color.to_string(),
+        ..Default::default()
+    }
+}
+
+/// A schema version 2 level holding only `blocks`.
+pub fn level(blocks: Vec<Block>) -> SpaceJSON {
+    SpaceJSON {
+        schema_version: 2,
+        blocks,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,302 @@
+//! Tests for server configuration loading, environment overrides and validation.
+
+mod common;
+
+use backend::auth::Scope;
+use backend::config::{apply_default_world, ServerConfig, StoreKind};
+use backend::models::{
+    BaseplateSettings, LightingSettings, SpawnPoint, SpawnSettings, WorldSettings,
+};
+use backend::validation::{validate_space_json, ValidationError};
+use common::{block, level};
+use std::collections::HashMap;
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+#[test]
+fn test_defaults_are_valid() {
+    let config = ServerConfig::load_with(env(&[])).unwrap();
+    assert_eq!(config.socket_addr().to_string(), "0.0.0.0:4000");
+    assert!(config.cors_origins().is_none());
+    assert_eq!(config.limits.max_blocks, 10_000);
+    assert_eq!(config.storage.kind, StoreKind::Fs);
+    assert_eq!(config.storage.path(), "data/levels");
+    assert!(!config.auth_config().is_enabled());
+}
+
+#[test]
+fn test_parses_toml_sections() {
+    let config = ServerConfig::from_toml(
+        r##"
+        [server]
+        bind_address = "127.0.0.1"
+        port = 8080
+        cors_origins = ["https://builder.example.com"]
+
+        [limits]
+        max_blocks = 500
+
+        [limits.bounds]
+        min_x = -200
+        max_x = 200
+
+        [world.baseplate]
+        color = "#00FF00"
+
+        [storage]
+        kind = "sqlite"
+
+        [[auth.api_keys]]
+        name = "ci"
+        key = "s3cret"
+        scopes = ["export", "store"]
+
+        [rate_limit]
+        requests = 10
+        "##,
+    )
+    .unwrap();
+    config.validate().unwrap();
+
+    assert_eq!(config.socket_addr().to_string(), "127.0.0.1:8080");
+    assert_eq!(config.cors_origins().unwrap().len(), 1);
+    assert_eq!(config.limits.max_blocks, 500);
+    assert_eq!((config.limits.bounds.min_x, config.limits.bounds.max_x), (-200, 200));
+    // Unset bounds keep their defaults
+    assert_eq!(config.limits.bounds.max_y, 1000);
+    assert_eq!(config.world.baseplate.unwrap().color.as_deref(), Some("#00FF00"));
+    assert_eq!(config.storage.path(), "data/levels.db");
+    assert_eq!(config.auth.api_keys[0].scopes, vec![Scope::Export, Scope::Store]);
+    assert_eq!(config.rate_limit.requests, 10);
+    assert_eq!(config.rate_limit.window_seconds, 60);
+}
+
+#[test]
+fn test_rejects_unknown_keys() {
+    let error = ServerConfig::from_toml("[server]\nprot = 8080\n").unwrap_err();
+    assert!(error.contains("prot"), "{}", error);
+}
+
+#[test]
+fn test_env_overrides_file() {
+    let mut config = ServerConfig::from_toml("[server]\nport = 8080\n[limits]\nmax_blocks = 500\n").unwrap();
+    config
+        .apply_env(env(&[
+            ("PORT", "9090"),
+            ("BIND_ADDRESS", "::1"),
+            ("CORS_ORIGINS", "https://a.example.com, http://localhost:5173"),
+            ("MAX_BLOCKS", "2000"),
+            ("MAX_TEAMS", "4"),
+            ("MAX_SPAWNS", "8"),
+            ("BOUNDS_MIN_X", "-200"),
+            ("BOUNDS_MAX_Y", "400"),
+            ("REJECT_FLOATING_BLOCKS", "true"),
+            ("LEVEL_STORE", "sqlite"),
+            ("API_KEYS", "ci:s3cret:export"),
+            ("TRUST_PROXY_HEADERS", "true"),
+            ("RATE_LIMIT_BLOCKS", ""),
+        ]))
+        .unwrap();
+    config.validate().unwrap();
+
+    assert_eq!(config.socket_addr().to_string(), "[::1]:9090");
+    assert_eq!(config.cors_origins().unwrap().len(), 2);
+    assert_eq!(config.limits.max_blocks, 2000);
+    assert_eq!((config.limits.max_teams, config.limits.max_spawns), (4, 8));
+    let bounds = config.limits.bounds;
+    assert_eq!((bounds.min_x, bounds.max_x, bounds.max_y), (-200, 1000, 400));
+    assert!(config.limits.reject_floating_blocks);
+    assert_eq!(config.storage.kind, StoreKind::Sqlite);
+    assert_eq!(config.auth.api_keys[0].name, "ci");
+    assert!(config.server.trust_proxy_headers);
+    // Empty variables are ignored
+    assert_eq!(config.rate_limit.blocks, 100_000);
+}
+
+#[test]
+fn test_invalid_env_vars_are_named() {
+    let error = ServerConfig::load_with(env(&[("PORT", "eighty")])).unwrap_err();
+    assert_eq!(error.error_code(), "INVALID_ENV_VAR");
+    assert!(error.message().contains("PORT"), "{}", error.message());
+
+    let error = ServerConfig::load_with(env(&[("BOUNDS_MAX_Z", "far")])).unwrap_err();
+    assert!(error.message().contains("BOUNDS_MAX_Z"), "{}", error.message());
+
+    let error = ServerConfig::load_with(env(&[("LEVEL_STORE", "mongo")])).unwrap_err();
+    assert!(error.message().contains("'fs' or 'sqlite'"), "{}", error.message());
+
+    let error = ServerConfig::load_with(env(&[("API_KEYS", "ci:s3cret:everything")])).unwrap_err();
+    assert!(error.message().contains("unknown scope"), "{}", error.message());
+    assert!(!error.message().contains("s3cret"));
+}
+
+#[test]
+fn test_validate_rejects_bad_settings() {
+    let check = |toml: &str, setting: &str| {
+        let error = ServerConfig::from_toml(toml).unwrap().validate().unwrap_err();
+        assert_eq!(error.error_code(), "INVALID_CONFIG");
+        assert!(error.message().contains(setting), "{}", error.message());
+    };
+    check("[server]\nbind_address = \"localhost:80\"\n", "server.bind_address");
+    check("[server]\ncors_origins = [\"builder.example.com\"]\n", "server.cors_origins");
+    check("[server]\ncors_origins = [\"*\", \"https://a.example.com\"]\n", "server.cors_origins");
+    check("[server]\ncors_origins = []\n", "server.cors_origins");
+    check("[server]\nbody_limit_bytes = 0\n", "server.body_limit_bytes");
+    check("[limits]\nmax_blocks = 0\n", "limits.max_blocks");
+    check("[limits.bounds]\nmin_y = 10\nmax_y = 5\n", "limits.bounds.min_y");
+    check("[world.lighting]\ntechnology = \"Raytraced\"\n", "world");
+    check(
+        "[[auth.api_keys]]\nname = \"ci\"\nkey = \"\"\nscopes = [\"export\"]\n",
+        "auth.api_keys",
+    );
+    check("[rate_limit]\nwindow_seconds = 0\n", "rate_limit.window_seconds");
+}
+
+#[test]
+fn test_world_is_checked_against_configured_bounds() {
+    let error = ServerConfig::from_toml(
+        "[limits.bounds]\nmax_y = 100\n[world.spawn]\nposition = { x = 0.0, y = 150.0, z = 0.0 }\n",
+    )
+    .unwrap()
+    .validate()
+    .unwrap_err();
+    assert!(error.message().contains("Y: 0 to 100"), "{}", error.message());
+}
+
+#[test]
+fn test_missing_config_file_is_reported() {
+    let error = ServerConfig::load_with(env(&[("CONFIG_FILE", "/nonexistent/config.toml")])).unwrap_err();
+    assert_eq!(error.error_code(), "INVALID_CONFIG_FILE");
+    assert!(error.message().contains("/nonexistent/config.toml"));
+}
+
+#[test]
+fn test_loads_config_file() {
+    let path = std::env::temp_dir().join(format!("config-test-{}.toml", std::process::id()));
+    std::fs::write(&path, "[server]\nport = 8080\n[limits]\nmax_blocks = 500\n").unwrap();
+    let config = ServerConfig::load_with(env(&[
+        ("CONFIG_FILE", path.to_str().unwrap()),
+        ("MAX_BLOCKS", "750"),
+    ]));
+    std::fs::remove_file(&path).unwrap();
+
+    let config = config.unwrap();
+    assert_eq!(config.server.port, 8080);
+    assert_eq!(config.limits.max_blocks, 750);
+}
+
+#[test]
+fn test_configured_limits_are_enforced() {
+    let config = ServerConfig::from_toml("[limits]\nmax_blocks = 2\n[limits.bounds]\nmin_x = -10\nmax_x = 10\n").unwrap();
+    let limits = config.limits;
+
+    assert!(validate_space_json(&level(vec![block(10, 0, 500, "#FF0000")]), &limits).is_ok());
+    let error = validate_space_json(&level(vec![block(11, 0, 0, "#FF0000")]), &limits).unwrap_err();
+    assert!(matches!(error, ValidationError::CoordinateOutOfBounds { x: 11, .. }));
+    assert!(
+        error.message().contains("X: -10 to 10, Y: 0 to 1000, Z: -1000 to 1000"),
+        "{}",
+        error.message()
+    );
+
+    let blocks = vec![block(0, 0, 0, "#FF0000"), block(1, 0, 0, "#FF0000"), block(2, 0, 0, "#FF0000")];
+    assert!(matches!(
+        validate_space_json(&level(blocks), &limits),
+        Err(ValidationError::BlockCountExceeded { count: 3, limit: 2 })
+    ));
+}
+
+#[test]
+fn test_apply_default_world() {
+    let defaults = WorldSettings {
+        baseplate: Some(BaseplateSettings {
+            color: Some("#00FF00".to_string()),
+            ..BaseplateSettings::default()
+        }),
+        spawn: Some(SpawnSettings {
+            auto: Some(true),
+            position: None,
+        }),
+        lighting: Some(LightingSettings {
+            technology: Some("Future".to_string()),
+            ..LightingSettings::default()
+        }),
+    };
+
+    let mut space = level(vec![block(0, 0, 0, "#FF0000")]);
+    space.world = Some(WorldSettings {
+        baseplate: Some(BaseplateSettings {
+            enabled: Some(false),
+            ..BaseplateSettings::default()
+        }),
+        ..WorldSettings::default()
+    });
+    apply_default_world(&mut space, &defaults);
+    let world = space.world.unwrap();
+    // The level's own baseplate settings are kept whole
+    assert_eq!(world.baseplate.as_ref().unwrap().enabled, Some(false));
+    assert!(world.baseplate.unwrap().color.is_none());
+    assert_eq!(world.spawn.unwrap().auto, Some(true));
+    assert_eq!(world.lighting.unwrap().technology.as_deref(), Some("Future"));
+
+    // Levels with spawn points keep their own spawns
+    let mut space = level(vec![block(0, 0, 0, "#FF0000")]);
+    space.spawns.push(SpawnPoint::default());
+    apply_default_world(&mut space, &defaults);
+    assert!(space.world.unwrap().spawn.is_none());
+
+    let mut space = level(Vec::new());
+    apply_default_world(&mut space, &WorldSettings::default());
+    assert!(space.world.is_none());
+}
+
+#[test]
+fn test_tiers_select_limits() {
+    let config = ServerConfig::from_toml(
+        r#"
+        [limits]
+        max_blocks = 1000
+
+        [tiers.pro]
+        max_blocks = 50000
+
+        [[auth.api_keys]]
+        name = "partner"
+        key = "p4rtner"
+        scopes = ["export"]
+        tier = "pro"
+        "#,
+    )
+    .unwrap();
+    config.validate().unwrap();
+
+    assert_eq!(config.limits_for(None).max_blocks, 1000);
+    assert_eq!(config.limits_for(Some("pro")).max_blocks, 50_000);
+    // Tier bounds not set fall back to the built-in defaults
+    assert_eq!(config.limits_for(Some("pro")).bounds.max_x, 1000);
+    // Tokens minted for a tier that was later removed get the default limits
+    assert_eq!(config.limits_for(Some("gold")).max_blocks, 1000);
+
+    let principal = config.auth_config().authenticate(Some("p4rtner"), None, 0).unwrap();
+    assert_eq!(principal.tier.as_deref(), Some("pro"));
+}
+
+#[test]
+fn test_tiers_are_validated() {
+    let error = ServerConfig::from_toml("[tiers.pro]
+max_blocks = 0
+")
+        .unwrap()
+        .validate()
+        .unwrap_err();
+    assert!(error.message().contains("tiers.pro.max_blocks"), "{}", error.message());
+
+    let error = ServerConfig::load_with(env(&[("API_KEYS", "partner:p4rtner:export:gold")])).unwrap_err();
+    assert!(error.message().contains("unknown tier 'gold'"), "{}", error.message());
+}
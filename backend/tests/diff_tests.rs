@@ -0,0 +1,65 @@
+//! Tests for block-level diffs between levels.
+
+mod common;
+
+use backend::diff::diff_levels;
+use common::{block, level};
+
+#[test]
+fn test_identical_levels_have_empty_diff() {
+    let space = level(vec![block(0, 0, 0, "#FF0000"), block(2, 0, 0, "#00FF00")]);
+    assert!(diff_levels(&space, &space).is_empty());
+}
+
+#[test]
+fn test_added_removed_and_recolored() {
+    let old = level(vec![
+        block(0, 0, 0, "#FF0000"),
+        block(2, 0, 0, "#00FF00"),
+        block(4, 0, 0, "#0000FF"),
+    ]);
+    let new = level(vec![
+        block(0, 0, 0, "#FF0000"),
+        block(2, 0, 0, "#FFFF00"),
+        block(6, 2, 0, "#0000FF"),
+    ]);
+    let diff = diff_levels(&old, &new);
+
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!((diff.added[0].x, diff.added[0].y), (6, 2));
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].x, 4);
+    assert_eq!(diff.recolored.len(), 1);
+    assert_eq!(diff.recolored[0].position.x, 2);
+    assert_eq!(diff.recolored[0].from, "#00FF00");
+    assert_eq!(diff.recolored[0].to, "#FFFF00");
+}
+
+#[test]
+fn test_equivalent_color_spellings_are_not_recolored() {
+    let old = level(vec![block(0, 0, 0, "#ff0000"), block(2, 0, 0, "#ABC")]);
+    let new = level(vec![block(0, 0, 0, "#FF0000"), block(2, 0, 0, "#aabbcc")]);
+    assert!(diff_levels(&old, &new).is_empty());
+}
+
+#[test]
+fn test_diff_is_ordered_by_position() {
+    let old = level(vec![]);
+    let new = level(vec![block(4, 0, 0, "#FFF"), block(0, 2, 0, "#FFF"), block(0, 0, 2, "#FFF")]);
+    let diff = diff_levels(&old, &new);
+    let positions: Vec<(i32, i32, i32)> = diff.added.iter().map(|b| (b.x, b.y, b.z)).collect();
+    assert_eq!(positions, vec![(0, 0, 2), (0, 2, 0), (4, 0, 0)]);
+    assert!(diff_levels(&new, &old).added.is_empty());
+    assert_eq!(diff_levels(&new, &old).removed.len(), 3);
+}
+
+#[test]
+fn test_recolored_serializes_flat_position() {
+    let old = level(vec![block(2, 4, 6, "#000000")]);
+    let new = level(vec![block(2, 4, 6, "#FFFFFF")]);
+    let json = serde_json::to_value(diff_levels(&old, &new)).unwrap();
+    assert_eq!(
+        json["recolored"][0],
+        serde_json::json!({ "x": 2, "y": 4, "z": 6, "from": "#000000", "to": "#FFFFFF" })
+    );
+}
@@ -0,0 +1,165 @@
+//! Tests for gameplay block kinds and the generated gameplay Script.
+
+mod common;
+
+use backend::gameplay::{
+    BlockKind, BLOCK_KINDS, BOUNCE_VELOCITY, GAMEPLAY_SCRIPT_NAME, GAMEPLAY_SCRIPT_SOURCE, SPEED_BOOST,
+};
+use backend::import::import_dom;
+use backend::meshing::greedy_mesh;
+use backend::models::{Block, SpaceJSON};
+use backend::rbxlx::{build_model_dom, build_place_dom, generate_rbxlx, ExportOptions};
+use backend::validation::ValidationLimits;
+use common::{block, level};
+use rbx_dom_weak::{ustr, Instance, WeakDom};
+use rbx_types::Variant;
+
+/// A red block at height 2 with the given gameplay `kind`.
+fn kind_block(x: i32, kind: Option<&str>) -> Block {
+    Block {
+        kind: kind.map(str::to_string),
+        ..block(x, 2, 0, "#FF0000")
+    }
+}
+
+fn gameplay_level() -> SpaceJSON {
+    level(vec![
+        kind_block(0, None),
+        kind_block(2, Some("kill")),
+        kind_block(4, Some("Checkpoint")),
+        kind_block(6, Some("finish")),
+        kind_block(8, Some("bounce")),
+        kind_block(10, Some("speed")),
+    ])
+}
+
+/// Returns every instance of the given class, in document order.
+fn instances_of<'a>(dom: &'a WeakDom, class: &str) -> Vec<&'a Instance> {
+    dom.descendants().filter(|instance| instance.class == class).collect()
+}
+
+/// Returns the CollectionService tags of an instance.
+fn tags(instance: &Instance) -> Vec<String> {
+    match instance.properties.get(&ustr("Tags")) {
+        Some(Variant::Tags(tags)) => tags.iter().map(str::to_string).collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Block kind tests
+
+#[test]
+fn test_block_kind_names_and_tags() {
+    assert_eq!(BlockKind::from_name("KILL"), Some(BlockKind::Kill));
+    assert_eq!(BlockKind::from_name("lava"), None);
+    for &kind in BLOCK_KINDS {
+        assert_eq!(BlockKind::from_name(kind.name()), Some(kind));
+        assert_eq!(BlockKind::from_tag(kind.tag()), Some(kind));
+        assert!(
+            GAMEPLAY_SCRIPT_SOURCE.contains(&format!("{} = function", kind.tag())),
+            "Script has no behavior for tag {}",
+            kind.tag()
+        );
+    }
+}
+
+#[test]
+fn test_script_constants_match_analysis() {
+    assert!(GAMEPLAY_SCRIPT_SOURCE.contains(&format!("local BOUNCE_VELOCITY = {}\n", BOUNCE_VELOCITY)));
+    assert!(GAMEPLAY_SCRIPT_SOURCE.contains(&format!("local SPEED_BOOST = {}\n", SPEED_BOOST)));
+}
+
+// Generation tests
+
+#[test]
+fn test_generate_tags_gameplay_parts() {
+    let dom = build_place_dom(&gameplay_level(), &ExportOptions::default());
+    let parts: Vec<_> = instances_of(&dom, "Part")
+        .into_iter()
+        .filter(|part| part.name != "Baseplate")
+        .collect();
+
+    let part_tags: Vec<Vec<String>> = parts.iter().map(|part| tags(part)).collect();
+    assert_eq!(
+        part_tags,
+        vec![
+            vec![],
+            vec!["KillBrick".to_string()],
+            vec!["Checkpoint".to_string()],
+            vec!["FinishPad".to_string()],
+            vec!["BouncePad".to_string()],
+            vec!["SpeedPad".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn test_generate_gameplay_script() {
+    let dom = build_place_dom(&gameplay_level(), &ExportOptions::default());
+
+    let services = instances_of(&dom, "ServerScriptService");
+    assert_eq!(services.len(), 1);
+    let scripts = instances_of(&dom, "Script");
+    assert_eq!(scripts.len(), 1);
+    assert_eq!(scripts[0].parent(), services[0].referent());
+    assert_eq!(
+        scripts[0].properties.get(&ustr("Source")),
+        Some(&Variant::String(GAMEPLAY_SCRIPT_SOURCE.to_string()))
+    );
+
+    let xml = String::from_utf8(generate_rbxlx(&gameplay_level()).unwrap()).unwrap();
+    assert!(xml.contains(GAMEPLAY_SCRIPT_NAME));
+    assert!(xml.contains("CollectionService:GetTagged(tag)"));
+}
+
+#[test]
+fn test_generate_without_gameplay_blocks_has_no_script() {
+    let space_json = SpaceJSON {
+        schema_version: 1,
+        blocks: vec![kind_block(0, None)],
+        ..Default::default()
+    };
+    let dom = build_place_dom(&space_json, &ExportOptions::default());
+
+    assert!(instances_of(&dom, "ServerScriptService").is_empty());
+    assert!(instances_of(&dom, "Script").is_empty());
+}
+
+#[test]
+fn test_generate_model_includes_gameplay_script() {
+    let dom = build_model_dom(&gameplay_level(), &ExportOptions::default());
+    let models = instances_of(&dom, "Model");
+    let scripts = instances_of(&dom, "Script");
+
+    assert_eq!(scripts.len(), 1);
+    assert_eq!(scripts[0].parent(), models[0].referent());
+}
+
+// Meshing and import tests
+
+#[test]
+fn test_greedy_mesh_keeps_kinds_apart() {
+    let blocks = vec![
+        kind_block(0, Some("kill")),
+        kind_block(2, Some("KILL")),
+        kind_block(4, None),
+        kind_block(6, Some("bounce")),
+    ];
+    let boxes = greedy_mesh(&blocks);
+
+    assert_eq!(boxes.len(), 3);
+    assert_eq!(boxes[0].extent, (2, 1, 1));
+}
+
+#[test]
+fn test_import_recovers_block_kinds() {
+    let dom = build_place_dom(&gameplay_level(), &ExportOptions::default());
+    let result = import_dom(&dom, &ValidationLimits::default()).unwrap();
+
+    let kinds: Vec<Option<&str>> = result.space_json.blocks.iter().map(|b| b.kind.as_deref()).collect();
+    assert_eq!(
+        kinds,
+        vec![None, Some("kill"), Some("checkpoint"), Some("finish"), Some("bounce"), Some("speed")]
+    );
+}
+
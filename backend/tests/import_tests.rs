@@ -0,0 +1,264 @@
+//! Tests for importing `.rbxlx` places back into Space JSON.
+
+mod common;
+
+use backend::import::{color3_to_hex, import_dom, import_rbxlx};
+use backend::models::{Block, SpaceJSON};
+use backend::rbxlx::{generate_rbxlx, generate_rbxlx_with_options, ExportOptions};
+use backend::validation::{CoordinateBounds, ValidationLimits};
+use common::block;
+use rbx_dom_weak::{InstanceBuilder, WeakDom};
+use rbx_types::{CFrame, Color3, Matrix3, Vector3};
+use std::collections::HashSet;
+
+fn positions(blocks: &[Block]) -> HashSet<(i32, i32, i32)> {
+    blocks.iter().map(|b| (b.x, b.y, b.z)).collect()
+}
+
+/// Builds a place whose Workspace contains the given instances.
+fn place_with(parts: Vec<InstanceBuilder>) -> WeakDom {
+    let mut dom = WeakDom::new(InstanceBuilder::new("DataModel"));
+    let workspace = dom.insert(dom.root_ref(), InstanceBuilder::new("Workspace"));
+    for part in parts {
+        dom.insert(workspace, part);
+    }
+    dom
+}
+
+fn part(name: &str, position: Vector3, size: Vector3) -> InstanceBuilder {
+    InstanceBuilder::new("Part")
+        .with_name(name)
+        .with_property("CFrame", CFrame::new(position, Matrix3::identity()))
+        .with_property("Size", size)
+        .with_property("Color", Color3::new(1.0, 0.0, 0.0))
+        .with_property("Anchored", true)
+}
+
+// Round-trip tests
+
+#[test]
+fn test_import_round_trip_blocks() {
+    let blocks = vec![
+        block(0, 0, 0, "#FF0000"),
+        block(2, 0, 0, "#00FF00"),
+        block(-4, 6, 10, "#0000FF"),
+    ];
+    let space_json = SpaceJSON {
+        schema_version: 1,
+        name: Some("Round Trip".to_string()),
+        blocks: blocks.clone(),
+        ..Default::default()
+    };
+    let xml = generate_rbxlx(&space_json).unwrap();
+
+    let result = import_rbxlx(&xml, &ValidationLimits::default()).unwrap();
+    assert_eq!(positions(&result.space_json.blocks), positions(&blocks));
+    for original in &blocks {
+        let imported = result
+            .space_json
+            .blocks
+            .iter()
+            .find(|b| (b.x, b.y, b.z) == (original.x, original.y, original.z))
+            .unwrap();
+        assert_eq!(imported.color, original.color);
+    }
+
+    let skipped: Vec<_> = result.skipped.iter().map(|s| s.name.as_str()).collect();
+    assert!(skipped.contains(&"Baseplate"));
+    assert!(skipped.contains(&"SpawnLocation"));
+}
+
+#[test]
+fn test_import_splits_merged_parts_into_voxels() {
+    let blocks: Vec<Block> = (0..10)
+        .flat_map(|x| (0..10).map(move |z| block(x * 2, 0, z * 2, "#808080")))
+        .collect();
+    let space_json = SpaceJSON {
+        schema_version: 1,
+        name: None,
+        blocks: blocks.clone(),
+        ..Default::default()
+    };
+    let options = ExportOptions { merge_blocks: true, ..Default::default() };
+    let xml = generate_rbxlx_with_options(&space_json, &options).unwrap();
+
+    let result = import_rbxlx(&xml, &ValidationLimits::default()).unwrap();
+    assert_eq!(result.space_json.blocks.len(), 100);
+    assert_eq!(positions(&result.space_json.blocks), positions(&blocks));
+}
+
+#[test]
+fn test_import_round_trip_v2_properties() {
+    let mut glass = block(0, 0, 0, "#00FFFF");
+    glass.material = Some("Glass".to_string());
+    glass.transparency = Some(0.5);
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        name: None,
+        blocks: vec![glass],
+        ..Default::default()
+    };
+    let xml = generate_rbxlx(&space_json).unwrap();
+
+    let result = import_rbxlx(&xml, &ValidationLimits::default()).unwrap();
+    let imported = &result.space_json.blocks[0];
+    assert_eq!(imported.material.as_deref(), Some("Glass"));
+    assert_eq!(imported.transparency, Some(0.5));
+    assert_eq!(result.space_json.schema_version, 2);
+}
+
+// Snapping and skip tests
+
+#[test]
+fn test_import_snaps_to_grid() {
+    let dom = place_with(vec![part(
+        "Nudged",
+        Vector3::new(0.3, 0.2, -0.4),
+        Vector3::new(2.0, 2.0, 2.0),
+    )]);
+    let result = import_dom(&dom, &ValidationLimits::default()).unwrap();
+    assert_eq!(positions(&result.space_json.blocks), HashSet::from([(0, 0, 0)]));
+    assert_eq!(result.space_json.blocks[0].color, "#FF0000");
+}
+
+#[test]
+fn test_import_splits_large_part() {
+    let dom = place_with(vec![part(
+        "Wall",
+        Vector3::new(3.0, 0.0, 0.0),
+        Vector3::new(8.0, 2.0, 2.0),
+    )]);
+    let result = import_dom(&dom, &ValidationLimits::default()).unwrap();
+    assert_eq!(
+        positions(&result.space_json.blocks),
+        HashSet::from([(0, 0, 0), (2, 0, 0), (4, 0, 0), (6, 0, 0)])
+    );
+}
+
+#[test]
+fn test_import_descends_into_models() {
+    let mut dom = place_with(vec![]);
+    let workspace = dom.root().children()[0];
+    let model = dom.insert(workspace, InstanceBuilder::new("Model").with_name("Tower"));
+    dom.insert(model, part("Floor", Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0)));
+
+    let result = import_dom(&dom, &ValidationLimits::default()).unwrap();
+    assert_eq!(result.space_json.blocks.len(), 1);
+    assert!(result.skipped.is_empty());
+}
+
+#[test]
+fn test_import_skips_unsupported_parts() {
+    let rotation = Matrix3 {
+        x: Vector3::new(0.0, 0.0, 1.0),
+        y: Vector3::new(0.0, 1.0, 0.0),
+        z: Vector3::new(-1.0, 0.0, 0.0),
+    };
+    let dom = place_with(vec![
+        part("Loose", Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0))
+            .with_property("Anchored", false),
+        part("Turned", Vector3::new(4.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0))
+            .with_property("CFrame", CFrame::new(Vector3::new(4.0, 0.0, 0.0), rotation)),
+        part("Thin", Vector3::new(8.0, 0.0, 0.0), Vector3::new(2.0, 0.5, 2.0)),
+        InstanceBuilder::new("MeshPart").with_name("Statue"),
+        InstanceBuilder::new("Script").with_name("Logic"),
+        part("Far", Vector3::new(5000.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0)),
+        part("Kept", Vector3::new(12.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0)),
+    ]);
+
+    let result = import_dom(&dom, &ValidationLimits::default()).unwrap();
+    assert_eq!(positions(&result.space_json.blocks), HashSet::from([(12, 0, 0)]));
+
+    let skipped: Vec<_> = result.skipped.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(skipped, vec!["Loose", "Turned", "Thin", "Statue", "Logic", "Far"]);
+    assert_eq!(result.skipped[0].reason, "Part is not anchored");
+    assert_eq!(result.skipped[3].class, "MeshPart");
+}
+
+#[test]
+fn test_import_skips_fully_overlapping_part() {
+    let dom = place_with(vec![
+        part("First", Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0)),
+        part("Second", Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0)),
+    ]);
+    let result = import_dom(&dom, &ValidationLimits::default()).unwrap();
+    assert_eq!(result.space_json.blocks.len(), 1);
+    assert_eq!(result.skipped.len(), 1);
+    assert_eq!(result.skipped[0].name, "Second");
+}
+
+#[test]
+fn test_import_skips_non_finite_and_huge_parts() {
+    let dom = place_with(vec![
+        part("Endless", Vector3::new(0.0, 0.0, 0.0), Vector3::new(f32::INFINITY, 2.0, 2.0)),
+        part("Unsized", Vector3::new(0.0, 0.0, 0.0), Vector3::new(f32::NAN, 2.0, 2.0)),
+        part("Nowhere", Vector3::new(f32::NAN, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0)),
+        part("Huge", Vector3::new(0.0, 0.0, 0.0), Vector3::new(4e9, 4e9, 4e9)),
+        part("Distant", Vector3::new(3e38, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0)),
+        part("Kept", Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0)),
+    ]);
+
+    let result = import_dom(&dom, &ValidationLimits::default()).unwrap();
+    assert_eq!(positions(&result.space_json.blocks), HashSet::from([(0, 0, 0)]));
+    let skipped: Vec<_> = result.skipped.iter().map(|s| s.reason.as_str()).collect();
+    assert_eq!(
+        skipped,
+        vec![
+            "Part has a non-finite size or position",
+            "Part has a non-finite size or position",
+            "Part has a non-finite size or position",
+            "Part would exceed the 10000 block limit",
+            "Part lies outside the level bounds",
+        ]
+    );
+}
+
+#[test]
+fn test_import_uses_callers_limits() {
+    let dom = place_with(vec![
+        part("Wall", Vector3::new(3.0, 0.0, 0.0), Vector3::new(8.0, 2.0, 2.0)),
+        part("Post", Vector3::new(20.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0)),
+        part("Step", Vector3::new(0.0, 4.0, 0.0), Vector3::new(2.0, 2.0, 2.0)),
+    ]);
+    let limits = ValidationLimits {
+        max_blocks: 5,
+        bounds: CoordinateBounds {
+            max_x: 10,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let result = import_dom(&dom, &limits).unwrap();
+    assert_eq!(result.space_json.blocks.len(), 5);
+    let skipped: Vec<_> = result.skipped.iter().map(|s| s.reason.as_str()).collect();
+    assert_eq!(skipped, vec!["Part lies outside the level bounds"]);
+
+    let limits = ValidationLimits {
+        max_blocks: 4,
+        ..limits
+    };
+    let result = import_dom(&dom, &limits).unwrap();
+    assert_eq!(result.space_json.blocks.len(), 4);
+    assert_eq!(result.skipped[1].reason, "Part would exceed the 4 block limit");
+}
+
+// Error tests
+
+#[test]
+fn test_import_rejects_place_without_workspace() {
+    let dom = WeakDom::new(InstanceBuilder::new("DataModel"));
+    assert!(import_dom(&dom, &ValidationLimits::default()).is_err());
+}
+
+#[test]
+fn test_import_rejects_invalid_xml() {
+    assert!(import_rbxlx(b"definitely not a place", &ValidationLimits::default()).is_err());
+}
+
+#[test]
+fn test_color3_to_hex() {
+    assert_eq!(color3_to_hex(Color3::new(1.0, 0.0, 0.0)), "#FF0000");
+    assert_eq!(color3_to_hex(Color3::new(0.5, 0.5, 0.5)), "#808080");
+    assert_eq!(color3_to_hex(Color3::new(0.0, 0.0, 1.0)), "#0000FF");
+}
@@ -0,0 +1,179 @@
+//! Tests for three-way merges of Space JSON levels.
+
+mod common;
+
+use backend::merge::{merge_levels, BlockConflict, LevelMerge, MergeConflict};
+use backend::models::{BlockSpawn, SpaceJSON, SpawnPoint, Team};
+use backend::validation::ValidationLimits;
+use common::{block, level};
+
+fn colors(space: &SpaceJSON) -> Vec<(i32, &str)> {
+    space.blocks.iter().map(|b| (b.x, b.color.as_str())).collect()
+}
+
+/// Returns the merge's block conflicts, failing on any other kind.
+fn block_conflicts(merge: &LevelMerge) -> Vec<&BlockConflict> {
+    merge
+        .conflicts
+        .iter()
+        .map(|conflict| match conflict {
+            MergeConflict::Block(conflict) => &**conflict,
+            other => panic!("expected a block conflict, got {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_non_overlapping_edits_merge_cleanly() {
+    let base = level(vec![block(0, 0, 0, "#FF0000"), block(2, 0, 0, "#FF0000"), block(4, 0, 0, "#FF0000")]);
+    // Ours recolors 0 and removes 4; theirs adds 6 and recolors 2
+    let ours = level(vec![block(0, 0, 0, "#00FF00"), block(2, 0, 0, "#FF0000")]);
+    let theirs = level(vec![
+        block(0, 0, 0, "#FF0000"),
+        block(2, 0, 0, "#0000FF"),
+        block(4, 0, 0, "#FF0000"),
+        block(6, 0, 0, "#FFFFFF"),
+    ]);
+
+    let result = merge_levels(&base, &ours, &theirs, &ValidationLimits::default()).unwrap();
+    assert!(result.conflicts.is_empty());
+    assert_eq!(
+        colors(&result.merged),
+        vec![(0, "#00FF00"), (2, "#0000FF"), (6, "#FFFFFF")]
+    );
+}
+
+#[test]
+fn test_identical_changes_do_not_conflict() {
+    let base = level(vec![block(0, 0, 0, "#FF0000")]);
+    let ours = level(vec![block(0, 0, 0, "#00ff00"), block(2, 0, 0, "#FFF")]);
+    let theirs = level(vec![block(0, 0, 0, "#00FF00"), block(2, 0, 0, "#FFFFFF")]);
+
+    let result = merge_levels(&base, &ours, &theirs, &ValidationLimits::default()).unwrap();
+    assert!(result.conflicts.is_empty());
+    assert_eq!(result.merged.blocks.len(), 2);
+}
+
+#[test]
+fn test_conflicting_changes_keep_ours() {
+    let base = level(vec![block(0, 0, 0, "#FF0000"), block(2, 0, 0, "#FF0000")]);
+    // Both recolor 0 differently; ours deletes 2 while theirs recolors it
+    let ours = level(vec![block(0, 0, 0, "#00FF00")]);
+    let theirs = level(vec![block(0, 0, 0, "#0000FF"), block(2, 0, 0, "#FFFFFF")]);
+
+    let result = merge_levels(&base, &ours, &theirs, &ValidationLimits::default()).unwrap();
+    assert_eq!(colors(&result.merged), vec![(0, "#00FF00")]);
+    let conflicts = block_conflicts(&result);
+    assert_eq!(conflicts.len(), 2);
+
+    let recolor = conflicts[0];
+    assert_eq!(recolor.position.x, 0);
+    assert_eq!(recolor.ours.as_ref().unwrap().color, "#00FF00");
+    assert_eq!(recolor.theirs.as_ref().unwrap().color, "#0000FF");
+
+    let delete = conflicts[1];
+    assert_eq!(delete.position.x, 2);
+    assert!(delete.ours.is_none());
+    assert_eq!(delete.base.as_ref().unwrap().color, "#FF0000");
+}
+
+#[test]
+fn test_both_adding_at_one_position_conflicts() {
+    let base = level(vec![]);
+    let ours = level(vec![block(0, 0, 0, "#FF0000")]);
+    let mut theirs = level(vec![block(0, 0, 0, "#FF0000")]);
+    theirs.blocks[0].material = Some("Wood".to_string());
+
+    let result = merge_levels(&base, &ours, &theirs, &ValidationLimits::default()).unwrap();
+    let conflicts = block_conflicts(&result);
+    assert_eq!(conflicts.len(), 1);
+    assert!(conflicts[0].base.is_none());
+    assert_eq!(result.merged.blocks.len(), 1);
+    assert!(result.merged.blocks[0].material.is_none());
+}
+
+#[test]
+fn test_root_fields_take_the_changed_side() {
+    let base = level(vec![]);
+    let ours = level(vec![]);
+    let mut theirs = level(vec![]);
+    theirs.name = Some("Renamed".to_string());
+    theirs.schema_version = 1;
+
+    let result = merge_levels(&base, &ours, &theirs, &ValidationLimits::default()).unwrap();
+    assert_eq!(result.merged.name.as_deref(), Some("Renamed"));
+    assert_eq!(result.merged.schema_version, 2);
+
+    let mut ours = level(vec![]);
+    ours.name = Some("Ours".to_string());
+    let result = merge_levels(&base, &ours, &theirs, &ValidationLimits::default()).unwrap();
+    assert_eq!(result.merged.name.as_deref(), Some("Ours"));
+}
+
+#[test]
+fn test_duplicate_positions_are_rejected() {
+    let base = level(vec![]);
+    let ours = level(vec![block(0, 0, 0, "#FF0000"), block(0, 0, 0, "#00FF00")]);
+    let error = merge_levels(&base, &ours, &base, &ValidationLimits::default()).unwrap_err();
+    assert_eq!(error.error_code(), "DUPLICATE_POSITION");
+}
+
+#[test]
+fn test_conflict_serializes_flat_position() {
+    let base = level(vec![block(0, 0, 0, "#FF0000")]);
+    let ours = level(vec![]);
+    let theirs = level(vec![block(0, 0, 0, "#0000FF")]);
+    let json = serde_json::to_value(merge_levels(&base, &ours, &theirs, &ValidationLimits::default()).unwrap()).unwrap();
+    let conflict = &json["conflicts"][0];
+    assert_eq!(conflict["x"], 0);
+    assert!(conflict.get("ours").is_none());
+    assert_eq!(conflict["theirs"]["color"], "#0000FF");
+    assert_eq!(json["merged"]["blocks"], serde_json::json!([]));
+}
+
+#[test]
+fn test_invalid_merged_levels_report_validation_conflicts() {
+    let mut base = level(vec![block(0, 0, 0, "#FF0000")]);
+    base.teams = vec![Team {
+        name: "Red".to_string(),
+        color: None,
+    }];
+    // Ours removes the team; theirs adds a spawn point for it and makes the block a spawn
+    let mut ours = base.clone();
+    ours.teams.clear();
+    let mut theirs = base.clone();
+    theirs.spawns = vec![SpawnPoint {
+        x: 10.0,
+        y: 0.5,
+        z: 0.0,
+        team: Some("Red".to_string()),
+    }];
+    theirs.blocks[0].spawn = Some(BlockSpawn {
+        team: Some("Red".to_string()),
+    });
+
+    let limits = ValidationLimits::default();
+    let result = merge_levels(&base, &ours, &theirs, &limits).unwrap();
+    assert!(result.merged.teams.is_empty());
+    assert_eq!(result.merged.spawns.len(), 1);
+    assert_eq!(result.conflicts.len(), 2);
+    assert!(result.conflicts.iter().all(|conflict| matches!(
+        conflict,
+        MergeConflict::Invalid { error: "UNKNOWN_TEAM", message } if message.contains("'Red'")
+    )));
+
+    // Limits apply to the merged level, not just to each side
+    let limits = ValidationLimits {
+        max_spawns: 1,
+        ..limits
+    };
+    let result = merge_levels(&base, &base, &theirs, &limits).unwrap();
+    assert!(matches!(
+        result.conflicts.as_slice(),
+        [MergeConflict::Invalid { error: "SPAWN_COUNT_EXCEEDED", .. }]
+    ));
+
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["conflicts"][0]["error"], "SPAWN_COUNT_EXCEEDED");
+    assert!(json["conflicts"][0].get("x").is_none());
+}
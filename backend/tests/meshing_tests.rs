@@ -0,0 +1,238 @@
+//! Tests for greedy meshing of blocks.
+
+mod common;
+
+use backend::meshing::{greedy_mesh, MergedBox};
+use backend::models::{Block, BlockSize, SpaceJSON};
+use backend::rbxlx::{generate_rbxlx_with_options, ExportOptions};
+use common::block;
+use std::collections::HashSet;
+
+/// Expands boxes into voxel positions, asserting no voxel is covered twice.
+fn covered_voxels(boxes: &[MergedBox]) -> HashSet<(i32, i32, i32)> {
+    let mut covered = HashSet::new();
+    for merged in boxes {
+        for voxel in merged.voxels() {
+            assert!(covered.insert(voxel), "Voxel {:?} covered by more than one box", voxel);
+        }
+    }
+    covered
+}
+
+/// Asserts the boxes cover exactly the input positions and never mix colors.
+fn assert_exact_cover(blocks: &[Block], boxes: &[MergedBox]) {
+    let expected: HashSet<_> = blocks.iter().map(|b| (b.x, b.y, b.z)).collect();
+    assert_eq!(covered_voxels(boxes), expected);
+
+    for merged in boxes {
+        let color = &blocks[merged.source].color;
+        for voxel in merged.voxels() {
+            let source = blocks
+                .iter()
+                .find(|b| (b.x, b.y, b.z) == voxel)
+                .expect("covered voxel must be an input block");
+            assert_eq!(&source.color, color, "Box at {:?} mixes colors", merged.origin);
+        }
+    }
+}
+
+#[test]
+fn test_greedy_mesh_empty() {
+    assert!(greedy_mesh(&[]).is_empty());
+}
+
+#[test]
+fn test_greedy_mesh_single_block() {
+    let blocks = vec![block(4, 2, -6, "#FF0000")];
+    let boxes = greedy_mesh(&blocks);
+    assert_eq!(boxes.len(), 1);
+    assert_eq!(boxes[0].origin, (4, 2, -6));
+    assert_eq!(boxes[0].extent, (1, 1, 1));
+    assert_eq!(boxes[0].size(), (2.0, 2.0, 2.0));
+    assert_eq!(boxes[0].center(), (4.0, 2.0, -6.0));
+}
+
+#[test]
+fn test_greedy_mesh_merges_run() {
+    let blocks: Vec<Block> = (0..5).map(|i| block(i * 2, 0, 0, "#00FF00")).collect();
+    let boxes = greedy_mesh(&blocks);
+    assert_eq!(boxes.len(), 1);
+    assert_eq!(boxes[0].extent, (5, 1, 1));
+    assert_eq!(boxes[0].size(), (10.0, 2.0, 2.0));
+    assert_eq!(boxes[0].center(), (4.0, 0.0, 0.0));
+    assert_exact_cover(&blocks, &boxes);
+}
+
+#[test]
+fn test_greedy_mesh_merges_floor_into_slab() {
+    let blocks: Vec<Block> = (0..10)
+        .flat_map(|x| (0..10).map(move |z| block(x * 2, 0, z * 2, "#808080")))
+        .collect();
+    let boxes = greedy_mesh(&blocks);
+    assert_eq!(boxes.len(), 1);
+    assert_eq!(boxes[0].extent, (10, 1, 10));
+    assert_exact_cover(&blocks, &boxes);
+}
+
+#[test]
+fn test_greedy_mesh_merges_solid_box() {
+    let mut blocks = Vec::new();
+    for x in 0..3 {
+        for y in 0..4 {
+            for z in 0..5 {
+                blocks.push(block(x * 2 - 10, y * 2, z * 2 + 6, "#8B4513"));
+            }
+        }
+    }
+    let boxes = greedy_mesh(&blocks);
+    assert_eq!(boxes.len(), 1);
+    assert_eq!(boxes[0].extent, (3, 4, 5));
+    assert_eq!(boxes[0].origin, (-10, 0, 6));
+    assert_eq!(boxes[0].center(), (-8.0, 3.0, 10.0));
+    assert_exact_cover(&blocks, &boxes);
+}
+
+#[test]
+fn test_greedy_mesh_keeps_colors_separate() {
+    let blocks = vec![
+        block(0, 0, 0, "#FF0000"),
+        block(2, 0, 0, "#FF0000"),
+        block(4, 0, 0, "#0000FF"),
+        block(6, 0, 0, "#0000FF"),
+    ];
+    let boxes = greedy_mesh(&blocks);
+    assert_eq!(boxes.len(), 2);
+    assert_exact_cover(&blocks, &boxes);
+}
+
+#[test]
+fn test_greedy_mesh_color_case_insensitive() {
+    let blocks = vec![block(0, 0, 0, "#ff0000"), block(2, 0, 0, "#FF0000")];
+    assert_eq!(greedy_mesh(&blocks).len(), 1);
+}
+
+#[test]
+fn test_greedy_mesh_keeps_materials_separate() {
+    let mut blocks = vec![block(0, 0, 0, "#FF0000"), block(2, 0, 0, "#FF0000"), block(4, 0, 0, "#FF0000")];
+    blocks[2].material = Some("Wood".to_string());
+    let boxes = greedy_mesh(&blocks);
+    assert_eq!(boxes.len(), 2);
+    assert_exact_cover(&blocks, &boxes);
+}
+
+#[test]
+fn test_greedy_mesh_never_merges_custom_size() {
+    let mut blocks = vec![block(0, 0, 0, "#FF0000"), block(2, 0, 0, "#FF0000"), block(4, 0, 0, "#FF0000")];
+    blocks[1].size = Some(BlockSize { x: 2.0, y: 2.0, z: 2.0 });
+    let boxes = greedy_mesh(&blocks);
+    assert_eq!(boxes.len(), 3);
+    assert!(boxes.iter().all(|b| b.extent == (1, 1, 1)));
+    assert_exact_cover(&blocks, &boxes);
+}
+
+#[test]
+fn test_greedy_mesh_does_not_bridge_gaps() {
+    let blocks = vec![block(0, 0, 0, "#FF0000"), block(4, 0, 0, "#FF0000")];
+    let boxes = greedy_mesh(&blocks);
+    assert_eq!(boxes.len(), 2);
+    assert_exact_cover(&blocks, &boxes);
+}
+
+#[test]
+fn test_greedy_mesh_separates_offset_grids() {
+    // Odd and even coordinates lie on different 2-stud grids and overlap if merged
+    let blocks = vec![block(0, 0, 0, "#FF0000"), block(1, 0, 0, "#FF0000"), block(2, 0, 0, "#FF0000")];
+    let boxes = greedy_mesh(&blocks);
+    assert_eq!(boxes.len(), 2);
+    assert_exact_cover(&blocks, &boxes);
+}
+
+#[test]
+fn test_greedy_mesh_l_shape() {
+    let blocks = vec![
+        block(0, 0, 0, "#FFFF00"),
+        block(2, 0, 0, "#FFFF00"),
+        block(4, 0, 0, "#FFFF00"),
+        block(0, 0, 2, "#FFFF00"),
+        block(0, 0, 4, "#FFFF00"),
+    ];
+    let boxes = greedy_mesh(&blocks);
+    assert_eq!(boxes.len(), 2);
+    assert_exact_cover(&blocks, &boxes);
+}
+
+#[test]
+fn test_greedy_mesh_duplicates_covered_once() {
+    let blocks = vec![block(0, 0, 0, "#FF0000"), block(0, 0, 0, "#FF0000")];
+    let boxes = greedy_mesh(&blocks);
+    assert_eq!(boxes.len(), 1);
+    assert_eq!(covered_voxels(&boxes).len(), 1);
+}
+
+#[test]
+fn test_greedy_mesh_exact_cover_scattered_volume() {
+    // Deterministic pseudo-random fill of a 12x6x12 volume with three colors
+    let colors = ["#FF0000", "#00FF00", "#0000FF"];
+    let mut state: u32 = 0x2545_F491;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+
+    let mut blocks = Vec::new();
+    for x in 0..12 {
+        for y in 0..6 {
+            for z in 0..12 {
+                let roll = next();
+                if roll % 3 != 0 {
+                    let color = colors[(roll / 3 % 3) as usize];
+                    blocks.push(block(x * 2 - 12, y * 2, z * 2 - 12, color));
+                }
+            }
+        }
+    }
+
+    let boxes = greedy_mesh(&blocks);
+    assert!(boxes.len() < blocks.len(), "Meshing should merge some blocks");
+    assert_exact_cover(&blocks, &boxes);
+}
+
+#[test]
+fn test_generate_rbxlx_merged_floor() {
+    let blocks: Vec<Block> = (0..10)
+        .flat_map(|x| (0..10).map(move |z| block(x * 2, 0, z * 2, "#00FF00")))
+        .collect();
+    let space_json = SpaceJSON {
+        schema_version: 1,
+        name: Some("Merged Floor".to_string()),
+        blocks,
+        ..Default::default()
+    };
+
+    let options = ExportOptions { merge_blocks: true, ..Default::default() };
+    let xml = generate_rbxlx_with_options(&space_json, &options).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+
+    let part_count = xml_str.matches("<Item class=\"Part\"").count();
+    assert_eq!(part_count, 2, "Should have 2 Parts (baseplate + 1 merged floor)");
+    assert!(xml_str.contains("<X>20</X>"), "Merged Part should be 20 studs wide");
+}
+
+#[test]
+fn test_generate_rbxlx_without_merge_keeps_blocks() {
+    let blocks: Vec<Block> = (0..4).map(|i| block(i * 2, 0, 0, "#00FF00")).collect();
+    let space_json = SpaceJSON {
+        schema_version: 1,
+        name: None,
+        blocks,
+        ..Default::default()
+    };
+
+    let xml = generate_rbxlx_with_options(&space_json, &ExportOptions::default()).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+
+    let part_count = xml_str.matches("<Item class=\"Part\"").count();
+    assert_eq!(part_count, 5, "Should have 5 Parts (baseplate + 4 blocks)");
+}
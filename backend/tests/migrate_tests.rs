@@ -0,0 +1,116 @@
+//! Tests for Space JSON schema migration.
+//!
+//! Each historical schema version has a fixture in `tests/fixtures/`.
+
+use backend::migrate::{migrate, migrate_value, MigrationError};
+use backend::models::CURRENT_SCHEMA_VERSION;
+use backend::validation::{validate_space_json, ValidationLimits};
+use serde_json::{json, Value};
+
+fn fixture(contents: &str) -> Value {
+    serde_json::from_str(contents).expect("fixture should be valid JSON")
+}
+
+// Fixture migration tests
+
+#[test]
+fn test_migrate_v1_fixture() {
+    let value = fixture(include_str!("fixtures/space_v1.json"));
+    let (space_json, report) = migrate(value).unwrap();
+
+    assert_eq!(space_json.schema_version, CURRENT_SCHEMA_VERSION);
+    assert_eq!(space_json.name.as_deref(), Some("Version 1 Level"));
+    assert_eq!(space_json.blocks.len(), 4);
+    assert_eq!(space_json.blocks[2].color, "#F00");
+    assert!(validate_space_json(&space_json, &ValidationLimits::default()).is_ok());
+
+    assert_eq!(report.from_version, 1);
+    assert_eq!(report.to_version, CURRENT_SCHEMA_VERSION);
+    assert_eq!(report.changes, vec!["Upgraded schemaVersion 1 to 2.".to_string()]);
+}
+
+#[test]
+fn test_migrate_v1_fixture_removes_unknown_fields() {
+    let value = fixture(include_str!("fixtures/space_v1_extra_fields.json"));
+    let (space_json, report) = migrate(value).unwrap();
+
+    assert!(space_json.blocks[0].material.is_none(), "v1 material must not gain v2 meaning");
+    assert!(validate_space_json(&space_json, &ValidationLimits::default()).is_ok());
+    assert_eq!(report.changes.len(), 3);
+    assert!(report.changes[0].contains("'material' from block 0"));
+    assert!(report.changes[1].contains("'selected' from block 1"));
+}
+
+#[test]
+fn test_migrate_v2_fixture_is_noop() {
+    let value = fixture(include_str!("fixtures/space_v2.json"));
+    let (migrated, report) = migrate_value(value.clone()).unwrap();
+
+    assert_eq!(migrated, value);
+    assert!(report.is_noop());
+    assert_eq!(report.from_version, 2);
+
+    let (space_json, _) = migrate(value).unwrap();
+    assert_eq!(space_json.blocks[1].material.as_deref(), Some("Glass"));
+    let world = space_json.world.as_ref().expect("v2 fixture has world settings");
+    assert_eq!(world.lighting.as_ref().unwrap().time_of_day, Some(18.5));
+    assert!(validate_space_json(&space_json, &ValidationLimits::default()).is_ok());
+}
+
+#[test]
+fn test_migrate_v1_removes_world_section() {
+    let value = json!({
+        "schemaVersion": 1,
+        "blocks": [],
+        "world": { "baseplate": { "enabled": false } }
+    });
+    let (space_json, report) = migrate(value).unwrap();
+
+    assert!(space_json.world.is_none(), "v1 payloads cannot carry world settings");
+    assert_eq!(report.changes[0], "Removed field 'world' (not part of schema version 1).");
+}
+
+#[test]
+fn test_migrate_value_updates_schema_version() {
+    let (migrated, _) = migrate_value(fixture(include_str!("fixtures/space_v1.json"))).unwrap();
+    assert_eq!(migrated["schemaVersion"], json!(CURRENT_SCHEMA_VERSION));
+}
+
+// Error tests
+
+#[test]
+fn test_migrate_rejects_future_version() {
+    let value = json!({ "schemaVersion": CURRENT_SCHEMA_VERSION + 1, "blocks": [] });
+    assert!(matches!(
+        migrate(value),
+        Err(MigrationError::UnsupportedSchemaVersion { .. })
+    ));
+}
+
+#[test]
+fn test_migrate_rejects_version_0() {
+    let value = json!({ "schemaVersion": 0, "blocks": [] });
+    let error = migrate(value).unwrap_err();
+    assert!(matches!(error, MigrationError::UnsupportedSchemaVersion { version: 0 }));
+    assert_eq!(error.error_code(), "INVALID_SCHEMA_VERSION");
+}
+
+#[test]
+fn test_migrate_rejects_missing_version() {
+    let value = json!({ "blocks": [] });
+    assert!(matches!(migrate(value), Err(MigrationError::MissingSchemaVersion)));
+}
+
+#[test]
+fn test_migrate_rejects_non_object() {
+    assert!(matches!(
+        migrate(json!([1, 2, 3])),
+        Err(MigrationError::InvalidPayload { .. })
+    ));
+}
+
+#[test]
+fn test_migrate_rejects_malformed_blocks() {
+    let value = json!({ "schemaVersion": 1, "blocks": [{ "x": "zero", "y": 0, "z": 0, "color": "#FFF" }] });
+    assert!(matches!(migrate(value), Err(MigrationError::InvalidPayload { .. })));
+}
@@ -0,0 +1,282 @@
+//! Tests for playability analysis.
+
+mod common;
+
+use backend::models::{BaseplateSettings, Block, BlockSize, SpaceJSON, SpawnSettings, WorldPosition, WorldSettings};
+use backend::playability::{analyze_playability, PlayabilityError, Surface, MAX_COLUMNS};
+use backend::spawn::{clear_spawns, SpawnClearance};
+use common::{block, level};
+
+const GRAY: &str = "#808080";
+
+fn kind_block(x: i32, y: i32, z: i32, kind: &str) -> Block {
+    Block {
+        kind: Some(kind.to_string()),
+        ..block(x, y, z, GRAY)
+    }
+}
+
+/// A level without a baseplate whose spawn pad (top at Y=1, edge at X=7) floats
+/// over the void, with one stepping stone at X=16 and a finish block at `finish_x`.
+fn stepping_stone_level(stone_kind: Option<&str>, finish_x: i32) -> SpaceJSON {
+    let stone = Block {
+        kind: stone_kind.map(str::to_string),
+        ..block(16, 0, 0, GRAY)
+    };
+    SpaceJSON {
+        world: Some(WorldSettings {
+            baseplate: Some(BaseplateSettings {
+                enabled: Some(false),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..level(vec![stone, kind_block(finish_x, 0, 0, "finish")])
+    }
+}
+
+/// Returns the surfaces of block `index`.
+fn surfaces_of(surfaces: &[Surface], index: usize) -> Vec<Surface> {
+    surfaces.iter().copied().filter(|surface| surface.block == index).collect()
+}
+
+#[test]
+fn test_empty_level_is_not_completable() {
+    let report = analyze_playability(&level(vec![])).unwrap();
+    assert!(!report.completable);
+    assert!(report.surfaces.is_empty());
+    assert!(report.unreachable_finishes.is_empty());
+}
+
+#[test]
+fn test_jump_height_limits_reachable_surfaces() {
+    let report = analyze_playability(&level(vec![
+        // Top at Y=7, within a 7.2-stud jump from the baseplate
+        block(20, 6, 0, GRAY),
+        // Top at Y=9, out of reach
+        block(40, 8, 0, GRAY),
+    ])).unwrap();
+    assert_eq!(
+        report.surfaces,
+        vec![
+            Surface {
+                block: 0,
+                x: 20,
+                z: 0,
+                y: 7.0,
+                reachable: true
+            },
+            Surface {
+                block: 1,
+                x: 40,
+                z: 0,
+                y: 9.0,
+                reachable: false
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_staircase_to_finish_is_completable() {
+    let mut blocks: Vec<Block> = (0..10).map(|step| block(10 + 2 * step, 4 * step, 0, GRAY)).collect();
+    blocks.push(kind_block(20, 8, 10, "checkpoint"));
+    blocks.push(kind_block(30, 40, 0, "finish"));
+    let report = analyze_playability(&level(blocks)).unwrap();
+
+    assert!(report.surfaces.iter().all(|surface| surface.reachable));
+    assert!(report.unreachable_checkpoints.is_empty());
+    assert!(report.unreachable_finishes.is_empty());
+    assert!(report.completable);
+}
+
+#[test]
+fn test_unreachable_goals_are_reported() {
+    let report = analyze_playability(&level(vec![
+        kind_block(20, 0, 0, "checkpoint"),
+        kind_block(40, 20, 0, "checkpoint"),
+        kind_block(60, 30, 0, "finish"),
+    ])).unwrap();
+    assert_eq!(report.unreachable_checkpoints, vec![1]);
+    assert_eq!(report.unreachable_finishes, vec![2]);
+    assert!(!report.completable);
+}
+
+#[test]
+fn test_goals_touched_from_the_side_are_reached() {
+    // A finish block hanging 9 studs up can't be stood on but can be jumped into
+    let report = analyze_playability(&level(vec![kind_block(20, 10, 0, "finish")])).unwrap();
+    assert!(!surfaces_of(&report.surfaces, 0)[0].reachable);
+    assert!(report.unreachable_finishes.is_empty());
+    assert!(report.completable);
+}
+
+#[test]
+fn test_jump_distance_limits_gaps() {
+    // The stone is 8 studs from the pad; the finish another 8 or 10 studs on
+    let report = analyze_playability(&stepping_stone_level(None, 26)).unwrap();
+    assert!(surfaces_of(&report.surfaces, 0)[0].reachable);
+    assert!(report.completable);
+
+    let report = analyze_playability(&stepping_stone_level(None, 28)).unwrap();
+    assert!(surfaces_of(&report.surfaces, 0)[0].reachable);
+    assert_eq!(report.unreachable_finishes, vec![1]);
+}
+
+#[test]
+fn test_gameplay_blocks_change_movement() {
+    // Speed blocks double the distance of jumps starting on them
+    let report = analyze_playability(&stepping_stone_level(Some("speed"), 30)).unwrap();
+    assert!(report.completable);
+    let report = analyze_playability(&stepping_stone_level(None, 30)).unwrap();
+    assert!(!report.completable);
+
+    // Kill blocks can't be stood on
+    let report = analyze_playability(&stepping_stone_level(Some("kill"), 26)).unwrap();
+    assert!(surfaces_of(&report.surfaces, 0).is_empty());
+    assert_eq!(report.unreachable_finishes, vec![1]);
+
+    // Bounce blocks launch characters far higher than a jump
+    let report = analyze_playability(&level(vec![kind_block(28, 0, 0, "bounce"), block(30, 14, 0, GRAY)])).unwrap();
+    assert!(surfaces_of(&report.surfaces, 1)[0].reachable);
+}
+
+#[test]
+fn test_surfaces_need_headroom() {
+    let report = analyze_playability(&level(vec![
+        // Covered by a block 2 studs above: too low to stand under
+        block(20, 0, 0, GRAY),
+        block(20, 4, 0, GRAY),
+        // Covered 6 studs above: just enough to stand under
+        block(30, 0, 0, GRAY),
+        block(30, 8, 0, GRAY),
+    ])).unwrap();
+    assert!(surfaces_of(&report.surfaces, 0).is_empty());
+    assert!(surfaces_of(&report.surfaces, 2)[0].reachable);
+}
+
+#[test]
+fn test_low_ceilings_limit_jumps() {
+    // A block beside the spawn pad, 4 studs higher than the pad's top
+    let mut space_json = stepping_stone_level(None, 40);
+    space_json.blocks = vec![block(8, 4, 0, GRAY)];
+    assert!(surfaces_of(&analyze_playability(&space_json).unwrap().surfaces, 0)[0].reachable);
+
+    // A roof 6 studs above the pad leaves 1 stud to jump
+    space_json.blocks.push(Block {
+        size: Some(BlockSize { x: 12.0, y: 2.0, z: 12.0 }),
+        ..block(0, 8, 0, GRAY)
+    });
+    assert!(!surfaces_of(&analyze_playability(&space_json).unwrap().surfaces, 0)[0].reachable);
+}
+
+#[test]
+fn test_large_blocks_have_one_surface_per_column() {
+    let platform = Block {
+        size: Some(BlockSize { x: 6.0, y: 2.0, z: 2.0 }),
+        ..block(20, 0, 0, GRAY)
+    };
+    let report = analyze_playability(&level(vec![platform])).unwrap();
+    let columns: Vec<_> = report.surfaces.iter().map(|surface| (surface.x, surface.z)).collect();
+    assert_eq!(columns, vec![(18, 0), (20, 0), (22, 0)]);
+}
+
+#[test]
+fn test_huge_levels_are_not_analyzed() {
+    // One thin 2048x2048 plate covers over a million columns
+    let plate = Block {
+        size: Some(BlockSize { x: 2048.0, y: 0.1, z: 2048.0 }),
+        ..block(0, 10, 0, GRAY)
+    };
+    let error = analyze_playability(&level(vec![plate])).unwrap_err();
+    assert!(matches!(
+        error,
+        PlayabilityError::LevelTooLarge { columns, limit: MAX_COLUMNS } if columns > 1_048_576
+    ));
+    assert_eq!(error.error_code(), "LEVEL_TOO_LARGE_TO_ANALYZE");
+
+    // Stacked large blocks are counted once per block
+    let stack: Vec<Block> = (0..10_000)
+        .map(|y| Block {
+            size: Some(BlockSize { x: 200.0, y: 2.0, z: 200.0 }),
+            ..block(0, 2 * y, 0, GRAY)
+        })
+        .collect();
+    assert!(matches!(
+        analyze_playability(&level(stack)),
+        Err(PlayabilityError::LevelTooLarge { columns, .. }) if columns > 100_000_000
+    ));
+}
+
+#[test]
+fn test_large_levels_within_the_cap_are_analyzed() {
+    // A 400x400 floor with a tall stack of blocks standing on it
+    let mut blocks = vec![Block {
+        size: Some(BlockSize { x: 400.0, y: 2.0, z: 400.0 }),
+        ..block(0, 0, 0, GRAY)
+    }];
+    blocks.extend((1..=2_000).map(|y| block(100, 2 * y, 100, GRAY)));
+    blocks.push(kind_block(-100, 2, -100, "finish"));
+    let report = analyze_playability(&level(blocks)).unwrap();
+
+    assert!(report.completable);
+    // Only the top of the stack has headroom
+    assert_eq!(report.surfaces.iter().filter(|surface| surface.x == 100 && surface.z == 100).count(), 1);
+    assert!(!surfaces_of(&report.surfaces, 2_000)[0].reachable);
+}
+
+#[test]
+fn test_buried_spawn_reaches_nothing() {
+    let roof = Block {
+        size: Some(BlockSize { x: 12.0, y: 2.0, z: 12.0 }),
+        ..block(0, 2, 0, GRAY)
+    };
+    let space_json = level(vec![roof, kind_block(20, 0, 0, "finish")]);
+    let report = analyze_playability(&space_json).unwrap();
+    assert!(report.surfaces.iter().all(|surface| !surface.reachable));
+    assert_eq!(report.unreachable_finishes, vec![1]);
+
+    // Relocating the spawn onto the roof frees the player
+    let report = analyze_playability(&clear_spawns(&space_json, SpawnClearance::Relocate)).unwrap();
+    assert!(report.completable);
+}
+
+#[test]
+fn test_spawn_blocks_and_positions_are_starting_points() {
+    // A platform 20 studs up, out of jumping range of an explicit spawn on the baseplate
+    let mut space_json = level(vec![
+        Block {
+            size: Some(BlockSize { x: 12.0, y: 2.0, z: 12.0 }),
+            ..block(40, 19, 40, GRAY)
+        },
+        kind_block(40, 21, 40, "finish"),
+    ]);
+    space_json.world = Some(WorldSettings {
+        spawn: Some(SpawnSettings {
+            auto: None,
+            position: Some(WorldPosition { x: 0.0, y: 0.5, z: 0.0 }),
+        }),
+        ..Default::default()
+    });
+    assert!(!analyze_playability(&space_json).unwrap().completable);
+
+    // A spawn block on the platform replaces the default spawn
+    space_json.blocks.push(Block {
+        spawn: Some(Default::default()),
+        ..block(36, 21, 36, GRAY)
+    });
+    assert!(analyze_playability(&space_json).unwrap().completable);
+}
+
+#[test]
+fn test_report_json_shape() {
+    let report = analyze_playability(&level(vec![kind_block(20, 0, 0, "finish")])).unwrap();
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["completable"], true);
+    assert_eq!(json["unreachableCheckpoints"], serde_json::json!([]));
+    assert_eq!(json["unreachableFinishes"], serde_json::json!([]));
+    assert_eq!(
+        json["surfaces"],
+        serde_json::json!([{ "block": 0, "x": 20, "z": 0, "y": 1.0, "reachable": true }])
+    );
+}
@@ -0,0 +1,90 @@
+//! Tests for per-client request and block budgets.
+
+use backend::rate_limit::{forwarded_client_address, RateLimitConfig, RateLimitError, RateLimiter};
+
+fn limiter(requests: u32, blocks: u32) -> RateLimiter {
+    RateLimiter::new(RateLimitConfig {
+        requests,
+        blocks,
+        window_seconds: 60,
+    })
+}
+
+#[test]
+fn test_requests_burst_then_refill() {
+    let limiter = limiter(3, 0);
+    for _ in 0..3 {
+        limiter.check_request("a", 0).unwrap();
+    }
+    let error = limiter.check_request("a", 0).unwrap_err();
+    assert_eq!(error.error_code(), "RATE_LIMITED");
+    // One request refills every 20 seconds
+    assert_eq!(error.retry_after_seconds(), 20);
+
+    assert!(limiter.check_request("a", 19_999).is_err());
+    limiter.check_request("a", 20_000).unwrap();
+    assert!(limiter.check_request("a", 20_000).is_err());
+}
+
+#[test]
+fn test_clients_have_separate_budgets() {
+    let limiter = limiter(1, 0);
+    limiter.check_request("a", 0).unwrap();
+    assert!(limiter.check_request("a", 0).is_err());
+    limiter.check_request("b", 0).unwrap();
+}
+
+#[test]
+fn test_block_quota() {
+    let limiter = limiter(0, 10_000);
+    limiter.check_blocks("a", 6_000, 0).unwrap();
+    let error = limiter.check_blocks("a", 6_000, 0).unwrap_err();
+    assert_eq!(
+        error,
+        RateLimitError::BlockQuotaExceeded {
+            blocks: 6_000,
+            // 2,000 missing blocks refill in 12 seconds
+            retry_after_seconds: 12,
+        }
+    );
+    assert_eq!(error.error_code(), "BLOCK_QUOTA_EXCEEDED");
+
+    // Requests are unlimited with a budget of 0
+    for _ in 0..1_000 {
+        limiter.check_request("a", 0).unwrap();
+    }
+}
+
+#[test]
+fn test_exports_larger_than_the_quota_need_a_full_bucket() {
+    let limiter = limiter(0, 1_000);
+    limiter.check_blocks("a", 5_000, 0).unwrap();
+    assert!(limiter.check_blocks("a", 1, 0).is_err());
+    assert!(limiter.check_blocks("a", 5_000, 59_999).is_err());
+    limiter.check_blocks("a", 5_000, 60_000).unwrap();
+}
+
+#[test]
+fn test_retry_after_is_at_least_one_second() {
+    let limiter = RateLimiter::new(RateLimitConfig {
+        requests: 1_000,
+        blocks: 0,
+        window_seconds: 1,
+    });
+    for _ in 0..1_000 {
+        limiter.check_request("a", 0).unwrap();
+    }
+    assert_eq!(limiter.check_request("a", 0).unwrap_err().retry_after_seconds(), 1);
+}
+
+#[test]
+fn test_forwarded_client_address_ignores_spoofed_entries() {
+    assert_eq!(forwarded_client_address("203.0.113.7"), Some("203.0.113.7"));
+    assert_eq!(forwarded_client_address("10.0.0.1, 203.0.113.7"), Some("203.0.113.7"));
+    // A client prepending its own entries cannot change the key
+    assert_eq!(
+        forwarded_client_address("198.51.100.1, 10.0.0.1, 203.0.113.7"),
+        forwarded_client_address("203.0.113.7")
+    );
+    assert_eq!(forwarded_client_address("203.0.113.7, "), None);
+}
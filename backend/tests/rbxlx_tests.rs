@@ -1,7 +1,14 @@
 //! Tests for RBXLX file generation.
 
-use backend::models::{Block, SpaceJSON};
-use backend::rbxlx::{calculate_spawn_position, generate_rbxlx, hex_to_color3};
+use backend::models::{
+    BaseplateSettings, Block, BlockSize, FogSettings, LightingSettings, SpaceJSON, SpawnSettings,
+    WorldPosition, WorldSettings,
+};
+use backend::rbxlx::{
+    build_model_dom, build_place_dom, calculate_spawn_position, generate_export, generate_rbxlx,
+    hex_to_color3, write_dom, ExportOptions, OutputFormat,
+};
+use rbx_types::{Variant, Vector3};
 
 // Color conversion tests
 
@@ -53,6 +60,7 @@ fn test_calculate_spawn_position_single() {
         y: 10,
         z: 40,
         color: "#FF0000".to_string(),
+        ..Default::default()
     }];
     let (x, y, z) = calculate_spawn_position(&blocks);
     assert_eq!(x, 20.0);
@@ -63,9 +71,9 @@ fn test_calculate_spawn_position_single() {
 #[test]
 fn test_calculate_spawn_position_multiple() {
     let blocks = vec![
-        Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string() },
-        Block { x: 20, y: 10, z: 20, color: "#00FF00".to_string() },
-        Block { x: 40, y: 6, z: 40, color: "#0000FF".to_string() },
+        Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+        Block { x: 20, y: 10, z: 20, color: "#00FF00".to_string(), ..Default::default() },
+        Block { x: 40, y: 6, z: 40, color: "#0000FF".to_string(), ..Default::default() },
     ];
     let (x, y, z) = calculate_spawn_position(&blocks);
     assert_eq!(x, 20.0);
@@ -81,6 +89,7 @@ fn test_generate_rbxlx_empty() {
         schema_version: 1,
         name: Some("Test Level".to_string()),
         blocks: vec![],
+        ..Default::default()
     };
     let result = generate_rbxlx(&space_json);
     assert!(result.is_ok());
@@ -102,7 +111,9 @@ fn test_generate_rbxlx_single_block() {
             y: 0,
             z: 0,
             color: "#FF0000".to_string(),
+            ..Default::default()
         }],
+        ..Default::default()
     };
     let result = generate_rbxlx(&space_json);
     assert!(result.is_ok());
@@ -118,9 +129,10 @@ fn test_generate_rbxlx_multiple_blocks() {
         schema_version: 1,
         name: Some("Test Level".to_string()),
         blocks: vec![
-            Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string() },
-            Block { x: 1, y: 0, z: 0, color: "#00FF00".to_string() },
+            Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+            Block { x: 1, y: 0, z: 0, color: "#00FF00".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
     let result = generate_rbxlx(&space_json);
     assert!(result.is_ok());
@@ -134,6 +146,7 @@ fn test_empty_level_export() {
         schema_version: 1,
         name: Some("Empty Level".to_string()),
         blocks: vec![],
+        ..Default::default()
     };
     let result = generate_rbxlx(&space_json);
     assert!(result.is_ok(), "Empty level should generate valid RBXLX");
@@ -159,7 +172,9 @@ fn test_single_block_export() {
             y: 5,
             z: 20,
             color: "#FF0000".to_string(),
+            ..Default::default()
         }],
+        ..Default::default()
     };
     let result = generate_rbxlx(&space_json);
     assert!(result.is_ok());
@@ -180,6 +195,7 @@ fn test_multi_block_export() {
                 y: 0,
                 z,
                 color: "#00FF00".to_string(),
+                ..Default::default()
             })
         })
         .collect();
@@ -188,6 +204,7 @@ fn test_multi_block_export() {
         schema_version: 1,
         name: Some("Multi Block Level".to_string()),
         blocks,
+        ..Default::default()
     };
 
     let start = std::time::Instant::now();
@@ -214,6 +231,7 @@ fn test_maximum_block_export() {
                 y: 0,
                 z: z - 50,
                 color: "#0000FF".to_string(),
+                ..Default::default()
             })
         })
         .collect();
@@ -222,6 +240,7 @@ fn test_maximum_block_export() {
         schema_version: 1,
         name: Some("Maximum Block Level".to_string()),
         blocks,
+        ..Default::default()
     };
 
     let start = std::time::Instant::now();
@@ -255,7 +274,7 @@ fn test_various_colors() {
     ];
 
     for (hex, name, expected_r, expected_g, expected_b) in &color_tests {
-        let color = hex_to_color3(hex).expect(&format!("Color {} should parse", name));
+        let color = hex_to_color3(hex).unwrap_or_else(|_| panic!("Color {} should parse", name));
         assert!((color.r - expected_r).abs() < 0.01, "Color {} R mismatch", name);
         assert!((color.g - expected_g).abs() < 0.01, "Color {} G mismatch", name);
         assert!((color.b - expected_b).abs() < 0.01, "Color {} B mismatch", name);
@@ -269,6 +288,7 @@ fn test_various_colors() {
             y: 0,
             z: 0,
             color: hex.to_string(),
+            ..Default::default()
         })
         .collect();
 
@@ -276,6 +296,7 @@ fn test_various_colors() {
         schema_version: 1,
         name: Some("Color Test Level".to_string()),
         blocks,
+        ..Default::default()
     };
 
     let result = generate_rbxlx(&space_json);
@@ -291,18 +312,19 @@ fn test_various_colors() {
 #[test]
 fn test_coordinate_edge_cases() {
     let edge_blocks = vec![
-        Block { x: -1000, y: 0, z: 0, color: "#FF0000".to_string() },
-        Block { x: 1000, y: 0, z: 0, color: "#00FF00".to_string() },
-        Block { x: 0, y: 0, z: -1000, color: "#0000FF".to_string() },
-        Block { x: 0, y: 0, z: 1000, color: "#FFFF00".to_string() },
-        Block { x: 0, y: 0, z: 0, color: "#FF00FF".to_string() },
-        Block { x: 0, y: 1000, z: 0, color: "#00FFFF".to_string() },
+        Block { x: -1000, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+        Block { x: 1000, y: 0, z: 0, color: "#00FF00".to_string(), ..Default::default() },
+        Block { x: 0, y: 0, z: -1000, color: "#0000FF".to_string(), ..Default::default() },
+        Block { x: 0, y: 0, z: 1000, color: "#FFFF00".to_string(), ..Default::default() },
+        Block { x: 0, y: 0, z: 0, color: "#FF00FF".to_string(), ..Default::default() },
+        Block { x: 0, y: 1000, z: 0, color: "#00FFFF".to_string(), ..Default::default() },
     ];
 
     let space_json = SpaceJSON {
         schema_version: 1,
         name: Some("Edge Case Level".to_string()),
         blocks: edge_blocks.clone(),
+        ..Default::default()
     };
 
     let result = generate_rbxlx(&space_json);
@@ -326,6 +348,7 @@ fn test_baseplate_creation() {
         schema_version: 1,
         name: Some("Baseplate Test".to_string()),
         blocks: vec![],
+        ..Default::default()
     };
     let result = generate_rbxlx(&space_json);
     assert!(result.is_ok());
@@ -343,6 +366,7 @@ fn test_spawn_platform_fixed_position() {
         schema_version: 1,
         name: Some("Empty Level".to_string()),
         blocks: vec![],
+        ..Default::default()
     };
     let result = generate_rbxlx(&space_json);
     assert!(result.is_ok());
@@ -363,9 +387,10 @@ fn test_spawn_platform_with_blocks() {
         schema_version: 1,
         name: Some("Level with Blocks".to_string()),
         blocks: vec![
-            Block { x: 10, y: 5, z: 20, color: "#FF0000".to_string() },
-            Block { x: -10, y: 3, z: -20, color: "#00FF00".to_string() },
+            Block { x: 10, y: 5, z: 20, color: "#FF0000".to_string(), ..Default::default() },
+            Block { x: -10, y: 3, z: -20, color: "#00FF00".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
     let result = generate_rbxlx(&space_json);
     assert!(result.is_ok());
@@ -383,6 +408,7 @@ fn test_spawn_platform_decal() {
         schema_version: 1,
         name: Some("Decal Test".to_string()),
         blocks: vec![],
+        ..Default::default()
     };
     let result = generate_rbxlx(&space_json);
     assert!(result.is_ok());
@@ -401,3 +427,353 @@ fn test_spawn_platform_decal() {
         "Texture should be Content type, not string"
     );
 }
+
+// Schema version 2 generation tests
+
+#[test]
+fn test_generate_rbxlx_v2_properties() {
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        name: Some("V2 Level".to_string()),
+        blocks: vec![Block {
+            x: 0,
+            y: 4,
+            z: 0,
+            color: "#00FFFF".to_string(),
+            material: Some("glass".to_string()),
+            transparency: Some(0.5),
+            reflectance: Some(0.25),
+            size: Some(BlockSize { x: 6.0, y: 1.0, z: 4.0 }),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let xml = generate_rbxlx(&space_json).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+
+    assert!(xml_str.contains(r#"<token name="Material">1568</token>"#), "Glass material should be exported");
+    assert!(xml_str.contains(r#"<float name="Transparency">0.5</float>"#));
+    assert!(xml_str.contains(r#"<float name="Reflectance">0.25</float>"#));
+    assert!(xml_str.contains("<X>6</X>"), "Custom block size should be exported");
+}
+
+#[test]
+fn test_generate_rbxlx_v1_block_keeps_defaults() {
+    let space_json = SpaceJSON {
+        schema_version: 1,
+        name: None,
+        blocks: vec![Block {
+            x: 0,
+            y: 0,
+            z: 0,
+            color: "#FF0000".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let xml = generate_rbxlx(&space_json).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+
+    assert!(!xml_str.contains(r#"name="Reflectance""#));
+    // Only the baseplate sets a material
+    assert_eq!(xml_str.matches(r#"name="Material""#).count(), 1);
+}
+
+// Output format tests
+
+fn format_test_level() -> SpaceJSON {
+    SpaceJSON {
+        schema_version: 1,
+        name: Some("Format Test".to_string()),
+        blocks: (0..3)
+            .map(|i| Block {
+                x: i * 2,
+                y: 0,
+                z: 0,
+                color: "#FF0000".to_string(),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_place_format_names() {
+    assert_eq!(OutputFormat::from_name("rbxlx"), Some(OutputFormat::Xml));
+    assert_eq!(OutputFormat::from_name("RBXL"), Some(OutputFormat::Binary));
+    assert_eq!(OutputFormat::from_name("obj"), None);
+    assert_eq!(OutputFormat::default(), OutputFormat::Xml);
+    assert_eq!(OutputFormat::Binary.extension(), "rbxl");
+    assert_eq!(OutputFormat::Xml.extension(), "rbxlx");
+}
+
+#[test]
+fn test_generate_export_xml_matches_generate_rbxlx() {
+    let space_json = format_test_level();
+    let xml = generate_export(&space_json, &ExportOptions::default(), OutputFormat::Xml).unwrap();
+    let expected = generate_rbxlx(&space_json).unwrap();
+
+    // Referents differ between runs, so compare the instances written instead of raw bytes
+    let part_count = |content: &[u8]| String::from_utf8_lossy(content).matches("<Item class=\"Part\"").count();
+    assert_eq!(part_count(&xml), part_count(&expected));
+    assert_eq!(part_count(&xml), 4);
+}
+
+#[test]
+fn test_generate_export_binary() {
+    let space_json = format_test_level();
+    let binary = generate_export(&space_json, &ExportOptions::default(), OutputFormat::Binary).unwrap();
+    assert!(binary.starts_with(b"<roblox!"), "Binary places start with the rbxl magic header");
+
+    let dom = rbx_binary::from_reader(binary.as_slice()).expect("binary output should decode");
+    let workspace = dom
+        .root()
+        .children()
+        .iter()
+        .map(|&r| dom.get_by_ref(r).unwrap())
+        .find(|i| i.class == "Workspace")
+        .expect("binary place should contain Workspace");
+    let part_count = workspace
+        .children()
+        .iter()
+        .filter(|&&r| dom.get_by_ref(r).unwrap().class == "Part")
+        .count();
+    assert_eq!(part_count, 4, "Should have 4 Parts (baseplate + 3 blocks)");
+}
+
+#[test]
+fn test_build_place_dom_writes_both_formats() {
+    let dom = build_place_dom(&format_test_level(), &ExportOptions::default());
+    let xml = write_dom(&dom, OutputFormat::Xml).unwrap();
+    let binary = write_dom(&dom, OutputFormat::Binary).unwrap();
+    assert!(String::from_utf8_lossy(&xml).contains("<roblox"));
+    assert!(binary.starts_with(b"<roblox!"));
+}
+
+#[test]
+fn test_lighting_technology_is_token() {
+    let xml = generate_rbxlx(&format_test_level()).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+    assert!(xml_str.contains(r#"<token name="Technology">3</token>"#));
+}
+
+// Model export tests
+
+#[test]
+fn test_model_format_names() {
+    assert_eq!(OutputFormat::from_name("rbxmx"), Some(OutputFormat::ModelXml));
+    assert_eq!(OutputFormat::from_name("rbxm"), Some(OutputFormat::ModelBinary));
+    assert!(OutputFormat::ModelBinary.is_model());
+    assert!(OutputFormat::ModelBinary.is_binary());
+    assert!(!OutputFormat::Binary.is_model());
+    assert_eq!(OutputFormat::ModelXml.extension(), "rbxmx");
+}
+
+#[test]
+fn test_generate_model_omits_place_content() {
+    let xml = generate_export(&format_test_level(), &ExportOptions::default(), OutputFormat::ModelXml).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+
+    assert_eq!(xml_str.matches(r#"<Item class="Model""#).count(), 1);
+    assert_eq!(xml_str.matches(r#"<Item class="Part""#).count(), 3, "Only block Parts are exported");
+    assert!(!xml_str.contains("Baseplate"));
+    assert!(!xml_str.contains("SpawnLocation"));
+    assert!(!xml_str.contains(r#"<Item class="Workspace""#));
+    assert!(!xml_str.contains(r#"<Item class="Lighting""#));
+    assert!(xml_str.contains(r#"<string name="Name">Format Test</string>"#));
+}
+
+#[test]
+fn test_build_model_dom_primary_part_and_pivot() {
+    let dom = build_model_dom(&format_test_level(), &ExportOptions::default());
+    let top_level = dom.root().children();
+    assert_eq!(top_level.len(), 1);
+
+    let model = dom.get_by_ref(top_level[0]).unwrap();
+    assert_eq!(model.class, "Model");
+    assert_eq!(model.children().len(), 3);
+
+    let primary_part = match model.properties.get(&rbx_dom_weak::ustr("PrimaryPart")) {
+        Some(Variant::Ref(primary_part)) => *primary_part,
+        other => panic!("PrimaryPart should be a Ref, got {:?}", other),
+    };
+    assert_eq!(primary_part, model.children()[0]);
+
+    // Blocks at X = 0, 2, 4 span -1..5 studs, so the pivot sits at X = 2
+    match model.properties.get(&rbx_dom_weak::ustr("WorldPivotData")) {
+        Some(Variant::OptionalCFrame(Some(pivot))) => {
+            assert_eq!(pivot.position, Vector3::new(2.0, 0.0, 0.0));
+        }
+        other => panic!("WorldPivotData should be a CFrame, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_build_model_dom_empty_level() {
+    let space_json = SpaceJSON {
+        schema_version: 1,
+        name: None,
+        blocks: vec![],
+        ..Default::default()
+    };
+    let dom = build_model_dom(&space_json, &ExportOptions::default());
+    let model = dom.get_by_ref(dom.root().children()[0]).unwrap();
+
+    assert!(model.children().is_empty());
+    assert!(!model.properties.contains_key(&rbx_dom_weak::ustr("PrimaryPart")));
+    assert!(write_dom(&dom, OutputFormat::ModelXml).is_ok());
+}
+
+#[test]
+fn test_generate_model_merged_blocks() {
+    let options = ExportOptions { merge_blocks: true, ..Default::default() };
+    let xml = generate_export(&format_test_level(), &options, OutputFormat::ModelXml).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+    assert_eq!(xml_str.matches(r#"<Item class="Part""#).count(), 1);
+}
+
+#[test]
+fn test_generate_model_binary() {
+    let binary = generate_export(&format_test_level(), &ExportOptions::default(), OutputFormat::ModelBinary).unwrap();
+    let dom = rbx_binary::from_reader(binary.as_slice()).expect("binary model should decode");
+    let top_level: Vec<_> = dom
+        .root()
+        .children()
+        .iter()
+        .map(|&r| dom.get_by_ref(r).unwrap())
+        .collect();
+    assert_eq!(top_level.len(), 1);
+    assert_eq!(top_level[0].class, "Model");
+    assert_eq!(top_level[0].children().len(), 3);
+}
+
+// World settings tests
+
+fn world_test_level(world: WorldSettings) -> SpaceJSON {
+    SpaceJSON {
+        schema_version: 2,
+        world: Some(world),
+        ..format_test_level()
+    }
+}
+
+/// Returns the XML of the first `<Item>` of the given class.
+fn item_section<'a>(xml: &'a str, class: &str) -> &'a str {
+    let start = xml
+        .find(&format!(r#"<Item class="{}""#, class))
+        .unwrap_or_else(|| panic!("{} should exist", class));
+    let end = xml[start..].find("</Properties>").unwrap() + start;
+    &xml[start..end]
+}
+
+#[test]
+fn test_world_defaults_match_previous_output() {
+    let xml = generate_rbxlx(&world_test_level(WorldSettings::default())).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+
+    let baseplate = item_section(&xml_str, "Part");
+    assert!(baseplate.contains("<X>200</X>") && baseplate.contains("<Y>16</Y>"));
+    assert!(baseplate.contains("<Y>-8</Y>"));
+    let spawn = item_section(&xml_str, "SpawnLocation");
+    assert!(spawn.contains("<Y>0.5</Y>"));
+    assert!(xml_str.contains(r#"<token name="Technology">3</token>"#));
+}
+
+#[test]
+fn test_world_baseplate_disabled() {
+    let world = WorldSettings {
+        baseplate: Some(BaseplateSettings {
+            enabled: Some(false),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let xml = generate_rbxlx(&world_test_level(world)).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+
+    assert!(!xml_str.contains("Baseplate"));
+    assert_eq!(xml_str.matches(r#"<Item class="Part""#).count(), 3);
+}
+
+#[test]
+fn test_world_custom_baseplate() {
+    let world = WorldSettings {
+        baseplate: Some(BaseplateSettings {
+            enabled: None,
+            size: Some(BlockSize { x: 64.0, y: 4.0, z: 32.0 }),
+            color: Some("#00FF00".to_string()),
+            material: Some("Grass".to_string()),
+        }),
+        ..Default::default()
+    };
+    let xml = generate_rbxlx(&world_test_level(world)).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+
+    let baseplate = item_section(&xml_str, "Part");
+    assert!(baseplate.contains("Baseplate"));
+    assert!(baseplate.contains("<X>64</X>") && baseplate.contains("<Z>32</Z>"));
+    assert!(baseplate.contains("<Y>-2</Y>"), "Top surface should stay at Y=0");
+    assert!(baseplate.contains(r#"<token name="Material">1280</token>"#));
+}
+
+#[test]
+fn test_world_spawn_auto_uses_calculated_position() {
+    let world = WorldSettings {
+        spawn: Some(SpawnSettings {
+            auto: Some(true),
+            position: None,
+        }),
+        ..Default::default()
+    };
+    let space_json = world_test_level(world);
+    let (x, y, z) = calculate_spawn_position(&space_json.blocks);
+    let xml = generate_rbxlx(&space_json).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+
+    let spawn: String = item_section(&xml_str, "SpawnLocation").split_whitespace().collect();
+    assert!(spawn.contains(&format!("<X>{}</X><Y>{}</Y><Z>{}</Z>", x, y + 0.5, z)));
+}
+
+#[test]
+fn test_world_spawn_explicit_position() {
+    let world = WorldSettings {
+        spawn: Some(SpawnSettings {
+            auto: None,
+            position: Some(WorldPosition { x: 10.0, y: 20.5, z: -6.0 }),
+        }),
+        ..Default::default()
+    };
+    let xml = generate_rbxlx(&world_test_level(world)).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+
+    let spawn: String = item_section(&xml_str, "SpawnLocation").split_whitespace().collect();
+    assert!(spawn.contains("<X>10</X><Y>20.5</Y><Z>-6</Z>"));
+}
+
+#[test]
+fn test_world_lighting() {
+    let world = WorldSettings {
+        lighting: Some(LightingSettings {
+            technology: Some("Future".to_string()),
+            time_of_day: Some(18.5),
+            ambient: Some("#FF0000".to_string()),
+            fog: Some(FogSettings {
+                color: Some("#0000FF".to_string()),
+                start: Some(50.0),
+                end: Some(400.0),
+            }),
+        }),
+        ..Default::default()
+    };
+    let xml = generate_rbxlx(&world_test_level(world)).unwrap();
+    let xml_str = String::from_utf8_lossy(&xml);
+
+    let lighting = item_section(&xml_str, "Lighting");
+    assert!(lighting.contains(r#"<token name="Technology">4</token>"#));
+    assert!(lighting.contains(r#"<float name="ClockTime">18.5</float>"#));
+    assert!(lighting.contains(r#"name="Ambient""#));
+    assert!(lighting.contains(r#"name="FogColor""#));
+    assert!(lighting.contains(r#"<float name="FogStart">50</float>"#));
+    assert!(lighting.contains(r#"<float name="FogEnd">400</float>"#));
+}
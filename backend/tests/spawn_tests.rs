@@ -0,0 +1,198 @@
+//! Tests for SpawnLocation clearance.
+
+mod common;
+
+use backend::models::{Block, BlockSize, SpaceJSON, SpawnPoint, WorldPosition};
+use backend::rbxlx::{build_place_dom, export_warnings, ExportOptions, OutputFormat};
+use backend::spawn::{blocks_obstructing_spawns, clear_spawns, SpawnClearance};
+use backend::stats::level_stats;
+use backend::validation::{ValidationLimits, ValidationWarning};
+use common::{block, level};
+use rbx_dom_weak::{ustr, WeakDom};
+use rbx_types::Variant;
+use std::borrow::Cow;
+
+const GRAY: &str = "#808080";
+
+/// A column of blocks 42 studs tall standing on the default spawn.
+fn tower() -> SpaceJSON {
+    level((0..=40).step_by(2).map(|y| block(0, y, 0, GRAY)).collect())
+}
+
+/// Returns the default spawn position a relocation stored in the level.
+fn relocated_spawn(space_json: &SpaceJSON) -> WorldPosition {
+    space_json
+        .world
+        .as_ref()
+        .and_then(|world| world.spawn.as_ref())
+        .and_then(|spawn| spawn.position)
+        .expect("relocated spawn position")
+}
+
+/// Returns the positions of every SpawnLocation in a generated place.
+fn spawn_positions(dom: &WeakDom) -> Vec<(f32, f32, f32)> {
+    dom.descendants()
+        .filter(|instance| instance.class == "SpawnLocation")
+        .filter_map(|instance| match instance.properties.get(&ustr("CFrame")) {
+            Some(Variant::CFrame(cframe)) => Some((cframe.position.x, cframe.position.y, cframe.position.z)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_spawn_clearance_from_name() {
+    assert_eq!(SpawnClearance::from_name("keep"), Some(SpawnClearance::Keep));
+    assert_eq!(SpawnClearance::from_name("Relocate"), Some(SpawnClearance::Relocate));
+    assert_eq!(SpawnClearance::from_name("CLEAR"), Some(SpawnClearance::Clear));
+    assert_eq!(SpawnClearance::from_name("hollow"), None);
+    assert_eq!(SpawnClearance::default(), SpawnClearance::Keep);
+}
+
+#[test]
+fn test_blocks_obstructing_default_spawn() {
+    let space_json = level(vec![
+        // In the pad
+        block(0, 0, 0, GRAY),
+        // In the player clearance above the pad
+        block(4, 6, -4, GRAY),
+        // On top of the clearance, only touching it
+        block(0, 8, 0, GRAY),
+        // Beside the 12x12 pad
+        block(8, 0, 0, GRAY),
+        // Overlapping the pad's edge
+        block(6, 0, 0, GRAY),
+    ]);
+    assert_eq!(blocks_obstructing_spawns(&space_json), vec![0, 1, 4]);
+}
+
+#[test]
+fn test_blocks_obstructing_spawn_points() {
+    let mut space_json = level(vec![block(0, 0, 0, GRAY), block(40, 0, 0, GRAY)]);
+    space_json.spawns = vec![SpawnPoint {
+        x: 40.0,
+        y: 0.5,
+        z: 0.0,
+        team: None,
+    }];
+    // The default spawn at the origin is replaced by the spawn point
+    assert_eq!(blocks_obstructing_spawns(&space_json), vec![1]);
+}
+
+#[test]
+fn test_clear_removes_obstructing_blocks() {
+    let space_json = level(vec![block(0, 0, 0, GRAY), block(20, 0, 0, GRAY), block(2, 4, 2, GRAY)]);
+    let cleared = clear_spawns(&space_json, SpawnClearance::Clear);
+    assert_eq!(cleared.blocks, vec![block(20, 0, 0, GRAY)]);
+
+    let clear_level = level(vec![block(20, 0, 0, GRAY)]);
+    assert!(matches!(clear_spawns(&clear_level, SpawnClearance::Clear), Cow::Borrowed(_)));
+    assert!(matches!(clear_spawns(&space_json, SpawnClearance::Keep), Cow::Borrowed(_)));
+}
+
+#[test]
+fn test_relocate_stands_spawn_on_low_blocks() {
+    let space_json = level(vec![block(0, 0, 0, GRAY), block(2, 0, 0, GRAY)]);
+    let relocated = clear_spawns(&space_json, SpawnClearance::Relocate);
+
+    // Resting on the blocks is closer than stepping 8 studs aside
+    assert_eq!(relocated_spawn(&relocated), WorldPosition { x: 0.0, y: 1.5, z: 0.0 });
+    assert!(blocks_obstructing_spawns(&relocated).is_empty());
+    assert_eq!(relocated.blocks, space_json.blocks);
+}
+
+#[test]
+fn test_relocate_steps_beside_tall_blocks() {
+    let space_json = tower();
+    let relocated = clear_spawns(&space_json, SpawnClearance::Relocate);
+
+    let position = relocated_spawn(&relocated);
+    assert_eq!(position.y, 0.5);
+    assert_eq!((position.x.powi(2) + position.z.powi(2)).sqrt(), 8.0);
+    assert!(blocks_obstructing_spawns(&relocated).is_empty());
+}
+
+#[test]
+fn test_relocate_stands_spawn_on_large_blocks() {
+    // Spans far more of the search area than any one column
+    let slab = Block {
+        size: Some(BlockSize { x: 400.0, y: 2.0, z: 400.0 }),
+        ..block(0, 0, 0, GRAY)
+    };
+    let space_json = level(vec![slab, block(0, 2, 0, GRAY)]);
+    let relocated = clear_spawns(&space_json, SpawnClearance::Relocate);
+
+    assert_eq!(relocated_spawn(&relocated), WorldPosition { x: 0.0, y: 3.5, z: 0.0 });
+    assert!(blocks_obstructing_spawns(&relocated).is_empty());
+}
+
+#[test]
+fn test_relocate_moves_only_obstructed_spawn_points() {
+    let mut space_json = level(vec![block(40, 0, 0, GRAY)]);
+    space_json.spawns = vec![
+        SpawnPoint {
+            x: 40.0,
+            y: 0.5,
+            z: 0.0,
+            team: None,
+        },
+        SpawnPoint {
+            x: -40.0,
+            y: 0.5,
+            z: 0.0,
+            team: None,
+        },
+    ];
+    let relocated = clear_spawns(&space_json, SpawnClearance::Relocate);
+
+    assert!(relocated.world.is_none());
+    assert_eq!((relocated.spawns[0].x, relocated.spawns[0].y), (40.0, 1.5));
+    assert_eq!((relocated.spawns[1].x, relocated.spawns[1].y), (-40.0, 0.5));
+}
+
+#[test]
+fn test_place_export_applies_spawn_clearance() {
+    let space_json = tower();
+    let options = |spawn_clearance| ExportOptions {
+        spawn_clearance,
+        ..Default::default()
+    };
+
+    let kept = build_place_dom(&space_json, &options(SpawnClearance::Keep));
+    assert_eq!(spawn_positions(&kept), vec![(0.0, 0.5, 0.0)]);
+
+    let relocated = build_place_dom(&space_json, &options(SpawnClearance::Relocate));
+    let (x, y, z) = spawn_positions(&relocated)[0];
+    assert_eq!((x.abs() + z.abs(), y), (8.0, 0.5));
+
+    // The bottom four blocks reach into the pad and clearance
+    let cleared = build_place_dom(&space_json, &options(SpawnClearance::Clear));
+    let parts = cleared.descendants().filter(|instance| instance.class == "Part").count();
+    assert_eq!(parts, 1 + space_json.blocks.len() - 4);
+
+    let limits = ValidationLimits::default();
+    let stats = level_stats(&space_json, &limits, &options(SpawnClearance::Clear), OutputFormat::Xml);
+    assert_eq!(stats.estimated_part_count, parts + 1);
+    // Models have no SpawnLocation, so nothing is cleared
+    let model_stats = level_stats(&space_json, &limits, &options(SpawnClearance::Clear), OutputFormat::ModelXml);
+    assert_eq!(model_stats.estimated_part_count, space_json.blocks.len());
+}
+
+#[test]
+fn test_export_warnings_report_spawn_overlap_only_when_kept() {
+    let mut space_json = tower();
+    space_json.name = Some("Tower".to_string());
+    let limits = ValidationLimits::default();
+    let options = |spawn_clearance| ExportOptions {
+        spawn_clearance,
+        ..Default::default()
+    };
+
+    let warnings = export_warnings(&space_json, &limits, &options(SpawnClearance::Keep));
+    assert_eq!(warnings, vec![ValidationWarning::SpawnOverlap { blocks: vec![0, 1, 2, 3] }]);
+    assert!(warnings[0].message().starts_with("4 block(s) starting at block index 0"));
+
+    for clearance in [SpawnClearance::Relocate, SpawnClearance::Clear] {
+        assert!(export_warnings(&space_json, &limits, &options(clearance)).is_empty());
+    }
+}
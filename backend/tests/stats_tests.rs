@@ -0,0 +1,116 @@
+//! Tests for level statistics.
+
+mod common;
+
+use backend::models::{BaseplateSettings, Block, BlockSpawn, SpaceJSON, SpawnPoint, WorldSettings};
+use backend::rbxlx::{build_place_dom, ExportOptions, OutputFormat};
+use backend::stats::{level_stats, BoundingBox, ColorCount, GridPosition};
+use backend::validation::{ValidationLimits, MAX_BLOCKS};
+use common::{block, level};
+
+#[test]
+fn test_stats_empty_level() {
+    let limits = ValidationLimits::default();
+    let stats = level_stats(&level(vec![]), &limits, &ExportOptions::default(), OutputFormat::Xml);
+
+    assert_eq!(stats.block_count, 0);
+    assert_eq!(stats.max_blocks, MAX_BLOCKS);
+    assert_eq!(stats.bounding_box, None);
+    assert!(stats.colors.is_empty());
+    // Baseplate and default SpawnLocation
+    assert_eq!(stats.estimated_part_count, 2);
+
+    let pro_tier = ValidationLimits {
+        max_blocks: 50_000,
+        ..limits
+    };
+    let stats = level_stats(&level(vec![]), &pro_tier, &ExportOptions::default(), OutputFormat::Xml);
+    assert_eq!(stats.max_blocks, 50_000);
+}
+
+#[test]
+fn test_stats_bounding_box_and_colors() {
+    let space_json = level(vec![
+        block(-4, 0, 6, "#ff0000"),
+        block(10, 8, -2, "#00FF00"),
+        block(2, 4, 0, "#FF0000"),
+    ]);
+    let stats = level_stats(&space_json, &ValidationLimits::default(), &ExportOptions::default(), OutputFormat::Xml);
+
+    assert_eq!(stats.block_count, 3);
+    assert_eq!(
+        stats.bounding_box,
+        Some(BoundingBox {
+            min: GridPosition { x: -4, y: 0, z: -2 },
+            max: GridPosition { x: 10, y: 8, z: 6 },
+        })
+    );
+    assert_eq!(
+        stats.colors,
+        vec![
+            ColorCount { color: "#FF0000".to_string(), count: 2 },
+            ColorCount { color: "#00FF00".to_string(), count: 1 },
+        ]
+    );
+}
+
+#[test]
+fn test_stats_part_count_matches_generation() {
+    let blocks: Vec<Block> = (0..10).map(|i| block(i * 2, 0, 0, "#808080")).collect();
+    let space_json = level(blocks);
+
+    for merge_blocks in [false, true] {
+        let options = ExportOptions { merge_blocks, ..Default::default() };
+        let stats = level_stats(&space_json, &ValidationLimits::default(), &options, OutputFormat::Xml);
+        let dom = build_place_dom(&space_json, &options);
+        let generated_parts = dom
+            .descendants()
+            .filter(|instance| instance.class == "Part" || instance.class == "SpawnLocation")
+            .count();
+        assert_eq!(stats.estimated_part_count, generated_parts, "merge_blocks: {}", merge_blocks);
+    }
+
+    let options = ExportOptions {
+        merge_blocks: true,
+        ..Default::default()
+    };
+    let merged = level_stats(&space_json, &ValidationLimits::default(), &options, OutputFormat::Xml);
+    assert_eq!(merged.estimated_part_count, 3);
+}
+
+#[test]
+fn test_stats_part_count_world_and_spawns() {
+    let mut spawn_block = block(0, 0, 0, "#FF0000");
+    spawn_block.spawn = Some(BlockSpawn::default());
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        blocks: vec![spawn_block, block(2, 0, 0, "#FF0000")],
+        spawns: vec![SpawnPoint { x: 20.0, y: 0.5, z: 0.0, team: None }],
+        world: Some(WorldSettings {
+            baseplate: Some(BaseplateSettings {
+                enabled: Some(false),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let place = level_stats(&space_json, &ValidationLimits::default(), &ExportOptions::default(), OutputFormat::Xml);
+    assert_eq!(place.estimated_part_count, 3);
+
+    let model = level_stats(&space_json, &ValidationLimits::default(), &ExportOptions::default(), OutputFormat::ModelXml);
+    assert_eq!(model.estimated_part_count, 2);
+}
+
+#[test]
+fn test_stats_file_size_by_format() {
+    let blocks: Vec<Block> = (0..100).map(|i| block(i * 2, 0, i * 2, "#808080")).collect();
+    let space_json = level(blocks);
+    let limits = ValidationLimits::default();
+    let size = |format| level_stats(&space_json, &limits, &ExportOptions::default(), format).estimated_file_size;
+
+    assert!(size(OutputFormat::Binary) < size(OutputFormat::Xml));
+    assert!(size(OutputFormat::ModelXml) < size(OutputFormat::Xml));
+    assert!(size(OutputFormat::ModelBinary) < size(OutputFormat::ModelXml));
+}
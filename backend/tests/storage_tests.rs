@@ -0,0 +1,319 @@
+//! Tests for the filesystem and SQLite level stores.
+
+mod common;
+
+use backend::models::SpaceJSON;
+use backend::storage::{
+    is_share_token, validate_level_id, FsLevelStore, LevelStore, SqliteLevelStore, StorageError,
+};
+use common::{block, level};
+use std::fs;
+use std::path::PathBuf;
+
+/// A level named `name` with a row of `block_count` blocks.
+fn named_level(name: &str, block_count: i32) -> SpaceJSON {
+    SpaceJSON {
+        name: Some(name.to_string()),
+        ..level((0..block_count).map(|i| block(i * 2, 0, 0, "#FF0000")).collect())
+    }
+}
+
+/// Creates an empty scratch directory unique to the test.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("level-store-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Exercises the full `LevelStore` contract against one implementation.
+fn check_store(store: &dyn LevelStore) {
+    assert!(store.list().unwrap().is_empty());
+
+    // Create and load
+    let created = store.create(named_level("First", 3)).unwrap();
+    assert!(validate_level_id(&created.id).is_ok());
+    assert_eq!(created.created_at, created.updated_at);
+    let loaded = store.get(&created.id).unwrap();
+    assert_eq!(loaded.space.name.as_deref(), Some("First"));
+    assert_eq!(loaded.space.blocks.len(), 3);
+    assert_eq!(loaded.created_at, created.created_at);
+
+    // Replacing keeps the creation time
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    let updated = store.put(&created.id, named_level("First v2", 1)).unwrap();
+    assert_eq!(updated.created_at, created.created_at);
+    assert!(updated.updated_at > created.updated_at);
+    assert_eq!(store.get(&created.id).unwrap().space.blocks.len(), 1);
+
+    // Put creates levels under client-chosen IDs
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    store.put("my-level_2", named_level("Second", 5)).unwrap();
+
+    let summaries = store.list().unwrap();
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[0].id, "my-level_2");
+    assert_eq!(summaries[0].block_count, 5);
+    assert_eq!(summaries[1].name.as_deref(), Some("First v2"));
+
+    // Delete
+    store.delete(&created.id).unwrap();
+    assert!(matches!(store.get(&created.id), Err(StorageError::NotFound { .. })));
+    assert!(matches!(store.delete(&created.id), Err(StorageError::NotFound { .. })));
+    assert_eq!(store.list().unwrap().len(), 1);
+
+    // IDs that could escape the storage location are rejected
+    assert!(matches!(store.get("../secret"), Err(StorageError::InvalidId { .. })));
+    assert!(matches!(store.put("", named_level("Empty", 0)), Err(StorageError::InvalidId { .. })));
+}
+
+#[test]
+fn test_fs_level_store() {
+    let dir = scratch_dir("fs");
+    let store = FsLevelStore::open(&dir).unwrap();
+    check_store(&store);
+    assert!(dir.join("my-level_2.json").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_fs_level_store_persists_across_instances() {
+    let dir = scratch_dir("fs-reopen");
+    let id = FsLevelStore::open(&dir).unwrap().create(named_level("Saved", 2)).unwrap().id;
+
+    let reopened = FsLevelStore::open(&dir).unwrap();
+    assert_eq!(reopened.get(&id).unwrap().space.name.as_deref(), Some("Saved"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_sqlite_level_store() {
+    let store = SqliteLevelStore::open_in_memory().unwrap();
+    check_store(&store);
+}
+
+#[test]
+fn test_sqlite_level_store_persists_across_instances() {
+    let dir = scratch_dir("sqlite-reopen");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("levels.db");
+    let id = SqliteLevelStore::open(&path).unwrap().create(named_level("Saved", 2)).unwrap().id;
+
+    let reopened = SqliteLevelStore::open(&path).unwrap();
+    assert_eq!(reopened.get(&id).unwrap().space.blocks.len(), 2);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_storage_error_codes() {
+    let error = validate_level_id("a/b").unwrap_err();
+    assert_eq!(error.error_code(), "INVALID_LEVEL_ID");
+    assert!(validate_level_id(&"a".repeat(65)).is_err());
+
+    let error = StorageError::NotFound { id: "abc".to_string() };
+    assert_eq!(error.error_code(), "LEVEL_NOT_FOUND");
+    assert_eq!(error.message(), "Level 'abc' does not exist.");
+}
+
+// Share link tests
+
+/// Exercises share links against one implementation.
+fn check_shares(store: &dyn LevelStore) {
+    let level_id = store.create(named_level("Shared", 2)).unwrap().id;
+    let now = backend::storage::now_millis();
+
+    let share = store.create_share(&level_id, None).unwrap();
+    assert!(is_share_token(&share.token));
+    assert_eq!(share.level_id, level_id);
+    assert!(!share.revoked);
+    let shared = store.shared_level(&share.token, now).unwrap();
+    assert_eq!(shared.space.name.as_deref(), Some("Shared"));
+
+    // Every link gets its own token
+    let other = store.create_share(&level_id, None).unwrap();
+    assert_ne!(other.token, share.token);
+
+    // Sharing a missing level fails
+    assert!(matches!(store.create_share("missing", None), Err(StorageError::NotFound { .. })));
+
+    // Unknown and malformed tokens
+    assert!(matches!(store.shared_level(&"0".repeat(64), now), Err(StorageError::ShareNotFound)));
+    assert!(matches!(store.shared_level("../../etc/passwd", now), Err(StorageError::ShareNotFound)));
+    assert!(matches!(store.shared_level(&level_id, now), Err(StorageError::ShareNotFound)));
+}
+
+/// Expired links stop working once their expiry time is reached.
+fn check_expired_shares(store: &dyn LevelStore) {
+    let level_id = store.create(named_level("Expiring", 1)).unwrap().id;
+    let share = store.create_share(&level_id, Some(1_000)).unwrap();
+    assert_eq!(store.get_share(&share.token).unwrap().expires_at, Some(1_000));
+
+    assert!(store.shared_level(&share.token, 999).is_ok());
+    assert!(matches!(store.shared_level(&share.token, 1_000), Err(StorageError::ShareExpired)));
+    assert!(matches!(
+        store.shared_level(&share.token, backend::storage::now_millis()),
+        Err(StorageError::ShareExpired)
+    ));
+}
+
+/// Revoked links stop working; only the owning level can revoke them.
+fn check_revoked_shares(store: &dyn LevelStore) {
+    let level_id = store.create(named_level("Revoked", 1)).unwrap().id;
+    let other_level_id = store.create(named_level("Other", 1)).unwrap().id;
+    let share = store.create_share(&level_id, None).unwrap();
+    let now = backend::storage::now_millis();
+
+    assert!(matches!(
+        store.revoke_share(&other_level_id, &share.token),
+        Err(StorageError::ShareNotFound)
+    ));
+    assert!(store.shared_level(&share.token, now).is_ok());
+
+    store.revoke_share(&level_id, &share.token).unwrap();
+    assert!(store.get_share(&share.token).unwrap().revoked);
+    assert!(matches!(store.shared_level(&share.token, now), Err(StorageError::ShareRevoked)));
+    // Revoking again is harmless
+    store.revoke_share(&level_id, &share.token).unwrap();
+
+    // Deleting the level invalidates its remaining links
+    let live = store.create_share(&other_level_id, None).unwrap();
+    store.delete(&other_level_id).unwrap();
+    assert!(matches!(store.shared_level(&live.token, now), Err(StorageError::ShareNotFound)));
+}
+
+/// Links to a deleted level stay dead when a new level is saved under its ID.
+fn check_deleted_level_shares(store: &dyn LevelStore) {
+    let level_id = store.create(named_level("Deleted", 1)).unwrap().id;
+    let share = store.create_share(&level_id, None).unwrap();
+    let kept = store.create(named_level("Kept", 1)).unwrap().id;
+    let kept_share = store.create_share(&kept, None).unwrap();
+    let now = backend::storage::now_millis();
+
+    store.delete(&level_id).unwrap();
+    store.put(&level_id, named_level("Recreated", 1)).unwrap();
+    assert!(matches!(store.get_share(&share.token), Err(StorageError::ShareNotFound)));
+    assert!(matches!(store.shared_level(&share.token, now), Err(StorageError::ShareNotFound)));
+    // Other levels keep their links
+    assert!(store.shared_level(&kept_share.token, now).is_ok());
+}
+
+#[test]
+fn test_fs_share_links() {
+    let dir = scratch_dir("fs-shares");
+    let store = FsLevelStore::open(&dir).unwrap();
+    check_shares(&store);
+    check_expired_shares(&store);
+    check_revoked_shares(&store);
+    check_deleted_level_shares(&store);
+    // Share files never show up as levels
+    assert_eq!(store.list().unwrap().len(), 5);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_sqlite_share_links() {
+    let store = SqliteLevelStore::open_in_memory().unwrap();
+    check_shares(&store);
+    check_expired_shares(&store);
+    check_revoked_shares(&store);
+    check_deleted_level_shares(&store);
+}
+
+#[test]
+fn test_share_error_codes() {
+    assert_eq!(StorageError::ShareExpired.error_code(), "SHARE_EXPIRED");
+    assert_eq!(StorageError::ShareRevoked.error_code(), "SHARE_REVOKED");
+    assert_eq!(StorageError::ShareNotFound.error_code(), "SHARE_NOT_FOUND");
+}
+
+// Revision history tests
+
+/// Every save records an immutable revision that can be listed and loaded.
+fn check_revisions(store: &dyn LevelStore) {
+    let created = store.create(named_level("Draft", 1)).unwrap();
+    assert_eq!(created.revision, 1);
+    let id = created.id;
+
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    assert_eq!(store.put(&id, named_level("Draft", 2)).unwrap().revision, 2);
+    let latest = store.put(&id, named_level("Final", 3)).unwrap();
+    assert_eq!(latest.revision, 3);
+    assert_eq!(store.get(&id).unwrap().revision, 3);
+
+    let revisions = store.list_revisions(&id).unwrap();
+    let numbers: Vec<u32> = revisions.iter().map(|revision| revision.revision).collect();
+    assert_eq!(numbers, vec![3, 2, 1]);
+    assert_eq!(revisions[0].name.as_deref(), Some("Final"));
+    assert_eq!(revisions[2].block_count, 1);
+    assert_eq!(revisions[2].created_at, created.created_at);
+
+    // Older revisions keep their content after later saves
+    let first = store.get_revision(&id, 1).unwrap();
+    assert_eq!(first.level_id, id);
+    assert_eq!(first.space.blocks.len(), 1);
+    assert_eq!(store.get_revision(&id, 3).unwrap().space.name.as_deref(), Some("Final"));
+
+    assert!(matches!(
+        store.get_revision(&id, 4),
+        Err(StorageError::RevisionNotFound { revision: 4, .. })
+    ));
+    assert!(matches!(store.get_revision("missing", 1), Err(StorageError::NotFound { .. })));
+    assert!(matches!(store.list_revisions("missing"), Err(StorageError::NotFound { .. })));
+
+    // Deleting a level deletes its history; saving again starts over
+    store.delete(&id).unwrap();
+    assert!(matches!(store.get_revision(&id, 1), Err(StorageError::NotFound { .. })));
+    assert_eq!(store.put(&id, named_level("Reborn", 1)).unwrap().revision, 1);
+    assert_eq!(store.list_revisions(&id).unwrap().len(), 1);
+}
+
+/// Conditional saves only replace a level that is still at the expected revision.
+fn check_conditional_puts(store: &dyn LevelStore) {
+    let id = store.create(named_level("Draft", 1)).unwrap().id;
+    assert_eq!(store.put_if_revision(&id, named_level("Edited", 2), 1).unwrap().revision, 2);
+
+    assert!(matches!(
+        store.put_if_revision(&id, named_level("Stale", 3), 1),
+        Err(StorageError::RevisionConflict { expected: 1, actual: 2, .. })
+    ));
+    assert_eq!(store.get(&id).unwrap().space.name.as_deref(), Some("Edited"));
+
+    // Conditional saves never create levels
+    store.delete(&id).unwrap();
+    assert!(matches!(store.put_if_revision(&id, named_level("Stale", 3), 2), Err(StorageError::NotFound { .. })));
+    assert!(matches!(store.get(&id), Err(StorageError::NotFound { .. })));
+}
+
+#[test]
+fn test_fs_revisions() {
+    let dir = scratch_dir("fs-revisions");
+    let store = FsLevelStore::open(&dir).unwrap();
+    check_revisions(&store);
+    check_conditional_puts(&store);
+    // Revision files never show up as levels
+    assert_eq!(store.list().unwrap().len(), 1);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_sqlite_revisions() {
+    let store = SqliteLevelStore::open_in_memory().unwrap();
+    check_revisions(&store);
+    check_conditional_puts(&store);
+}
+
+#[test]
+fn test_revision_error_code() {
+    let error = StorageError::RevisionNotFound {
+        id: "abc".to_string(),
+        revision: 7,
+    };
+    assert_eq!(error.error_code(), "REVISION_NOT_FOUND");
+    assert_eq!(error.message(), "Level 'abc' has no revision 7.");
+
+    let error = StorageError::RevisionConflict {
+        id: "abc".to_string(),
+        expected: 7,
+        actual: 8,
+    };
+    assert_eq!(error.error_code(), "REVISION_CONFLICT");
+}
@@ -0,0 +1,151 @@
+//! Tests for teams, spawn points and spawn blocks.
+
+mod common;
+
+use backend::brick_colors::{brick_color_name, nearest_brick_color, resolve_team_colors};
+use backend::models::{Block, BlockSpawn, SpaceJSON, SpawnPoint, Team};
+use backend::rbxlx::{build_place_dom, ExportOptions};
+use common::block;
+use rbx_dom_weak::{ustr, Instance, WeakDom};
+use rbx_types::{BrickColor, Variant};
+
+fn team(name: &str, color: Option<&str>) -> Team {
+    Team {
+        name: name.to_string(),
+        color: color.map(str::to_string),
+    }
+}
+
+fn spawn_point(x: f32, z: f32, team: Option<&str>) -> SpawnPoint {
+    SpawnPoint {
+        x,
+        y: 0.5,
+        z,
+        team: team.map(str::to_string),
+    }
+}
+
+/// Returns every instance of the given class, in document order.
+fn instances_of<'a>(dom: &'a WeakDom, class: &str) -> Vec<&'a Instance> {
+    dom.descendants().filter(|instance| instance.class == class).collect()
+}
+
+fn property<'a>(instance: &'a Instance, name: &str) -> Option<&'a Variant> {
+    instance.properties.get(&ustr(name))
+}
+
+fn name_of(instance: &Instance) -> Option<&str> {
+    Some(instance.name.as_str())
+}
+
+fn brick_color(number: u16) -> Variant {
+    Variant::BrickColor(BrickColor::from_number(number).unwrap())
+}
+
+// BrickColor palette tests
+
+#[test]
+fn test_nearest_brick_color() {
+    assert_eq!(nearest_brick_color("#FF0000"), Some(1004));
+    assert_eq!(nearest_brick_color("#C4281C"), Some(21));
+    assert_eq!(nearest_brick_color("#0D69AC"), Some(23));
+    assert_eq!(nearest_brick_color("#000"), Some(1003));
+    assert_eq!(nearest_brick_color("red"), None);
+    assert_eq!(brick_color_name(21), Some("Bright red"));
+}
+
+#[test]
+fn test_resolve_team_colors_assigns_defaults() {
+    assert_eq!(
+        resolve_team_colors(&[Some("#FF0000"), None, None]),
+        vec![Some(1004), Some(21), Some(23)]
+    );
+}
+
+#[test]
+fn test_resolve_team_colors_skips_taken_defaults() {
+    // The second team explicitly picks Bright red, so the first gets the next default
+    assert_eq!(resolve_team_colors(&[None, Some("#C4281C")]), vec![Some(23), Some(21)]);
+}
+
+// Generation tests
+
+fn team_level() -> SpaceJSON {
+    let mut red_spawn = block(10, 0, 0, "#FF0000");
+    red_spawn.spawn = Some(BlockSpawn {
+        team: Some("Red".to_string()),
+    });
+
+    SpaceJSON {
+        schema_version: 2,
+        blocks: vec![block(0, 0, 0, "#808080"), red_spawn],
+        teams: vec![team("Red", Some("#C4281C")), team("Blue", None)],
+        spawns: vec![spawn_point(-20.0, 0.0, Some("Blue")), spawn_point(0.0, 20.0, None)],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_generate_teams_service() {
+    let dom = build_place_dom(&team_level(), &ExportOptions::default());
+
+    assert_eq!(instances_of(&dom, "Teams").len(), 1);
+    let teams = instances_of(&dom, "Team");
+    assert_eq!(teams.len(), 2);
+    assert_eq!(name_of(teams[0]), Some("Red"));
+    assert_eq!(property(teams[0], "TeamColor"), Some(&brick_color(21)));
+    assert_eq!(name_of(teams[1]), Some("Blue"));
+    assert_eq!(property(teams[1], "TeamColor"), Some(&brick_color(23)));
+}
+
+#[test]
+fn test_generate_spawns_replace_default_spawn() {
+    let dom = build_place_dom(&team_level(), &ExportOptions::default());
+    let spawns = instances_of(&dom, "SpawnLocation");
+
+    // One spawn block plus two spawn points; no default spawn at the origin
+    assert_eq!(spawns.len(), 3);
+
+    let spawn_block = spawns.iter().find(|s| name_of(s) == Some("Block1")).expect("spawn block");
+    assert_eq!(property(spawn_block, "Neutral"), Some(&Variant::Bool(false)));
+    assert_eq!(property(spawn_block, "TeamColor"), Some(&brick_color(21)));
+    assert!(matches!(property(spawn_block, "Size"), Some(Variant::Vector3(size)) if size.x == 2.0));
+
+    let points: Vec<_> = spawns.iter().filter(|s| name_of(s) == Some("SpawnLocation")).collect();
+    assert_eq!(points.len(), 2);
+    assert_eq!(property(points[0], "TeamColor"), Some(&brick_color(23)));
+    assert_eq!(property(points[1], "Neutral"), Some(&Variant::Bool(true)));
+    assert!(property(points[1], "TeamColor").is_none());
+}
+
+#[test]
+fn test_generate_without_teams_keeps_default_spawn() {
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        blocks: vec![block(0, 0, 0, "#808080")],
+        ..Default::default()
+    };
+    let dom = build_place_dom(&space_json, &ExportOptions::default());
+
+    assert!(instances_of(&dom, "Teams").is_empty());
+    let spawns = instances_of(&dom, "SpawnLocation");
+    assert_eq!(spawns.len(), 1);
+    assert_eq!(property(spawns[0], "Neutral"), Some(&Variant::Bool(true)));
+}
+
+#[test]
+fn test_generate_merged_keeps_spawn_blocks_separate() {
+    let mut blocks: Vec<Block> = (0..4).map(|i| block(i * 2, 0, 0, "#FF0000")).collect();
+    blocks[1].spawn = Some(BlockSpawn::default());
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        blocks,
+        ..Default::default()
+    };
+    let options = ExportOptions { merge_blocks: true, ..Default::default() };
+    let dom = build_place_dom(&space_json, &options);
+
+    assert_eq!(instances_of(&dom, "SpawnLocation").len(), 1);
+    // Baseplate + the block before the spawn + the two blocks after it
+    assert_eq!(instances_of(&dom, "Part").len(), 3);
+}
@@ -1,10 +1,14 @@
 //! Tests for Space JSON validation.
 
-use backend::models::{Block, SpaceJSON};
+use backend::models::{
+    BaseplateSettings, Block, BlockSize, BlockSpawn, FogSettings, LightingSettings, SpaceJSON,
+    SpawnPoint, SpawnSettings, Team, WorldPosition, WorldSettings,
+};
 use backend::validation::{
-    validate_block_count, validate_color_format, validate_coordinate_bounds,
-    validate_no_duplicates, validate_schema_version, validate_space_json, ValidationError,
-    MAX_BLOCKS,
+    validate_block_count, validate_block_size, validate_color_format, validate_coordinate_bounds,
+    collect_warnings, find_floating_islands, validate_material, validate_no_duplicates, validate_schema_version,
+    validate_space_json, validate_space_json_all, validate_unit_interval, CoordinateBounds, SpawnSource,
+    ValidationError, ValidationLimits, ValidationWarning, MAX_BLOCKS, MAX_BLOCK_SIZE, MAX_ERRORS_PER_CODE,
 };
 
 // Schema version tests
@@ -23,10 +27,15 @@ fn test_validate_schema_version_rejects_0() {
 }
 
 #[test]
-fn test_validate_schema_version_rejects_2() {
+fn test_validate_schema_version_accepts_2() {
+    assert!(validate_schema_version(2).is_ok());
+}
+
+#[test]
+fn test_validate_schema_version_rejects_3() {
     assert!(matches!(
-        validate_schema_version(2),
-        Err(ValidationError::InvalidSchemaVersion { version: 2 })
+        validate_schema_version(3),
+        Err(ValidationError::InvalidSchemaVersion { version: 3 })
     ));
 }
 
@@ -34,18 +43,18 @@ fn test_validate_schema_version_rejects_2() {
 
 #[test]
 fn test_validate_block_count_accepts_max() {
-    assert!(validate_block_count(MAX_BLOCKS).is_ok());
+    assert!(validate_block_count(MAX_BLOCKS, &ValidationLimits::default()).is_ok());
 }
 
 #[test]
 fn test_validate_block_count_accepts_0() {
-    assert!(validate_block_count(0).is_ok());
+    assert!(validate_block_count(0, &ValidationLimits::default()).is_ok());
 }
 
 #[test]
 fn test_validate_block_count_rejects_over_max() {
     assert!(matches!(
-        validate_block_count(MAX_BLOCKS + 1),
+        validate_block_count(MAX_BLOCKS + 1, &ValidationLimits::default()),
         Err(ValidationError::BlockCountExceeded { count, limit }) if count == MAX_BLOCKS + 1 && limit == MAX_BLOCKS
     ));
 }
@@ -54,15 +63,15 @@ fn test_validate_block_count_rejects_over_max() {
 
 #[test]
 fn test_validate_coordinate_bounds_accepts_valid() {
-    assert!(validate_coordinate_bounds(0, 500, 0, 0).is_ok());
-    assert!(validate_coordinate_bounds(-1000, 0, -1000, 0).is_ok());
-    assert!(validate_coordinate_bounds(1000, 1000, 1000, 0).is_ok());
+    assert!(validate_coordinate_bounds(0, 500, 0, 0, &CoordinateBounds::default()).is_ok());
+    assert!(validate_coordinate_bounds(-1000, 0, -1000, 0, &CoordinateBounds::default()).is_ok());
+    assert!(validate_coordinate_bounds(1000, 1000, 1000, 0, &CoordinateBounds::default()).is_ok());
 }
 
 #[test]
 fn test_validate_coordinate_bounds_rejects_x_too_low() {
     assert!(matches!(
-        validate_coordinate_bounds(-1001, 0, 0, 0),
+        validate_coordinate_bounds(-1001, 0, 0, 0, &CoordinateBounds::default()),
         Err(ValidationError::CoordinateOutOfBounds { x: -1001, .. })
     ));
 }
@@ -70,7 +79,7 @@ fn test_validate_coordinate_bounds_rejects_x_too_low() {
 #[test]
 fn test_validate_coordinate_bounds_rejects_x_too_high() {
     assert!(matches!(
-        validate_coordinate_bounds(1001, 0, 0, 0),
+        validate_coordinate_bounds(1001, 0, 0, 0, &CoordinateBounds::default()),
         Err(ValidationError::CoordinateOutOfBounds { x: 1001, .. })
     ));
 }
@@ -78,7 +87,7 @@ fn test_validate_coordinate_bounds_rejects_x_too_high() {
 #[test]
 fn test_validate_coordinate_bounds_rejects_z_too_low() {
     assert!(matches!(
-        validate_coordinate_bounds(0, 0, -1001, 0),
+        validate_coordinate_bounds(0, 0, -1001, 0, &CoordinateBounds::default()),
         Err(ValidationError::CoordinateOutOfBounds { z: -1001, .. })
     ));
 }
@@ -86,7 +95,7 @@ fn test_validate_coordinate_bounds_rejects_z_too_low() {
 #[test]
 fn test_validate_coordinate_bounds_rejects_z_too_high() {
     assert!(matches!(
-        validate_coordinate_bounds(0, 0, 1001, 0),
+        validate_coordinate_bounds(0, 0, 1001, 0, &CoordinateBounds::default()),
         Err(ValidationError::CoordinateOutOfBounds { z: 1001, .. })
     ));
 }
@@ -94,7 +103,7 @@ fn test_validate_coordinate_bounds_rejects_z_too_high() {
 #[test]
 fn test_validate_coordinate_bounds_rejects_y_too_low() {
     assert!(matches!(
-        validate_coordinate_bounds(0, -1, 0, 0),
+        validate_coordinate_bounds(0, -1, 0, 0, &CoordinateBounds::default()),
         Err(ValidationError::CoordinateOutOfBounds { y: -1, .. })
     ));
 }
@@ -102,11 +111,29 @@ fn test_validate_coordinate_bounds_rejects_y_too_low() {
 #[test]
 fn test_validate_coordinate_bounds_rejects_y_too_high() {
     assert!(matches!(
-        validate_coordinate_bounds(0, 1001, 0, 0),
+        validate_coordinate_bounds(0, 1001, 0, 0, &CoordinateBounds::default()),
         Err(ValidationError::CoordinateOutOfBounds { y: 1001, .. })
     ));
 }
 
+#[test]
+fn test_validate_coordinate_bounds_message_uses_bounds() {
+    let error = validate_coordinate_bounds(0, 1001, 0, 0, &CoordinateBounds::default()).unwrap_err();
+    assert!(error.message().contains("Valid range: X/Z: -1000 to 1000, Y: 0 to 1000."));
+
+    let bounds = CoordinateBounds {
+        min_x: -50,
+        max_x: 50,
+        max_y: 20,
+        ..CoordinateBounds::default()
+    };
+    assert!(validate_coordinate_bounds(50, 20, 500, 0, &bounds).is_ok());
+    let error = validate_coordinate_bounds(51, 0, 0, 0, &bounds).unwrap_err();
+    assert!(error
+        .message()
+        .contains("Valid range: X: -50 to 50, Y: 0 to 20, Z: -1000 to 1000."));
+}
+
 // Color format tests
 
 #[test]
@@ -163,9 +190,9 @@ fn test_validate_color_format_rejects_non_hex() {
 #[test]
 fn test_validate_no_duplicates_accepts_unique() {
     let blocks = vec![
-        Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string() },
-        Block { x: 1, y: 0, z: 0, color: "#00FF00".to_string() },
-        Block { x: 0, y: 1, z: 0, color: "#0000FF".to_string() },
+        Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+        Block { x: 1, y: 0, z: 0, color: "#00FF00".to_string(), ..Default::default() },
+        Block { x: 0, y: 1, z: 0, color: "#0000FF".to_string(), ..Default::default() },
     ];
     assert!(validate_no_duplicates(&blocks).is_ok());
 }
@@ -173,9 +200,9 @@ fn test_validate_no_duplicates_accepts_unique() {
 #[test]
 fn test_validate_no_duplicates_rejects_duplicate() {
     let blocks = vec![
-        Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string() },
-        Block { x: 1, y: 0, z: 0, color: "#00FF00".to_string() },
-        Block { x: 0, y: 0, z: 0, color: "#0000FF".to_string() },
+        Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+        Block { x: 1, y: 0, z: 0, color: "#00FF00".to_string(), ..Default::default() },
+        Block { x: 0, y: 0, z: 0, color: "#0000FF".to_string(), ..Default::default() },
     ];
     assert!(matches!(
         validate_no_duplicates(&blocks),
@@ -191,23 +218,25 @@ fn test_validate_space_json_passes_valid() {
         schema_version: 1,
         name: Some("Test Level".to_string()),
         blocks: vec![
-            Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string() },
-            Block { x: 1, y: 0, z: 0, color: "#00FF00".to_string() },
+            Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+            Block { x: 1, y: 0, z: 0, color: "#00FF00".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
-    assert!(validate_space_json(&space_json).is_ok());
+    assert!(validate_space_json(&space_json, &ValidationLimits::default()).is_ok());
 }
 
 #[test]
 fn test_validate_space_json_fails_invalid_schema() {
     let space_json = SpaceJSON {
-        schema_version: 2,
+        schema_version: 3,
         name: Some("Test Level".to_string()),
         blocks: vec![],
+        ..Default::default()
     };
     assert!(matches!(
-        validate_space_json(&space_json),
-        Err(ValidationError::InvalidSchemaVersion { version: 2 })
+        validate_space_json(&space_json, &ValidationLimits::default()),
+        Err(ValidationError::InvalidSchemaVersion { version: 3 })
     ));
 }
 
@@ -219,15 +248,17 @@ fn test_validate_space_json_fails_block_count_exceeded() {
             y: 0,
             z: 0,
             color: "#FF0000".to_string(),
+            ..Default::default()
         })
         .collect();
     let space_json = SpaceJSON {
         schema_version: 1,
         name: Some("Test Level".to_string()),
         blocks,
+        ..Default::default()
     };
     assert!(matches!(
-        validate_space_json(&space_json),
+        validate_space_json(&space_json, &ValidationLimits::default()),
         Err(ValidationError::BlockCountExceeded { .. })
     ));
 }
@@ -242,10 +273,12 @@ fn test_validate_space_json_fails_out_of_bounds() {
             y: 0,
             z: 0,
             color: "#FF0000".to_string(),
+            ..Default::default()
         }],
+        ..Default::default()
     };
     assert!(matches!(
-        validate_space_json(&space_json),
+        validate_space_json(&space_json, &ValidationLimits::default()),
         Err(ValidationError::CoordinateOutOfBounds { x: 1001, .. })
     ));
 }
@@ -260,10 +293,12 @@ fn test_validate_space_json_fails_invalid_color() {
             y: 0,
             z: 0,
             color: "not-a-color".to_string(),
+            ..Default::default()
         }],
+        ..Default::default()
     };
     assert!(matches!(
-        validate_space_json(&space_json),
+        validate_space_json(&space_json, &ValidationLimits::default()),
         Err(ValidationError::InvalidColorFormat { .. })
     ));
 }
@@ -274,12 +309,697 @@ fn test_validate_space_json_fails_duplicate() {
         schema_version: 1,
         name: Some("Test Level".to_string()),
         blocks: vec![
-            Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string() },
-            Block { x: 0, y: 0, z: 0, color: "#00FF00".to_string() },
+            Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+            Block { x: 0, y: 0, z: 0, color: "#00FF00".to_string(), ..Default::default() },
         ],
+        ..Default::default()
     };
     assert!(matches!(
-        validate_space_json(&space_json),
+        validate_space_json(&space_json, &ValidationLimits::default()),
         Err(ValidationError::DuplicatePosition { x: 0, y: 0, z: 0, .. })
     ));
 }
+
+// Schema version 2 property tests
+
+#[test]
+fn test_validate_material_accepts_known_case_insensitive() {
+    assert!(validate_material("Wood", 0).is_ok());
+    assert!(validate_material("smoothplastic", 0).is_ok());
+    assert!(validate_material("NEON", 0).is_ok());
+}
+
+#[test]
+fn test_validate_material_rejects_unknown() {
+    assert!(matches!(
+        validate_material("Lava", 3),
+        Err(ValidationError::InvalidMaterial { index: 3, .. })
+    ));
+    // Terrain-only materials cannot be applied to Parts
+    assert!(validate_material("Water", 0).is_err());
+}
+
+#[test]
+fn test_validate_unit_interval() {
+    assert!(validate_unit_interval("transparency", 0.0, 0).is_ok());
+    assert!(validate_unit_interval("transparency", 1.0, 0).is_ok());
+    assert!(matches!(
+        validate_unit_interval("reflectance", 1.5, 2),
+        Err(ValidationError::PropertyOutOfRange { property: "reflectance", index: 2, .. })
+    ));
+    assert!(validate_unit_interval("transparency", -0.1, 0).is_err());
+}
+
+#[test]
+fn test_validate_block_size() {
+    assert!(validate_block_size(BlockSize { x: 4.0, y: 1.0, z: 0.5 }, 0).is_ok());
+    assert!(validate_block_size(BlockSize { x: MAX_BLOCK_SIZE, y: 2.0, z: 2.0 }, 0).is_ok());
+    assert!(matches!(
+        validate_block_size(BlockSize { x: 0.0, y: 2.0, z: 2.0 }, 1),
+        Err(ValidationError::InvalidBlockSize { index: 1, .. })
+    ));
+    assert!(validate_block_size(BlockSize { x: 2.0, y: -2.0, z: 2.0 }, 0).is_err());
+    assert!(validate_block_size(BlockSize { x: 2.0, y: 2.0, z: MAX_BLOCK_SIZE + 1.0 }, 0).is_err());
+}
+
+#[test]
+fn test_validate_space_json_v2_properties() {
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        name: Some("V2 Level".to_string()),
+        blocks: vec![Block {
+            x: 0,
+            y: 0,
+            z: 0,
+            color: "#FF0000".to_string(),
+            material: Some("Glass".to_string()),
+            transparency: Some(0.5),
+            reflectance: Some(0.25),
+            size: Some(BlockSize { x: 4.0, y: 1.0, z: 2.0 }),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    assert!(validate_space_json(&space_json, &ValidationLimits::default()).is_ok());
+}
+
+#[test]
+fn test_validate_space_json_v2_rejects_invalid_material() {
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        name: None,
+        blocks: vec![Block {
+            x: 0,
+            y: 0,
+            z: 0,
+            color: "#FF0000".to_string(),
+            material: Some("Unobtainium".to_string()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_space_json(&space_json, &ValidationLimits::default()),
+        Err(ValidationError::InvalidMaterial { .. })
+    ));
+}
+
+#[test]
+fn test_space_json_v1_payload_deserializes_unchanged() {
+    let json = r##"{"schemaVersion":1,"name":"Old","blocks":[{"x":0,"y":0,"z":0,"color":"#FF0000"}]}"##;
+    let space_json: SpaceJSON = serde_json::from_str(json).unwrap();
+    assert!(space_json.blocks[0].material.is_none());
+    assert!(space_json.blocks[0].size.is_none());
+    assert!(validate_space_json(&space_json, &ValidationLimits::default()).is_ok());
+}
+
+#[test]
+fn test_space_json_v2_payload_deserializes() {
+    let json = r##"{"schemaVersion":2,"blocks":[{"x":0,"y":0,"z":0,"color":"#FF0000","material":"Neon","transparency":0.2,"reflectance":0.1,"size":{"x":4,"y":1,"z":2}}]}"##;
+    let space_json: SpaceJSON = serde_json::from_str(json).unwrap();
+    let block = &space_json.blocks[0];
+    assert_eq!(block.material.as_deref(), Some("Neon"));
+    assert_eq!(block.transparency, Some(0.2));
+    assert_eq!(block.size, Some(BlockSize { x: 4.0, y: 1.0, z: 2.0 }));
+    assert!(validate_space_json(&space_json, &ValidationLimits::default()).is_ok());
+}
+
+// Collect-all validation tests
+
+#[test]
+fn test_validate_space_json_all_passes_valid() {
+    let space_json = SpaceJSON {
+        schema_version: 1,
+        name: Some("Test Level".to_string()),
+        blocks: vec![
+            Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+            Block { x: 2, y: 0, z: 0, color: "#00FF00".to_string(), ..Default::default() },
+        ],
+        ..Default::default()
+    };
+    assert!(validate_space_json_all(&space_json, &ValidationLimits::default()).is_ok());
+}
+
+#[test]
+fn test_validate_space_json_all_collects_every_error() {
+    let space_json = SpaceJSON {
+        schema_version: 3,
+        name: Some("Test Level".to_string()),
+        blocks: vec![
+            Block { x: 0, y: 0, z: 0, color: "red".to_string(), ..Default::default() },
+            Block { x: 1001, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+            Block { x: 0, y: 0, z: 0, color: "#GGGGGG".to_string(), ..Default::default() },
+            Block { x: 0, y: -1, z: 0, color: "#00FF00".to_string(), ..Default::default() },
+        ],
+        ..Default::default()
+    };
+
+    let groups = validate_space_json_all(&space_json, &ValidationLimits::default()).unwrap_err();
+    let codes: Vec<_> = groups.iter().map(|g| g.code).collect();
+    assert_eq!(
+        codes,
+        vec![
+            "INVALID_SCHEMA_VERSION",
+            "COORDINATE_OUT_OF_BOUNDS",
+            "INVALID_COLOR_FORMAT",
+            "DUPLICATE_POSITION"
+        ]
+    );
+
+    let counts: Vec<_> = groups.iter().map(|g| g.count).collect();
+    assert_eq!(counts, vec![1, 2, 2, 1]);
+    assert!(matches!(
+        groups[2].errors[..],
+        [
+            ValidationError::InvalidColorFormat { index: 0, .. },
+            ValidationError::InvalidColorFormat { index: 2, .. }
+        ]
+    ));
+}
+
+#[test]
+fn test_validate_space_json_all_reports_every_duplicate() {
+    let space_json = SpaceJSON {
+        schema_version: 1,
+        name: None,
+        blocks: vec![
+            Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+            Block { x: 0, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+            Block { x: 2, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+            Block { x: 2, y: 0, z: 0, color: "#FF0000".to_string(), ..Default::default() },
+        ],
+        ..Default::default()
+    };
+
+    let groups = validate_space_json_all(&space_json, &ValidationLimits::default()).unwrap_err();
+    assert_eq!(groups.len(), 1);
+    assert!(matches!(
+        groups[0].errors[..],
+        [
+            ValidationError::DuplicatePosition { index: 1, .. },
+            ValidationError::DuplicatePosition { index: 3, .. }
+        ]
+    ));
+}
+
+#[test]
+fn test_validate_space_json_all_caps_each_code() {
+    let blocks: Vec<Block> = (0..MAX_ERRORS_PER_CODE + 25)
+        .map(|i| Block {
+            x: i as i32 * 2,
+            y: 0,
+            z: 0,
+            color: "invalid".to_string(),
+            ..Default::default()
+        })
+        .collect();
+    let space_json = SpaceJSON {
+        schema_version: 1,
+        name: None,
+        blocks,
+        ..Default::default()
+    };
+
+    let groups = validate_space_json_all(&space_json, &ValidationLimits::default()).unwrap_err();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].code, "INVALID_COLOR_FORMAT");
+    assert_eq!(groups[0].count, MAX_ERRORS_PER_CODE + 25);
+    assert_eq!(groups[0].errors.len(), MAX_ERRORS_PER_CODE);
+}
+
+#[test]
+fn test_validate_space_json_all_continues_past_block_count() {
+    let mut blocks: Vec<Block> = (0..MAX_BLOCKS + 1)
+        .map(|i| Block {
+            x: (i % 1000) as i32,
+            y: (i / 1000) as i32,
+            z: 0,
+            color: "#FF0000".to_string(),
+            ..Default::default()
+        })
+        .collect();
+    blocks[5].color = "#12".to_string();
+    let space_json = SpaceJSON {
+        schema_version: 1,
+        name: None,
+        blocks,
+        ..Default::default()
+    };
+
+    let groups = validate_space_json_all(&space_json, &ValidationLimits::default()).unwrap_err();
+    let codes: Vec<_> = groups.iter().map(|g| g.code).collect();
+    assert_eq!(codes, vec!["BLOCK_COUNT_EXCEEDED", "INVALID_COLOR_FORMAT"]);
+}
+
+// World settings tests
+
+fn world_level(schema_version: u32, world: WorldSettings) -> SpaceJSON {
+    SpaceJSON {
+        schema_version,
+        world: Some(world),
+        ..Default::default()
+    }
+}
+
+fn invalid_setting(space_json: &SpaceJSON) -> Option<&'static str> {
+    match validate_space_json(space_json, &ValidationLimits::default()) {
+        Err(ValidationError::InvalidWorldSetting { setting, .. }) => Some(setting),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_validate_world_accepts_full_settings() {
+    let world = WorldSettings {
+        baseplate: Some(BaseplateSettings {
+            enabled: Some(true),
+            size: Some(BlockSize { x: 512.0, y: 4.0, z: 512.0 }),
+            color: Some("#3A7D44".to_string()),
+            material: Some("grass".to_string()),
+        }),
+        spawn: Some(SpawnSettings {
+            auto: None,
+            position: Some(WorldPosition { x: 10.0, y: 20.5, z: -10.0 }),
+        }),
+        lighting: Some(LightingSettings {
+            technology: Some("future".to_string()),
+            time_of_day: Some(24.0),
+            ambient: Some("#000".to_string()),
+            fog: Some(FogSettings {
+                color: Some("#FFFFFF".to_string()),
+                start: Some(10.0),
+                end: Some(500.0),
+            }),
+        }),
+    };
+    assert!(validate_space_json(&world_level(2, world), &ValidationLimits::default()).is_ok());
+}
+
+#[test]
+fn test_validate_world_rejects_invalid_baseplate() {
+    let baseplate = |settings: BaseplateSettings| WorldSettings {
+        baseplate: Some(settings),
+        ..Default::default()
+    };
+
+    let too_big = baseplate(BaseplateSettings {
+        size: Some(BlockSize { x: MAX_BLOCK_SIZE + 1.0, y: 4.0, z: 100.0 }),
+        ..Default::default()
+    });
+    assert_eq!(invalid_setting(&world_level(2, too_big)), Some("baseplate.size"));
+
+    let bad_color = baseplate(BaseplateSettings {
+        color: Some("green".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(invalid_setting(&world_level(2, bad_color)), Some("baseplate.color"));
+
+    let bad_material = baseplate(BaseplateSettings {
+        material: Some("Water".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(invalid_setting(&world_level(2, bad_material)), Some("baseplate.material"));
+}
+
+#[test]
+fn test_validate_world_rejects_invalid_spawn() {
+    let conflicting = WorldSettings {
+        spawn: Some(SpawnSettings {
+            auto: Some(true),
+            position: Some(WorldPosition { x: 0.0, y: 1.0, z: 0.0 }),
+        }),
+        ..Default::default()
+    };
+    assert_eq!(invalid_setting(&world_level(2, conflicting)), Some("spawn"));
+
+    let out_of_bounds = WorldSettings {
+        spawn: Some(SpawnSettings {
+            auto: None,
+            position: Some(WorldPosition { x: 0.0, y: -5.0, z: 0.0 }),
+        }),
+        ..Default::default()
+    };
+    assert_eq!(invalid_setting(&world_level(2, out_of_bounds)), Some("spawn.position"));
+}
+
+#[test]
+fn test_validate_world_rejects_invalid_lighting() {
+    let lighting = |settings: LightingSettings| WorldSettings {
+        lighting: Some(settings),
+        ..Default::default()
+    };
+
+    let bad_technology = lighting(LightingSettings {
+        technology: Some("RayTraced".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(invalid_setting(&world_level(2, bad_technology)), Some("lighting.technology"));
+
+    let bad_time = lighting(LightingSettings {
+        time_of_day: Some(25.0),
+        ..Default::default()
+    });
+    assert_eq!(invalid_setting(&world_level(2, bad_time)), Some("lighting.timeOfDay"));
+
+    let inverted_fog = lighting(LightingSettings {
+        fog: Some(FogSettings {
+            color: None,
+            start: Some(500.0),
+            end: Some(100.0),
+        }),
+        ..Default::default()
+    });
+    assert_eq!(invalid_setting(&world_level(2, inverted_fog)), Some("lighting.fog"));
+}
+
+#[test]
+fn test_validate_space_json_all_collects_world_errors() {
+    let world = WorldSettings {
+        baseplate: Some(BaseplateSettings {
+            color: Some("#XYZ".to_string()),
+            ..Default::default()
+        }),
+        lighting: Some(LightingSettings {
+            ambient: Some("blue".to_string()),
+            time_of_day: Some(-1.0),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut space_json = world_level(2, world);
+    space_json.blocks.push(Block {
+        color: "#12".to_string(),
+        ..Default::default()
+    });
+
+    let groups = validate_space_json_all(&space_json, &ValidationLimits::default()).unwrap_err();
+    let codes: Vec<_> = groups.iter().map(|g| g.code).collect();
+    assert_eq!(codes, vec!["INVALID_COLOR_FORMAT", "INVALID_WORLD_SETTING"]);
+    assert_eq!(groups[1].count, 3);
+    assert!(groups[1].errors[0].message().contains("world.baseplate.color"));
+}
+
+// Team and spawn tests
+
+fn team(name: &str, color: Option<&str>) -> Team {
+    Team {
+        name: name.to_string(),
+        color: color.map(str::to_string),
+    }
+}
+
+fn spawn_block(x: i32, team: Option<&str>) -> Block {
+    Block {
+        x,
+        color: "#FF0000".to_string(),
+        spawn: Some(BlockSpawn {
+            team: team.map(str::to_string),
+        }),
+        ..Default::default()
+    }
+}
+
+fn spawn_point(x: f32, y: f32, team: Option<&str>) -> SpawnPoint {
+    SpawnPoint {
+        x,
+        y,
+        z: 0.0,
+        team: team.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_validate_teams_accepts_valid_setup() {
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        blocks: vec![spawn_block(0, Some("Red")), spawn_block(2, None)],
+        teams: vec![team("Red", Some("#FF0000")), team("Blue", None)],
+        spawns: vec![spawn_point(10.0, 0.5, Some("Blue"))],
+        ..Default::default()
+    };
+    assert!(validate_space_json(&space_json, &ValidationLimits::default()).is_ok());
+}
+
+#[test]
+fn test_validate_teams_rejects_unknown_team() {
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        blocks: vec![spawn_block(0, Some("Green"))],
+        teams: vec![team("Red", None)],
+        ..Default::default()
+    };
+    let error = validate_space_json(&space_json, &ValidationLimits::default()).unwrap_err();
+    assert!(matches!(
+        &error,
+        ValidationError::UnknownTeam { team, source: SpawnSource::Block, index: 0 } if team == "Green"
+    ));
+    assert_eq!(error.error_code(), "UNKNOWN_TEAM");
+
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        spawns: vec![spawn_point(0.0, 0.5, Some("Red"))],
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_space_json(&space_json, &ValidationLimits::default()),
+        Err(ValidationError::UnknownTeam { source: SpawnSource::SpawnPoint, index: 0, .. })
+    ));
+}
+
+#[test]
+fn test_validate_teams_rejects_invalid_teams() {
+    let invalid_team = |teams: Vec<Team>| {
+        let space_json = SpaceJSON {
+            schema_version: 2,
+            teams,
+            ..Default::default()
+        };
+        match validate_space_json(&space_json, &ValidationLimits::default()) {
+            Err(ValidationError::InvalidTeam { reason, .. }) => reason,
+            other => panic!("expected INVALID_TEAM, got {:?}", other),
+        }
+    };
+
+    assert!(invalid_team(vec![team("Red", None), team("Red", None)]).contains("unique"));
+    assert!(invalid_team(vec![team(" ", None)]).contains("empty"));
+    assert!(invalid_team(vec![team("Red", Some("crimson"))]).contains("crimson"));
+    // Both colors snap to Really red
+    assert!(invalid_team(vec![team("Red", Some("#FF0000")), team("Crimson", Some("#F80404"))]).contains("Really red"));
+}
+
+#[test]
+fn test_validate_spawn_point_bounds() {
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        spawns: vec![spawn_point(0.0, 0.5, None), spawn_point(5000.0, 0.5, None)],
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_space_json(&space_json, &ValidationLimits::default()),
+        Err(ValidationError::InvalidSpawnPoint { index: 1, .. })
+    ));
+}
+
+#[test]
+fn test_validate_team_and_spawn_counts() {
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        blocks: vec![spawn_block(0, Some("Red")), spawn_block(2, None)],
+        teams: vec![team("Red", None), team("Blue", None)],
+        spawns: vec![spawn_point(10.0, 0.5, Some("Blue"))],
+        ..Default::default()
+    };
+    let limits = |max_teams, max_spawns| ValidationLimits {
+        max_teams,
+        max_spawns,
+        ..Default::default()
+    };
+    assert!(validate_space_json(&space_json, &limits(2, 3)).is_ok());
+
+    let error = validate_space_json(&space_json, &limits(1, 3)).unwrap_err();
+    assert!(matches!(error, ValidationError::TeamCountExceeded { count: 2, limit: 1 }));
+    assert_eq!(error.error_code(), "TEAM_COUNT_EXCEEDED");
+
+    // Spawn blocks and spawn points both count
+    let error = validate_space_json(&space_json, &limits(2, 2)).unwrap_err();
+    assert!(matches!(error, ValidationError::SpawnCountExceeded { count: 3, limit: 2 }));
+    assert_eq!(error.error_code(), "SPAWN_COUNT_EXCEEDED");
+}
+
+#[test]
+fn test_validate_counts_skip_further_team_checks() {
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        teams: vec![team("Red", None), team("Red", None)],
+        spawns: vec![spawn_point(0.0, 0.5, Some("Green"))],
+        ..Default::default()
+    };
+    let limits = ValidationLimits {
+        max_teams: 1,
+        ..Default::default()
+    };
+    let groups = validate_space_json_all(&space_json, &limits).unwrap_err();
+    let codes: Vec<_> = groups.iter().map(|group| group.code).collect();
+    assert_eq!(codes, ["TEAM_COUNT_EXCEEDED"]);
+}
+
+#[test]
+fn test_validate_spawns_conflict_with_default_spawn_settings() {
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        spawns: vec![spawn_point(0.0, 0.5, None)],
+        world: Some(WorldSettings {
+            spawn: Some(SpawnSettings {
+                auto: Some(true),
+                position: None,
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_space_json(&space_json, &ValidationLimits::default()),
+        Err(ValidationError::InvalidWorldSetting { setting: "spawn", .. })
+    ));
+}
+
+// Gameplay block kind tests
+
+#[test]
+fn test_validate_block_kind() {
+    let kind_level = |kind: &str, schema_version: u32| SpaceJSON {
+        schema_version,
+        blocks: vec![Block {
+            color: "#FF0000".to_string(),
+            kind: Some(kind.to_string()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    assert!(validate_space_json(&kind_level("Checkpoint", 2), &ValidationLimits::default()).is_ok());
+
+    let error = validate_space_json(&kind_level("lava", 2), &ValidationLimits::default()).unwrap_err();
+    assert!(matches!(&error, ValidationError::InvalidBlockKind { kind, index: 0 } if kind == "lava"));
+    assert_eq!(error.error_code(), "INVALID_BLOCK_KIND");
+}
+
+// Structural integrity tests
+
+fn gray(x: i32, y: i32, z: i32) -> Block {
+    Block {
+        x,
+        y,
+        z,
+        color: "#808080".to_string(),
+        ..Default::default()
+    }
+}
+
+fn floating_level() -> SpaceJSON {
+    SpaceJSON {
+        schema_version: 2,
+        blocks: vec![
+            // Grounded column
+            gray(0, 0, 0),
+            gray(0, 2, 0),
+            // Touches the column only along an edge, so it floats
+            gray(2, 4, 0),
+            // Separate two-block island
+            gray(10, 6, 0),
+            gray(10, 6, 2),
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_find_floating_islands() {
+    let islands = find_floating_islands(&floating_level());
+    let indices: Vec<_> = islands.iter().map(|island| island.blocks.clone()).collect();
+    assert_eq!(indices, vec![vec![2], vec![3, 4]]);
+}
+
+#[test]
+fn test_find_floating_islands_without_baseplate() {
+    let mut space_json = floating_level();
+    space_json.world = Some(WorldSettings {
+        baseplate: Some(BaseplateSettings {
+            enabled: Some(false),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    // Nothing grounds the column once there is no baseplate
+    let islands = find_floating_islands(&space_json);
+    let indices: Vec<_> = islands.iter().map(|island| island.blocks.clone()).collect();
+    assert_eq!(indices, vec![vec![0, 1], vec![2], vec![3, 4]]);
+}
+
+#[test]
+fn test_find_floating_islands_connects_sized_blocks() {
+    let mut space_json = floating_level();
+    // A long beam resting on the column supports both islands
+    space_json.blocks.push(Block {
+        size: Some(BlockSize { x: 10.0, y: 2.0, z: 2.0 }),
+        ..gray(5, 4, 0)
+    });
+    assert_eq!(find_floating_islands(&space_json).len(), 0);
+    assert!(find_floating_islands(&SpaceJSON::default()).is_empty());
+}
+
+#[test]
+fn test_floating_blocks_only_fail_when_strict() {
+    let space_json = floating_level();
+    assert!(validate_space_json(&space_json, &ValidationLimits::default()).is_ok());
+
+    let strict = ValidationLimits {
+        reject_floating_blocks: true,
+        ..Default::default()
+    };
+    let error = validate_space_json(&space_json, &strict).unwrap_err();
+    assert_eq!(error.error_code(), "FLOATING_BLOCKS");
+    assert!(error.message().starts_with("1 block(s) starting at block index 2"), "{}", error.message());
+
+    let groups = validate_space_json_all(&space_json, &strict).unwrap_err();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].count, 2);
+    assert!(matches!(&groups[0].errors[1], ValidationError::FloatingBlocks { blocks } if blocks == &[3, 4]));
+}
+
+// Warning tests
+
+#[test]
+fn test_collect_warnings() {
+    let mut space_json = floating_level();
+    space_json.name = Some("Untitled Level".to_string());
+    space_json.blocks[1].color = "#888".to_string();
+
+    let warnings = collect_warnings(&space_json, &ValidationLimits::default());
+    let codes: Vec<_> = warnings.iter().map(|warning| warning.warning_code()).collect();
+    assert_eq!(codes, vec!["SHORT_HEX_COLOR", "DEFAULT_LEVEL_NAME", "FLOATING_BLOCKS", "FLOATING_BLOCKS"]);
+    assert_eq!(warnings[0], ValidationWarning::ShortHexColors { blocks: vec![1] });
+    assert_eq!(warnings[3].blocks(), &[3, 4]);
+}
+
+#[test]
+fn test_collect_warnings_for_clean_level() {
+    let space_json = SpaceJSON {
+        schema_version: 2,
+        name: Some("Tower".to_string()),
+        blocks: vec![gray(20, 0, 20), gray(20, 2, 20)],
+        ..Default::default()
+    };
+    assert!(collect_warnings(&space_json, &ValidationLimits::default()).is_empty());
+}
+
+#[test]
+fn test_strict_floating_blocks_are_not_warnings() {
+    let strict = ValidationLimits {
+        reject_floating_blocks: true,
+        ..Default::default()
+    };
+    let warnings = collect_warnings(&floating_level(), &strict);
+    assert!(warnings.iter().all(|warning| warning.warning_code() != "FLOATING_BLOCKS"));
+}
+